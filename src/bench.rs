@@ -0,0 +1,74 @@
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::picker::{list_known_repos, visible_repos, visible_worktrees_idx};
+use crate::{RepoConfig, WorktreeEntry, assign_hotkeys};
+
+/// Time the picker's list-building stages over `repos` synthetic repos with `worktrees`
+/// worktrees each, printing one `<stage>: <duration>` line per stage. Repo configs are written
+/// to a scratch config root that is removed afterwards; no git repos are created.
+pub(crate) fn run(repos: usize, worktrees: usize) -> anyhow::Result<()> {
+    let root = std::env::temp_dir().join(format!("gw-bench-{}", std::process::id()));
+    let res = run_in(&root, repos, worktrees);
+    std::fs::remove_dir_all(&root).ok();
+    res
+}
+
+fn run_in(cfg_root: &Path, repos: usize, worktrees: usize) -> anyhow::Result<()> {
+    let started = Instant::now();
+    for i in 0..repos {
+        let dir = cfg_root.join("repos").join(format!("{i:016x}"));
+        std::fs::create_dir_all(&dir)?;
+        let cfg = RepoConfig {
+            repo_name: format!("repo-{i}"),
+            git_common_dir: format!("/bench/repo-{i}/.git"),
+            anchor_path: format!("/bench/repo-{i}"),
+            ..Default::default()
+        };
+        std::fs::write(dir.join("config.toml"), toml::to_string_pretty(&cfg)?)?;
+    }
+    report("synthesize", started.elapsed());
+
+    let started = Instant::now();
+    let known = list_known_repos(cfg_root)?;
+    report("list_known_repos", started.elapsed());
+
+    let started = Instant::now();
+    let (vis, _, _) = visible_repos(&known, "");
+    report("visible_repos", started.elapsed());
+
+    let started = Instant::now();
+    let (filtered, _, _) = visible_repos(&known, "repo-1");
+    report("visible_repos (filtered)", started.elapsed());
+
+    let pool: Vec<char> = ('a'..='z').collect();
+    let started = Instant::now();
+    let codes = assign_hotkeys(vis.len(), &pool);
+    report("assign_hotkeys", started.elapsed());
+
+    let entries: Vec<WorktreeEntry> = (0..worktrees)
+        .map(|i| WorktreeEntry {
+            path: format!("/bench/wt/feat-{i}").into(),
+            branch: Some(format!("feat-{i}")),
+            ..Default::default()
+        })
+        .collect();
+    let started = Instant::now();
+    for _ in &known {
+        visible_worktrees_idx(&entries, "feat-1");
+    }
+    report("visible_worktrees_idx (per repo)", started.elapsed());
+
+    eprintln!(
+        "gw: {} repos ({} matched filter), {} worktrees each, {} hotkeys",
+        known.len(),
+        filtered.len(),
+        worktrees,
+        codes.len()
+    );
+    Ok(())
+}
+
+fn report(stage: &str, d: Duration) {
+    println!("{stage}: {:.3}ms", d.as_secs_f64() * 1000.0);
+}
@@ -0,0 +1,234 @@
+use serde::de::{self, Deserialize, Deserializer, Visitor, value};
+
+use crate::{GlobalConfig, Hook, RepoConfig};
+
+/// Built-in values of keys that are in effect when neither config sets them. Keys not listed
+/// here are unset by default (the feature is off or gw prompts/infers).
+const DEFAULTS: &[(&str, &str)] = &[
+    ("base", "HEAD"),
+    ("branch_exists_action", "use"),
+    ("worktree_readme", "false"),
+    ("link_worktree_git_hooks", "false"),
+    ("copy_on_new", "[]"),
+    ("copy_source", "anchor"),
+    ("open_on_go", "false"),
+    ("instant_filter", "false"),
+    ("picker_keep_cwd", "false"),
+    ("confirm_quit_with_selection", "false"),
+    ("hook_concurrency", "1"),
+    ("hook_quiet", "false"),
+    ("sort_repos", "name"),
+    ("require_clean", "false"),
+];
+
+/// Repo config fields gw maintains itself rather than settings; `gw config get` rejects them.
+const BOOKKEEPING: &[&str] = &[
+    "repo_name",
+    "git_common_dir",
+    "anchor_path",
+    "last_opened",
+    "last_branch",
+    "worktrees",
+];
+
+/// Keys `gw config get` understands: every field of [`GlobalConfig`] and [`RepoConfig`] (minus
+/// [`BOOKKEEPING`]), so new config fields are covered without being listed here.
+pub(crate) fn keys() -> Vec<&'static str> {
+    let mut keys = field_names::<GlobalConfig>();
+    for key in field_names::<RepoConfig>() {
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    keys.retain(|k| !BOOKKEEPING.contains(k));
+    keys
+}
+
+/// The field names serde's derive passes to `deserialize_struct` for `T`.
+fn field_names<T: for<'de> Deserialize<'de>>() -> Vec<&'static str> {
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldNames(&mut fields));
+    fields.to_vec()
+}
+
+/// A deserializer that only records the field names of the struct asked of it.
+struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+impl<'de> Deserializer<'de> for FieldNames<'_> {
+    type Error = value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("expected a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = fields;
+        Err(de::Error::custom("only field names are read"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier
+        ignored_any
+    }
+}
+
+/// Where an effective setting came from, in precedence order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Source {
+    Repo,
+    Global,
+    /// An environment variable (`GW_WORKTREES_DIR`).
+    Env,
+    /// Worked out from the repo's existing worktrees.
+    Inferred,
+    Default,
+}
+
+impl Source {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Source::Repo => "repo",
+            Source::Global => "global",
+            Source::Env => "env",
+            Source::Inferred => "inferred",
+            Source::Default => "default",
+        }
+    }
+}
+
+/// What [`resolve`] reads: both configs, plus what gw derives from them at runtime.
+pub(crate) struct Context<'a> {
+    pub(crate) global: &'a GlobalConfig,
+    pub(crate) repo: Option<&'a RepoConfig>,
+    /// Every hook gw runs for the repo, as `configured_hooks` lists them: global ones first, then
+    /// inherited and repo ones.
+    pub(crate) hooks: &'a [Hook],
+    /// The worktrees base `gw new` would use and where it comes from, as
+    /// `effective_worktrees_dir` reports it.
+    pub(crate) worktrees_dir: Option<(String, &'static str)>,
+}
+
+/// Resolve `key` the way gw does at runtime (repo config over global config over the built-in
+/// default). Most keys yield one value; `hooks` yields every hook that runs, as
+/// `<when>: <command>`. An empty result means the key is unset.
+pub(crate) fn resolve(ctx: &Context, key: &str) -> anyhow::Result<Vec<(String, Source)>> {
+    let Context {
+        global,
+        repo,
+        hooks,
+        ref worktrees_dir,
+    } = *ctx;
+    let known = keys();
+    if !known.contains(&key) {
+        anyhow::bail!("unknown config key {key:?} (known: {})", known.join(", "));
+    }
+    let default = DEFAULTS.iter().find(|(k, _)| *k == key).map(|(_, d)| *d);
+
+    if key == "hooks" {
+        return Ok(hooks
+            .iter()
+            .enumerate()
+            .map(|(i, h)| {
+                let source = if i < global.hooks.len() {
+                    Source::Global
+                } else {
+                    Source::Repo
+                };
+                (format!("{}: {}", h.when.as_str(), h.command), source)
+            })
+            .collect());
+    }
+
+    if key == "worktrees_dir" {
+        return Ok(worktrees_dir
+            .iter()
+            .map(|(dir, from)| {
+                let source = match *from {
+                    "repo" => Source::Repo,
+                    crate::WORKTREES_DIR_ENV => Source::Env,
+                    "inferred" => Source::Inferred,
+                    _ => Source::Global,
+                };
+                (dir.clone(), source)
+            })
+            .collect());
+    }
+
+    let repo_value = match repo {
+        Some(r) => lookup(r, &RepoConfig::default(), key)?,
+        None => None,
+    };
+    let value = match repo_value {
+        Some(v) => Some((v, Source::Repo)),
+        None => lookup(global, &GlobalConfig::default(), key)?.map(|v| (v, Source::Global)),
+    }
+    .or_else(|| default.map(|d| (d.to_string(), Source::Default)));
+    Ok(value.into_iter().collect())
+}
+
+/// `key`'s value in `config`, treating empty lists and values equal to the unset config's (a
+/// plain `false` can't be told apart from "unset") as absent.
+fn lookup<T: serde::Serialize>(config: &T, unset: &T, key: &str) -> anyhow::Result<Option<String>> {
+    let value = toml::Value::try_from(config)?;
+    let unset = toml::Value::try_from(unset)?;
+    Ok(match value.get(key) {
+        None => None,
+        Some(v) if unset.get(key) == Some(v) => None,
+        Some(toml::Value::String(s)) => Some(s.clone()),
+        Some(toml::Value::Array(a)) if a.is_empty() => None,
+        Some(v) => Some(v.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_config_field_resolves() {
+        // spec: GW-CONFIG-005
+        let known = keys();
+        for key in [
+            "require_clean",
+            "sort_repos",
+            "instant_filter",
+            "preview_cmd",
+            "picker_keep_cwd",
+            "worktrees_dir_windows",
+            "worktrees_dir_unix",
+            "hook_concurrency",
+            "hook_quiet",
+            "confirm_quit_with_selection",
+        ] {
+            assert!(known.contains(&key), "{key} is not a config key");
+        }
+        assert!(!known.contains(&"anchor_path"));
+
+        let global = GlobalConfig::default();
+        let repo = RepoConfig::default();
+        let with_repo = Context {
+            global: &global,
+            repo: Some(&repo),
+            hooks: &[],
+            worktrees_dir: None,
+        };
+        let without_repo = Context {
+            global: &global,
+            repo: None,
+            hooks: &[],
+            worktrees_dir: None,
+        };
+        for key in known {
+            resolve(&with_repo, key).unwrap_or_else(|e| panic!("{key}: {e:#}"));
+            resolve(&without_repo, key).unwrap_or_else(|e| panic!("{key}: {e:#}"));
+        }
+        let hook_quiet = resolve(&without_repo, "hook_quiet").unwrap();
+        assert_eq!(hook_quiet, [("false".to_string(), Source::Default)]);
+    }
+}
@@ -0,0 +1,474 @@
+use serde::Serialize;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::{GlobalConfig, Hook, HookPhase, RepoContext};
+
+/// What a hook run needs to know about the worktree it is setting up.
+pub(crate) struct HookContext<'a> {
+    pub(crate) repo: &'a RepoContext,
+    pub(crate) branch: &'a str,
+    /// The worktree's path; for pre-create hooks it doesn't exist yet.
+    pub(crate) wt_path: &'a Path,
+    /// The ref a new branch was created from (`GW_BASE`); empty if it already existed, `None`
+    /// outside `gw new`'s post-create hooks.
+    pub(crate) base: Option<&'a str>,
+    /// Extra variables (e.g. from `--env-file`), applied before the `GW_*` ones.
+    pub(crate) extra_env: &'a [(String, String)],
+}
+
+/// Outcome of one hook when output is captured (`--json-hooks-output`).
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct HookResult {
+    pub(crate) command: String,
+    /// `None` if the hook was killed by a signal.
+    pub(crate) exit_code: Option<i32>,
+    pub(crate) stdout: String,
+    pub(crate) stderr: String,
+    pub(crate) duration_ms: u64,
+}
+
+fn hook_command(hook: &Hook, ctx: &HookContext) -> Command {
+    #[cfg(unix)]
+    let mut cmd = {
+        let mut c = Command::new("sh");
+        c.args(["-lc", &hook.command]);
+        c
+    };
+
+    #[cfg(windows)]
+    let mut cmd = {
+        let mut c = Command::new("cmd");
+        c.args(["/C", &hook.command]);
+        c
+    };
+
+    let cwd = match hook.when {
+        HookPhase::PreCreate => ctx.repo.toplevel.as_path(),
+        HookPhase::PostCreate | HookPhase::PreRemove => ctx.wt_path,
+    };
+    cmd.current_dir(cwd)
+        .envs(ctx.extra_env.iter().map(|(k, v)| (k, v)))
+        .envs(hook_env(ctx));
+    // Its own process group, so a timeout also kills whatever the shell started. That takes it
+    // out of the terminal's foreground group; gw forwards Ctrl-C to it ([`hook_groups`]).
+    #[cfg(unix)]
+    if hook.timeout_secs.is_some() {
+        std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+    }
+    cmd
+}
+
+/// The `GW_*` variables set for hooks (in [`HOOK_ENV_VARS`] order); also printed by
+/// `gw worktree-env`.
+pub(crate) fn hook_env(ctx: &HookContext) -> Vec<(&'static str, String)> {
+    let mut env = vec![
+        (
+            "GW_WORKTREE_PATH",
+            ctx.wt_path.to_string_lossy().to_string(),
+        ),
+        ("GW_BRANCH", ctx.branch.to_string()),
+        (
+            "GW_REPO_ROOT",
+            ctx.repo.toplevel.to_string_lossy().to_string(),
+        ),
+    ];
+    if let Some(base) = ctx.base {
+        env.push(("GW_BASE", base.to_string()));
+    }
+    env
+}
+
+/// What running one hook produced; `status` is `None` if it timed out.
+struct Ran {
+    status: Option<ExitStatus>,
+    result: HookResult,
+}
+
+/// Width of the command shown in the `[hook: ...]` prefix of hook output lines.
+const PREFIX_WIDTH: usize = 24;
+
+/// Run the `phase` hooks of `hooks` in order, up to `hook_concurrency` (global config) at a
+/// time, capturing their output. With `results`, each hook's output is recorded there (also for
+/// the hooks run before a failure). Otherwise a successful hook's output is printed line by line
+/// behind a `[hook: <command>]` prefix (nothing with `hook_quiet`), in config order, and a failed
+/// hook's full output goes into the error.
+pub(crate) fn run_hooks(
+    hooks: &[Hook],
+    phase: HookPhase,
+    ctx: &HookContext,
+    global: &GlobalConfig,
+    mut results: Option<&mut Vec<HookResult>>,
+) -> anyhow::Result<()> {
+    let hooks: Vec<&Hook> = hooks.iter().filter(|h| h.when == phase).collect();
+    let jobs = global.hook_concurrency.unwrap_or(1);
+    let mut failures = Vec::new();
+    // Records a finished hook; true if the remaining hooks must not run.
+    let mut finish = |hook: &Hook, ran: Ran| -> anyhow::Result<bool> {
+        let succeeded = ran.status.is_some_and(|s| s.success());
+        let output = if results.is_none() {
+            let prefix = format!("[hook: {}] ", hook_prefix(hook));
+            if succeeded && !global.hook_quiet {
+                print_prefixed(&mut std::io::stderr(), &prefix, &ran.result.stdout)?;
+                print_prefixed(&mut std::io::stderr(), &prefix, &ran.result.stderr)?;
+            }
+            format!("{}{}", ran.result.stdout, ran.result.stderr)
+        } else {
+            String::new()
+        };
+        if let Some(results) = results.as_deref_mut() {
+            results.push(ran.result);
+        }
+        let failure = match ran.status {
+            _ if succeeded => return Ok(false),
+            Some(_) => hook.command.clone(),
+            None => format!(
+                "{} (timed out after {}s)",
+                hook.command,
+                hook.timeout_secs.unwrap_or_default()
+            ),
+        };
+        let stop = !hook.continue_on_error;
+        if !stop {
+            eprintln!("gw: hook failed (continuing): {failure}");
+        }
+        failures.push((failure, output));
+        Ok(stop)
+    };
+
+    if jobs > 1 {
+        for (hook, ran) in hooks.iter().zip(run_parallel(&hooks, ctx, jobs)) {
+            // `None`: not started because an earlier hook failed.
+            let Some(ran) = ran else { break };
+            if finish(hook, ran?)? {
+                break;
+            }
+        }
+    } else {
+        for hook in &hooks {
+            if finish(hook, run_hook(hook, ctx)?)? {
+                break;
+            }
+        }
+    }
+    let mut msg = match failures.as_slice() {
+        [] => return Ok(()),
+        [(one, _)] => format!("hook failed: {one}"),
+        many => format!(
+            "{} hooks failed: {}",
+            many.len(),
+            many.iter()
+                .map(|(f, _)| f.as_str())
+                .collect::<Vec<_>>()
+                .join("; ")
+        ),
+    };
+    for (failure, output) in &failures {
+        if !output.trim().is_empty() {
+            msg.push_str(&format!(
+                "\n--- output of {failure} ---\n{}",
+                output.trim_end()
+            ));
+        }
+    }
+    anyhow::bail!(msg)
+}
+
+/// The hook's name, or its command cut to [`PREFIX_WIDTH`] characters.
+fn hook_prefix(hook: &Hook) -> String {
+    if let Some(name) = &hook.name {
+        return name.clone();
+    }
+    let command = hook.command.trim();
+    if command.chars().count() <= PREFIX_WIDTH {
+        return command.to_string();
+    }
+    let cut: String = command.chars().take(PREFIX_WIDTH - 1).collect();
+    format!("{}…", cut.trim_end())
+}
+
+fn print_prefixed(out: &mut impl Write, prefix: &str, text: &str) -> std::io::Result<()> {
+    for line in text.lines() {
+        writeln!(out, "{prefix}{line}")?;
+    }
+    Ok(())
+}
+
+/// Run `hooks` on up to `jobs` threads; results are in `hooks` order.
+/// Once a hook without `continue_on_error` fails, no further hooks are started.
+fn run_parallel(
+    hooks: &[&Hook],
+    ctx: &HookContext,
+    jobs: usize,
+) -> Vec<Option<anyhow::Result<Ran>>> {
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let ran = Mutex::new((0..hooks.len()).map(|_| None).collect::<Vec<_>>());
+    thread::scope(|s| {
+        for _ in 0..jobs.min(hooks.len()) {
+            s.spawn(|| {
+                while !stop.load(Ordering::Relaxed) {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(hook) = hooks.get(i) else { break };
+                    let r = run_hook(hook, ctx);
+                    let failed = r
+                        .as_ref()
+                        .map_or(true, |r| !r.status.is_some_and(|s| s.success()));
+                    if failed && !hook.continue_on_error {
+                        stop.store(true, Ordering::Relaxed);
+                    }
+                    ran.lock().unwrap()[i] = Some(r);
+                }
+            });
+        }
+    });
+    ran.into_inner().unwrap()
+}
+
+/// Run one hook with its output captured, killing it after its `timeout_secs`.
+fn run_hook(hook: &Hook, ctx: &HookContext) -> anyhow::Result<Ran> {
+    let mut cmd = hook_command(hook, ctx);
+    let timeout = hook.timeout_secs.map(Duration::from_secs);
+    let started = Instant::now();
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+    let status = wait_timeout(&mut child, timeout)?;
+    // After a timeout, processes the hook started may still hold the pipes open.
+    let collect = |h: thread::JoinHandle<Vec<u8>>| match status {
+        Some(_) => String::from_utf8_lossy(&h.join().unwrap_or_default()).to_string(),
+        None => String::new(),
+    };
+    let result = HookResult {
+        command: hook.command.clone(),
+        exit_code: status.and_then(|s| s.code()),
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+        duration_ms: started.elapsed().as_millis() as u64,
+    };
+    Ok(Ran { status, result })
+}
+
+/// Wait for `child`, killing it (and on Unix its process group) once `timeout` (if any)
+/// elapses; `None` if it was killed.
+fn wait_timeout(
+    child: &mut Child,
+    timeout: Option<Duration>,
+) -> std::io::Result<Option<ExitStatus>> {
+    let Some(timeout) = timeout else {
+        return child.wait().map(Some);
+    };
+    #[cfg(unix)]
+    hook_groups::started(child.id());
+    let status = poll_until(child, Instant::now() + timeout);
+    #[cfg(unix)]
+    hook_groups::finished(child.id());
+    status
+}
+
+fn poll_until(child: &mut Child, deadline: Instant) -> std::io::Result<Option<ExitStatus>> {
+    loop {
+        #[cfg(unix)]
+        if hook_groups::interrupted() {
+            hook_groups::forward_interrupt(child);
+        }
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            #[cfg(unix)]
+            hook_groups::signal(child.id(), "KILL");
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
+/// Timed hooks run in their own process group, out of reach of the terminal's Ctrl-C. While any
+/// of them runs, gw catches SIGINT and passes it on to their groups before exiting itself.
+#[cfg(unix)]
+mod hook_groups {
+    use signal_hook::consts::SIGINT;
+    use std::process::{Child, Command, Stdio};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex, OnceLock};
+
+    struct Flags {
+        /// Set by SIGINT.
+        interrupted: Arc<AtomicBool>,
+        /// Set while no timed hook runs: SIGINT then ends gw as usual.
+        default: Arc<AtomicBool>,
+    }
+
+    static FLAGS: OnceLock<Flags> = OnceLock::new();
+    /// Process groups of the timed hooks running right now.
+    static RUNNING: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+    fn flags() -> &'static Flags {
+        FLAGS.get_or_init(|| {
+            let flags = Flags {
+                interrupted: Arc::new(AtomicBool::new(false)),
+                default: Arc::new(AtomicBool::new(true)),
+            };
+            let _ =
+                signal_hook::flag::register_conditional_default(SIGINT, Arc::clone(&flags.default));
+            let _ = signal_hook::flag::register(SIGINT, Arc::clone(&flags.interrupted));
+            flags
+        })
+    }
+
+    pub(super) fn started(group: u32) {
+        let flags = flags();
+        let mut running = RUNNING.lock().unwrap();
+        running.push(group);
+        flags.default.store(false, Ordering::SeqCst);
+    }
+
+    pub(super) fn finished(group: u32) {
+        let mut running = RUNNING.lock().unwrap();
+        running.retain(|&g| g != group);
+        if running.is_empty() {
+            flags().default.store(true, Ordering::SeqCst);
+        }
+    }
+
+    pub(super) fn interrupted() -> bool {
+        flags().interrupted.load(Ordering::SeqCst)
+    }
+
+    /// Send `signal` (a `kill -SIGNAL` name) to every process in `group`.
+    pub(super) fn signal(group: u32, signal: &str) {
+        let _ = Command::new("kill")
+            .args([&format!("-{signal}"), "--", &format!("-{group}")])
+            .stderr(Stdio::null())
+            .status();
+    }
+
+    /// Ctrl-C while timed hooks run: interrupt all of them, then exit the way Ctrl-C would
+    /// have ended gw.
+    pub(super) fn forward_interrupt(child: &mut Child) -> ! {
+        for &group in RUNNING.lock().unwrap().iter() {
+            signal(group, "INT");
+        }
+        let _ = child.wait();
+        let _ = signal_hook::low_level::emulate_default_handler(SIGINT);
+        std::process::exit(130);
+    }
+}
+
+/// Drain a child's pipe on its own thread so a chatty hook can't block on a full pipe.
+fn read_in_background(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    })
+}
+
+/// Write hook results as a JSON array to `out`, or as a single stderr line if `out` is `-`
+/// (stdout is reserved for the worktree path the shell wrapper `cd`s to).
+pub(crate) fn write_results_json(out: &Path, results: &[HookResult]) -> anyhow::Result<()> {
+    let json = serde_json::to_string(results)?;
+    if out == Path::new("-") {
+        eprintln!("{json}");
+    } else {
+        std::fs::write(out, json)?;
+    }
+    Ok(())
+}
+
+/// Variables `gw` may set for hooks: the first three for every hook, `GW_BASE` only for
+/// post-create hooks of `gw new`.
+pub(crate) const HOOK_ENV_VARS: &[&str] =
+    &["GW_WORKTREE_PATH", "GW_BRANCH", "GW_REPO_ROOT", "GW_BASE"];
+
+/// Shell builtins/keywords that can lead a command without being a binary on `PATH`.
+const SHELL_BUILTINS: &[&str] = &[
+    ".", ":", "[", "alias", "break", "case", "cd", "command", "continue", "echo", "eval", "exec",
+    "exit", "export", "false", "for", "if", "printf", "pwd", "read", "return", "set", "shift",
+    "source", "test", "trap", "true", "type", "ulimit", "umask", "unset", "wait", "while", "{",
+    "(",
+];
+
+/// Statically check a hook command (no execution): the leading program must be resolvable and
+/// every `$GW_*` reference must be a variable gw actually sets. Returns human-readable warnings.
+pub(crate) fn lint_hook_command(command: &str) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    // Leading program: first word that isn't a `KEY=value` assignment.
+    let program = command
+        .split_whitespace()
+        .find(|w| !is_env_assignment(w))
+        .map(|w| w.trim_start_matches(['(', '{']).trim_end_matches(';'));
+    if let Some(program) = program
+        && !program.is_empty()
+        && !SHELL_BUILTINS.contains(&program)
+        && !program.starts_with('$')
+        && !program_resolvable(program)
+    {
+        warnings.push(format!("`{program}` not found on PATH"));
+    }
+
+    for var in gw_var_refs(command) {
+        if !HOOK_ENV_VARS.contains(&var.as_str()) {
+            warnings.push(format!(
+                "${var} is not set by gw (available: {})",
+                HOOK_ENV_VARS.join(", ")
+            ));
+        }
+    }
+    warnings
+}
+
+fn is_env_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(k, _)| {
+        !k.is_empty()
+            && k.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !k.starts_with(|c: char| c.is_ascii_digit())
+    })
+}
+
+/// Relative paths (`./script.sh`) depend on the worktree contents, so they're not checked.
+fn program_resolvable(program: &str) -> bool {
+    if program.contains('/') {
+        let p = Path::new(program);
+        return !p.is_absolute() || p.is_file();
+    }
+    std::env::var_os("PATH").is_some_and(|path| {
+        std::env::split_paths(&path).any(|dir| {
+            let candidate = dir.join(program);
+            candidate.is_file() || (cfg!(windows) && candidate.with_extension("exe").is_file())
+        })
+    })
+}
+
+/// Names of `$GW_*` / `${GW_*}` references in `command`.
+fn gw_var_refs(command: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut rest = command;
+    while let Some(i) = rest.find('$') {
+        rest = &rest[i + 1..];
+        let body = rest.strip_prefix('{').unwrap_or(rest);
+        let name: String = body
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+            .collect();
+        if name.starts_with("GW_") && !out.contains(&name) {
+            out.push(name);
+        }
+    }
+    out
+}
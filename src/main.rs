@@ -1,60 +1,174 @@
-use clap::{Parser, Subcommand, ValueEnum};
+use anyhow::Context;
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
-use std::io::IsTerminal;
+use std::collections::BTreeMap;
+use std::io::{IsTerminal, Write};
 use std::path::{Path, PathBuf};
 
+mod bench;
+mod effective;
+mod hooks;
 mod picker;
+mod session;
+mod stats;
+mod sync;
 
 #[derive(Parser, Debug)]
 #[command(name = "gw")]
 #[command(about = "Git worktree helper", long_about = None)]
 #[command(version)]
 struct Cli {
+    /// When to use colors in the picker and prompts
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+    /// Use this config root for this run (overrides `GW_CONFIG_DIR`)
+    #[arg(long, global = true, value_name = "PATH")]
+    config_dir: Option<PathBuf>,
     #[command(subcommand)]
     command: Option<Command>,
 }
 
+/// The clap command tree behind [`Cli`] (what `gw completions` generates scripts from).
+fn cli_command() -> clap::Command {
+    Cli::command()
+}
+
+/// Make the generated zsh script complete `gw new`'s SPEC with local and remote-tracking branch
+/// names (clap only knows static values). This patches clap_complete's output, so it errors
+/// instead of quietly emitting an unpatched script if the lines it anchors on ever change.
+fn zsh_complete_branches(script: &str) -> anyhow::Result<String> {
+    const BRANCHES_FN: &str = r#"(( $+functions[__gw_branches] )) ||
+__gw_branches() {
+    local -a branches
+    branches=(${(f)"$(git for-each-ref --format='%(refname:short)' refs/heads refs/remotes 2>/dev/null)"})
+    _describe -t branches 'branch' branches "$@"
+}
+
+"#;
+    let mut out = String::with_capacity(script.len() + BRANCHES_FN.len());
+    let (mut specs, mut dispatch) = (0, 0);
+    for line in script.split_inclusive('\n') {
+        if line.contains("'::spec -- ") && line.contains(":_default'") {
+            specs += 1;
+            out.push_str(&line.replace(":_default'", ":__gw_branches'"));
+        } else {
+            if line.starts_with("if [ \"$funcstack[1]\" = \"_gw\" ]") {
+                dispatch += 1;
+                out.push_str(BRANCHES_FN);
+            }
+            out.push_str(line);
+        }
+    }
+    if specs != 1 || dispatch != 1 {
+        anyhow::bail!(
+            "unexpected zsh completion script layout (found {specs} SPEC argument line(s) and \
+             {dispatch} dispatch line(s), expected one of each)"
+        );
+    }
+    Ok(out)
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum ColorChoice {
+    /// Color when stderr is a terminal and `NO_COLOR` is unset
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+                    && std::io::stderr().is_terminal()
+            }
+        }
+    }
+}
+
+static COLOR: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(true);
+
+pub(crate) fn color_enabled() -> bool {
+    COLOR.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Theme for interactive prompts: colorful unless colors are disabled (`--color never`).
+pub(crate) fn prompt_theme() -> Box<dyn dialoguer::theme::Theme> {
+    if color_enabled() {
+        Box::new(dialoguer::theme::ColorfulTheme::default())
+    } else {
+        Box::new(dialoguer::theme::SimpleTheme)
+    }
+}
+
 #[derive(Subcommand, Debug)]
 enum Command {
     /// Print shell integration snippets
     Init {
+        /// Shell to print the wrapper for (default: detected from `$SHELL`)
+        #[arg(value_enum)]
+        shell: Option<Shell>,
+    },
+    /// Print a shell completion script for gw's subcommands and flags
+    ///
+    /// Usage: `gw completions zsh > ~/.zsh/completions/_gw` (a directory on your `fpath`).
+    Completions {
         #[arg(value_enum)]
         shell: Shell,
     },
+    /// Print a snippet that removes the shell integration (reverse of `init`)
+    ///
+    /// Usage: `eval "$(gw uninit)"`, then drop the `gw init` line from your shell rc.
+    Uninit {
+        /// Shell to print the removal for (default: detected from `$SHELL`)
+        #[arg(value_enum)]
+        shell: Option<Shell>,
+        /// Also delete the whole config root (all gw config; git repos are never touched)
+        #[arg(long)]
+        purge: bool,
+        /// Skip the confirmation prompt for `--purge`
+        #[arg(long, requires = "purge")]
+        yes: bool,
+    },
     /// List worktrees for the current repository
-    List,
+    List(ListArgs),
     /// Create a new branch + worktree
-    New {
-        /// Branch name or GitHub PR URL
-        ///
-        /// If this is a GitHub PR URL, `gw` will fetch the PR head ref and create a worktree.
-        /// Otherwise it is treated as a branch name.
-        spec: Option<String>,
-        /// Override the repo worktrees directory and persist it to config
-        #[arg(long)]
-        worktrees_dir: Option<PathBuf>,
-        /// Create the worktree at an explicit path (skips the default <worktrees_dir>/<branch>)
-        #[arg(long)]
-        path: Option<PathBuf>,
-        /// Base ref/commit to create the branch from (default: HEAD)
-        #[arg(long)]
-        base: Option<String>,
-        /// Skip running hooks
-        #[arg(long)]
-        no_hooks: bool,
+    New(Box<NewArgs>),
+    /// Print the worktree that has BRANCH checked out, creating it (as `gw new BRANCH`) if none
+    /// does
+    Switch {
+        /// Branch to jump to or create
+        branch: String,
+    },
+    /// Rename a branch (`git branch -m`) and move its worktree to the path `gw new` would give
+    /// the new name
+    ///
+    /// Prints the new path when run from inside the moved worktree, so shell integration can
+    /// follow it.
+    Rename {
+        /// Branch to rename
+        old: String,
+        /// New branch name (must not exist yet)
+        new: String,
     },
     /// Interactive picker to jump between repos/worktrees (prints selected path).
     ///
     /// Tip: running `gw` with no args does the same thing.
-    Go,
+    Go(GoArgs),
     /// Alias for `go`
-    Ls,
-    /// Interactive worktree removal: pick repo -> worktree, then remove it (no branch deletion)
+    Ls(GoArgs),
+    /// Interactive worktree removal: pick repo -> worktree, then remove it (the branch is kept
+    /// unless `--delete-branch`)
+    #[command(visible_alias = "remove")]
     Rm {
-        /// Worktree path to remove
+        /// Worktree path, or the branch checked out in it, to remove
         ///
-        /// Example: `gw rm .` (remove current worktree), or `gw rm /path/to/wt`.
-        #[arg(value_name = "PATH", conflicts_with = "path")]
+        /// Example: `gw rm .` (remove current worktree), `gw rm /path/to/wt`, or `gw rm feat`.
+        /// An existing path wins over a branch of the same name.
+        #[arg(value_name = "PATH|BRANCH", conflicts_with = "path")]
         target: Option<PathBuf>,
         /// Worktree path to remove (skips interactive picker)
         #[arg(long, value_name = "PATH", conflicts_with = "target")]
@@ -65,29 +179,536 @@ enum Command {
         /// Also remove untracked files/dirs in that worktree (passes `--force` to git)
         #[arg(long)]
         force: bool,
+        /// Print what would be done (git command, branch handling, dirty state) without removing
+        #[arg(long)]
+        dry_run: bool,
+        /// Keep the worktree's per-worktree metadata in the repo config (default: prune it)
+        #[arg(long)]
+        keep_config: bool,
+        /// After removing the worktree, delete its branch (`git branch -D`); refused if another
+        /// worktree has the branch checked out
+        #[arg(long, conflicts_with = "keep_branch")]
+        delete_branch: bool,
+        /// Keep the worktree's branch (the default)
+        #[arg(long)]
+        keep_branch: bool,
+        /// Before removing, save the worktree's uncommitted changes into DIR as
+        /// `<branch>-<timestamp>.patch` (`git diff HEAD`), plus a `.tar` of its untracked files;
+        /// once saved, a dirty worktree is removed without `--force`
+        #[arg(long, value_name = "DIR")]
+        archive: Option<PathBuf>,
+    },
+    /// Fast-forward every worktree's branch from its upstream (`git pull --ff-only`)
+    ///
+    /// Prints `<path><TAB><branch><TAB><result>` per worktree with an upstream, where result is
+    /// `advanced <old>..<new>`, `up-to-date`, `skipped-dirty` or `failed`; exits non-zero if any
+    /// failed.
+    Sync {
+        /// Number of worktrees to pull in parallel
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: u16,
+        /// Sync this known repo (name or config hash) instead of the current one
+        #[arg(long, value_name = "REPO", conflicts_with = "all_repos")]
+        repo: Option<String>,
+        /// Sync every registered repo
+        #[arg(long)]
+        all_repos: bool,
+    },
+    /// Run `git worktree prune` and drop gw's config for worktrees whose directories are gone
+    ///
+    /// Prints the pruned worktree paths. Use after deleting worktree directories by hand.
+    Prune,
+    /// Delete local branches that no worktree has checked out and that are fully merged into the
+    /// default branch
+    ///
+    /// Prints the candidate branches, then asks before deleting them with `git branch -d`. The
+    /// default branch is the repo's configured base if it is a local branch, else origin's
+    /// default branch, else the main worktree's branch.
+    PruneBranches {
+        /// Delete without asking
+        #[arg(long)]
+        yes: bool,
     },
     /// Print effective config paths/values for the current repo (if any)
-    Config,
+    Config {
+        #[command(subcommand)]
+        action: Option<ConfigCommand>,
+    },
     /// Show configured hooks (global + per-repo)
-    Hooks,
+    Hooks {
+        #[command(subcommand)]
+        action: Option<HooksCommand>,
+    },
+    /// List registered repos (`<name><TAB><anchor><TAB><git common dir>`)
+    Repos {
+        /// Merge registrations whose git common dir is the same repo on disk into one config
+        /// (newest anchor, union of hooks and worktree metadata) and delete the redundant ones
+        #[arg(long)]
+        prune_duplicates: bool,
+    },
+    /// Report worktree and dirty-worktree counts for every registered repo
+    Stats {
+        /// Print machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Save and re-open named sets of worktree paths
+    Session {
+        #[command(subcommand)]
+        action: SessionCommand,
+    },
+    /// Print `export GW_*=...` lines with the hook environment for a worktree
+    ///
+    /// Usage: `eval "$(gw worktree-env)"`. As for hooks run by `gw new`, `GW_REPO_ROOT` is the
+    /// worktree gw runs in.
+    WorktreeEnv {
+        /// Worktree to describe (default: the current one)
+        path: Option<PathBuf>,
+    },
     /// Print the current gw version
     Version,
+    /// Time picker list building over synthetic repos (for profiling)
+    #[command(hide = true)]
+    Bench {
+        /// Number of synthetic repos
+        #[arg(long, default_value_t = 1000)]
+        repos: usize,
+        /// Number of synthetic worktrees per repo
+        #[arg(long, default_value_t = 20)]
+        worktrees: usize,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigCommand {
+    /// Carry a repo's config over after it moved on disk (its git common dir, and so its hash,
+    /// changed)
+    MigrateHash {
+        /// Where the repo used to live (its old toplevel or git common dir)
+        #[arg(long, value_name = "PATH")]
+        old: PathBuf,
+        /// Where the repo lives now (default: current directory)
+        #[arg(long, value_name = "PATH")]
+        new: Option<PathBuf>,
+        /// Replace an existing config for the new location (e.g. a stub created since the move)
+        #[arg(long)]
+        force: bool,
+    },
+    /// Statically check hook commands (programs on PATH, `$GW_*` references) without running them
+    DoctorHooks,
+    /// Print the value gw uses for a config key (repo config over global config over default)
+    Get {
+        /// Config key, e.g. `base`, `worktrees_dir` or `hooks`
+        key: String,
+        /// Also print where each value comes from (`repo`, `global`, `env`, `inferred` or
+        /// `default`). Command-line
+        /// flags (e.g. `gw new --base`) aren't known here, so there is no `flag` source
+        #[arg(long)]
+        effective: bool,
+    },
+    /// Edit configured hooks
+    Hooks {
+        #[command(subcommand)]
+        action: ConfigHooksCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum HooksCommand {
+    /// Run the hook named NAME (global or repo config) in the current worktree, with the usual
+    /// `GW_*` variables
+    Run { name: String },
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigHooksCommand {
+    /// Change the order hooks run in (interactive unless `--order` is given)
+    Reorder {
+        /// New order as 0-based indices into the current list (as shown by `gw hooks`), e.g. `2,0,1`
+        #[arg(long, value_delimiter = ',', value_name = "INDICES")]
+        order: Option<Vec<usize>>,
+        /// Reorder the global hooks instead of the current repo's
+        #[arg(long)]
+        global: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum SessionCommand {
+    /// Save worktree paths under NAME (default: the current worktree)
+    Save {
+        name: String,
+        /// Worktree paths to include
+        #[arg(value_name = "PATH")]
+        paths: Vec<PathBuf>,
+    },
+    /// Print a session's paths, one per line (for a shell/multiplexer wrapper to open)
+    Open { name: String },
+    /// List saved sessions
+    List,
+}
+
+#[derive(Args, Debug, Default)]
+struct NewArgs {
+    /// Branch name or GitHub PR URL
+    ///
+    /// If this is a GitHub PR URL, `gw` will fetch the PR head ref and create a worktree.
+    /// Otherwise it is treated as a branch name.
+    spec: Option<String>,
+    /// Override the repo worktrees directory and persist it to config (`-` reads it from stdin)
+    #[arg(long)]
+    worktrees_dir: Option<PathBuf>,
+    /// Create the worktree at an explicit path (skips the default <worktrees_dir>/<branch>)
+    #[arg(long)]
+    path: Option<PathBuf>,
+    /// Base ref/commit to create the branch from (default: HEAD)
+    #[arg(long)]
+    base: Option<String>,
+    /// Skip running hooks
+    #[arg(long)]
+    no_hooks: bool,
+    /// Also write the created worktree path to this file (atomically)
+    ///
+    /// Alternative to stdout capture for shells where that is awkward.
+    #[arg(long, value_name = "PATH")]
+    cd_file: Option<PathBuf>,
+    /// `KEY=value` file to load into the hook environment (default: repo config `env_file`)
+    #[arg(long, value_name = "PATH")]
+    env_file: Option<PathBuf>,
+    /// Let `--env-file` values overwrite variables already set in the environment
+    #[arg(long)]
+    env_override: bool,
+    /// If no worktrees dir is configured, use the one inferred from existing worktrees
+    /// instead of prompting
+    #[arg(long)]
+    infer_dir: bool,
+    /// If a worktree for this exact branch already exists, skip creation and only re-run hooks
+    ///
+    /// Useful after a hook failed mid-setup.
+    #[arg(long)]
+    resume: bool,
+    /// Branch new branches from the freshly fetched default branch of the remote (e.g.
+    /// `origin/HEAD`): the `--track` remote, the only remote, or the one you pick
+    #[arg(long, conflicts_with = "base")]
+    base_remote_default: bool,
+    /// Branch new branches from the branch checked out in the current worktree (an error on
+    /// a detached HEAD)
+    #[arg(long, conflicts_with_all = ["base", "base_remote_default", "set_main_branch"])]
+    branch_from_current: bool,
+    /// Prefix for the worktree directory name (not the branch); overrides repo config
+    /// `worktree_dir_prefix`
+    #[arg(long, value_name = "PREFIX")]
+    worktree_prefix: Option<String>,
+    /// Create the worktree even if the `max_worktrees` or `min_free_disk_mb` limit is hit
+    #[arg(long)]
+    force: bool,
+    /// Delete a leftover directory at the target path (one that holds no git checkout) and
+    /// create the worktree there
+    #[arg(long)]
+    replace_leftover: bool,
+    /// Capture hook output and write per-hook results (command, exit code, stdout, stderr,
+    /// duration) as a JSON array to PATH (`-` for a single stderr line)
+    #[arg(long, value_name = "PATH")]
+    json_hooks_output: Option<PathBuf>,
+    /// What to do when the branch already exists locally (default: config
+    /// `branch_exists_action`, else `use`)
+    #[arg(long, value_enum, value_name = "ACTION")]
+    branch_exists_action: Option<BranchExistsAction>,
+    /// Fetch the base from its remote first when it is a remote ref (e.g. `origin/main`)
+    #[arg(long)]
+    prefetch: bool,
+    /// Write a `.gw-worktree.md` context file (branch, base, creation time, PR URL) into the
+    /// new worktree (default: config `worktree_readme`)
+    #[arg(long, conflicts_with = "no_worktree_readme")]
+    worktree_readme: bool,
+    /// Don't write `.gw-worktree.md` even if config `worktree_readme = true`
+    #[arg(long)]
+    no_worktree_readme: bool,
+    /// Link the main worktree's git hooks into the new worktree's effective hooks dir when it
+    /// differs, e.g. a relative `core.hooksPath` (default: config `link_worktree_git_hooks`)
+    #[arg(long)]
+    link_worktree_git_hooks: bool,
+    /// After creating the worktree, print how many commits its branch is ahead of/behind
+    /// TRUNK (default: local `main`, else `master`)
+    #[arg(long, value_name = "TRUNK", num_args = 0..=1, require_equals = true, default_missing_value = "")]
+    show_relation: Option<String>,
+    /// When the branch is fetched from a remote, only fetch history since DATE (e.g.
+    /// `2024-01-01` or `2.weeks.ago`); ignored for local/new branches. This makes the whole
+    /// repository shallow (every worktree shares it) until `git fetch --unshallow`
+    #[arg(long, value_name = "DATE")]
+    shallow_since: Option<String>,
+    /// Print the free disk space on the worktree's filesystem before creating it (creation
+    /// is refused below config `min_free_disk_mb` unless `--force`)
+    #[arg(long)]
+    print_remaining_disk: bool,
+    /// Copy config `copy_on_new` paths from the main worktree rather than the anchor (the
+    /// last used worktree); same as config `copy_source = "main"`
+    #[arg(long)]
+    copy_from_main: bool,
+    /// Also run the hooks of another known repo (name or config hash), before this repo's
+    /// (default: config `inherit_hooks_from`)
+    #[arg(long, value_name = "REPO")]
+    inherit_hooks_from: Option<String>,
+    /// Refuse to create the worktree while the current worktree has uncommitted changes
+    /// (default: repo config `require_clean`)
+    #[arg(long)]
+    require_clean: bool,
+    /// For a PR URL, check out the PR's merge ref (`refs/pull/N/merge`, the PR as it would
+    /// land) detached at `pr/N-merge`; falls back to the head ref if the PR isn't mergeable
+    #[arg(long)]
+    base_pr_merge: bool,
+    /// Use this remote (no prompt when several are configured) when looking up the branch
+    /// or PR; fails if a branch that isn't local doesn't exist there either
+    #[arg(long, value_name = "REMOTE")]
+    track: Option<String>,
+    /// Check out `--base` (default: HEAD, or SPEC if it is an existing local branch) with a
+    /// detached HEAD instead of creating a branch; SPEC only names the directory
+    #[arg(long, conflicts_with_all = ["track", "base_pr_merge", "branch_exists_action"])]
+    detach: bool,
+    /// After creating the worktree, open the PR (for a PR URL) or a GitHub compare page for
+    /// the branch in the browser (`$BROWSER`, else `open`/`xdg-open`)
+    #[arg(long)]
+    open_url: bool,
+    /// Leave the repo's anchor (the last created/selected worktree, used by the picker and
+    /// `copy_on_new`) and recency untouched, e.g. for worktrees created by automation
+    #[arg(long)]
+    keep_cwd: bool,
+    /// Save BRANCH as this repo's default base for new branches (repo config `base`, used
+    /// when `--base` isn't given) and branch from it
+    #[arg(long, value_name = "BRANCH", conflicts_with_all = ["base", "base_remote_default"])]
+    set_main_branch: Option<String>,
+    /// Store TEXT as the new branch's description (`branch.<name>.description`, as with `git
+    /// branch --edit-description`); the picker shows it next to the worktree
+    #[arg(long, value_name = "TEXT", conflicts_with = "detach")]
+    set_description: Option<String>,
+}
+
+#[derive(Args, Debug, Default)]
+struct ListArgs {
+    /// Append the number of modified + untracked files in each worktree
+    #[arg(long)]
+    untracked_count: bool,
+    /// Print paths relative to BASE (default: current directory) when they are under it
+    #[arg(long, value_name = "BASE", num_args = 0..=1, require_equals = true, default_missing_value = ".")]
+    relative: Option<PathBuf>,
+    /// Print worktrees in sections by the trunk branch they were branched from
+    #[arg(long)]
+    group_by_base: bool,
+    /// Candidate trunk branches for `--group-by-base` (only existing ones are used)
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "BRANCHES",
+        default_value = "main,master,develop",
+        requires = "group_by_base"
+    )]
+    base_candidates: Vec<String>,
+    /// Align columns with spaces (long paths are shortened to fit the terminal) instead of
+    /// separating them with tabs
+    #[arg(long)]
+    table: bool,
+    /// Print a JSON array of `{"path", "branch", "head"}` objects (`branch` is null when
+    /// detached, `head` is the full SHA or null; the bare entry also has `"bare": true`)
+    #[arg(long, conflicts_with_all = ["table", "group_by_base", "untracked_count"])]
+    json: bool,
+    /// Print only the branch of each worktree, one per line (detached worktrees are skipped)
+    #[arg(long, conflicts_with_all = ["json", "table", "group_by_base", "untracked_count", "relative"])]
+    branches_only: bool,
+}
+
+#[derive(Args, Debug, Default)]
+struct GoArgs {
+    /// Also write the selected worktree path to this file (atomically)
+    #[arg(long, value_name = "PATH")]
+    cd_file: Option<PathBuf>,
+    /// Skip the repo screen: list every repo's worktrees in one list grouped by repo
+    #[arg(long)]
+    depth_first: bool,
+    /// Open the picker with this filter already applied
+    #[arg(long, value_name = "SUBSTR")]
+    filter: Option<String>,
+    /// Also open the selected worktree in your editor (overrides config `open_on_go`)
+    #[arg(long, conflicts_with = "no_open")]
+    open: bool,
+    /// Don't open an editor even if config `open_on_go = true`
+    #[arg(long)]
+    no_open: bool,
+    /// Open the selection in a new tmux window (when inside tmux) instead of printing its path
+    #[arg(long, conflicts_with = "wezterm")]
+    tmux: bool,
+    /// Open the selection in a new WezTerm tab (when inside WezTerm) instead of printing its path
+    #[arg(long)]
+    wezterm: bool,
+    /// Copy `cd '<path>'` to the clipboard instead of printing the path (for shells without
+    /// the `gw init` wrapper; uses OSC 52 over SSH or when no clipboard tool is found)
+    #[arg(long, conflicts_with_all = ["tmux", "wezterm"])]
+    copy_cd: bool,
+    /// Initial order of the repo list (overrides config `sort_repos`; default: name)
+    #[arg(long, value_enum, value_name = "MODE")]
+    sort_repos: Option<SortRepos>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Multiplexer {
+    Tmux,
+    Wezterm,
+}
+
+impl Multiplexer {
+    fn program(self) -> &'static str {
+        match self {
+            Multiplexer::Tmux => "tmux",
+            Multiplexer::Wezterm => "wezterm",
+        }
+    }
+
+    /// Whether we're running inside this multiplexer (`$TMUX` / `$WEZTERM_PANE`).
+    fn detected(self) -> bool {
+        let var = match self {
+            Multiplexer::Tmux => "TMUX",
+            Multiplexer::Wezterm => "WEZTERM_PANE",
+        };
+        std::env::var_os(var).is_some_and(|v| !v.is_empty())
+    }
+
+    fn args(self, path: &Path, name: &str) -> Vec<std::ffi::OsString> {
+        match self {
+            Multiplexer::Tmux => vec![
+                "new-window".into(),
+                "-c".into(),
+                path.into(),
+                "-n".into(),
+                name.into(),
+            ],
+            Multiplexer::Wezterm => vec!["cli".into(), "spawn".into(), "--cwd".into(), path.into()],
+        }
+    }
+}
+
+/// Open `path` in a new multiplexer window named `name`, running `program` (normally
+/// [`Multiplexer::program`]). Output is discarded so stdout stays free for the path protocol.
+fn open_in_multiplexer(
+    mux: Multiplexer,
+    program: &str,
+    path: &Path,
+    name: &str,
+) -> anyhow::Result<()> {
+    let status = std::process::Command::new(program)
+        .args(mux.args(path, name))
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .status()
+        .with_context(|| format!("failed to run {program}"))?;
+    if !status.success() {
+        anyhow::bail!("{program} exited with {status}");
+    }
+    Ok(())
+}
+
+/// Window name for a worktree: its checked-out branch, else its directory name.
+fn worktree_window_name(path: &Path) -> String {
+    git_stdout(path, &["branch", "--show-current"])
+        .ok()
+        .map(|b| b.trim().to_string())
+        .filter(|b| !b.is_empty())
+        .or_else(|| path.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "gw".to_string())
 }
 
-#[derive(ValueEnum, Debug, Clone, Copy)]
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
 enum Shell {
     Zsh,
+    Bash,
+}
+
+impl Shell {
+    fn completion_shell(self) -> clap_complete::Shell {
+        match self {
+            Shell::Zsh => clap_complete::Shell::Zsh,
+            Shell::Bash => clap_complete::Shell::Bash,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Shell::Zsh => "zsh",
+            Shell::Bash => "bash",
+        }
+    }
+
+    fn rc_file(self) -> &'static str {
+        match self {
+            Shell::Zsh => "~/.zshrc",
+            Shell::Bash => "~/.bashrc",
+        }
+    }
+
+    /// The shell named by `$SHELL` (matched on its basename, e.g. `/bin/zsh` -> zsh), or when
+    /// that is unset, the parent process (the shell running `eval "$(gw init)"`).
+    fn detect() -> anyhow::Result<Self> {
+        let supported = || {
+            Shell::value_variants()
+                .iter()
+                .filter_map(|s| s.to_possible_value().map(|v| v.get_name().to_string()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let var = std::env::var("SHELL").unwrap_or_default();
+        let (name, source) = match Path::new(&var).file_name() {
+            Some(n) => (n.to_string_lossy().to_string(), format!("$SHELL={var}")),
+            None => match parent_process_name() {
+                Some(n) => (n, "the parent process".to_string()),
+                None => anyhow::bail!(
+                    "$SHELL is not set; pass the shell explicitly (supported: {})",
+                    supported()
+                ),
+            },
+        };
+        Shell::from_str(&name, true).map_err(|_| {
+            anyhow::anyhow!(
+                "unsupported shell `{name}` (from {source}); supported: {}",
+                supported()
+            )
+        })
+    }
+}
+
+/// Basename of the parent process's command (`ps -o comm=`), without the `-` login shells get.
+fn parent_process_name() -> Option<String> {
+    #[cfg(unix)]
+    {
+        let ppid = std::os::unix::process::parent_id();
+        let out = std::process::Command::new("ps")
+            .args(["-o", "comm=", "-p", &ppid.to_string()])
+            .output()
+            .ok()
+            .filter(|o| o.status.success())?;
+        let comm = String::from_utf8_lossy(&out.stdout);
+        let comm = comm.trim().trim_start_matches('-');
+        let name = Path::new(comm).file_name()?.to_string_lossy().to_string();
+        (!name.is_empty()).then_some(name)
+    }
+    #[cfg(not(unix))]
+    None
 }
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    COLOR.store(cli.color.enabled(), std::sync::atomic::Ordering::Relaxed);
+    if let Some(dir) = cli.config_dir {
+        let _ = CONFIG_DIR_OVERRIDE.set(dir);
+    }
 
     match cli.command {
-        Some(Command::Init { shell: Shell::Zsh }) => {
+        Some(Command::Init { shell }) => {
+            let shell = match shell {
+                Some(s) => s,
+                None => Shell::detect()?,
+            };
             // A wrapper so `gw go` can `cd` the current shell. `command gw` avoids recursion.
-            // Usage: `eval "$(gw init zsh)"`
+            // Usage: `eval "$(gw init zsh)"`. The same function body works in zsh and bash.
             println!(
-                r#"# gw shell integration (zsh)
+                r#"# gw shell integration ({})
 gw() {{
   local dest
   if [[ "$#" -eq 0 ]]; then
@@ -99,194 +720,1957 @@ gw() {{
   elif [[ "$1" == "ls" ]]; then
     dest="$(command gw ls "${{@:2}}")" || return $?
     if [[ -n "$dest" ]]; then cd "$dest" || return $?; fi
-  elif [[ "$1" == "rm" ]]; then
-    dest="$(command gw rm "${{@:2}}")" || return $?
+  elif [[ "$1" == "rm" || "$1" == "remove" ]]; then
+    dest="$(command gw "$@")" || return $?
     if [[ -n "$dest" ]]; then cd "$dest" || return $?; fi
   elif [[ "$1" == "new" ]]; then
     dest="$(command gw new "${{@:2}}")" || return $?
     if [[ -n "$dest" ]]; then cd "$dest" || return $?; fi
+  elif [[ "$1" == "switch" ]]; then
+    dest="$(command gw switch "${{@:2}}")" || return $?
+    if [[ -n "$dest" ]]; then cd "$dest" || return $?; fi
+  elif [[ "$1" == "rename" ]]; then
+    dest="$(command gw rename "${{@:2}}")" || return $?
+    if [[ -n "$dest" ]]; then cd "$dest" || return $?; fi
   else
     command gw "$@"
   fi
-}}"#
+}}"#,
+                shell.name()
             );
         }
-        Some(Command::List) => {
-            let out = std::process::Command::new("git")
-                .args(["worktree", "list", "--porcelain"])
-                .output()?;
-            if !out.status.success() {
-                anyhow::bail!(
-                    "git worktree list failed: {}",
-                    String::from_utf8_lossy(&out.stderr)
-                );
+        Some(Command::Completions { shell }) => {
+            let mut script = Vec::new();
+            clap_complete::generate(
+                shell.completion_shell(),
+                &mut cli_command(),
+                "gw",
+                &mut script,
+            );
+            let script = String::from_utf8(script)?;
+            match shell {
+                Shell::Zsh => print!("{}", zsh_complete_branches(&script)?),
+                Shell::Bash => print!("{script}"),
             }
-            let txt = String::from_utf8(out.stdout)?;
-            for entry in parse_worktree_porcelain(&txt) {
-                let branch = entry.branch.unwrap_or_else(|| "(detached)".to_string());
-                println!("{}\t{}", entry.path, branch);
+        }
+        Some(Command::Uninit { shell, purge, yes }) => {
+            let shell = match shell {
+                Some(s) => s,
+                None => Shell::detect()?,
+            };
+            println!(
+                r#"# gw shell integration removal ({name})
+unset -f gw 2>/dev/null
+# Also remove the `eval "$(gw init {name})"` line from your {rc}."#,
+                name = shell.name(),
+                rc = shell.rc_file()
+            );
+            if purge {
+                purge_config_root(&config_root()?, yes)?;
             }
         }
-        Some(Command::New {
-            spec,
-            worktrees_dir,
-            path,
-            base,
-            no_hooks,
-        }) => {
+        Some(Command::List(args)) => run_list(args)?,
+        Some(Command::New(args)) => run_new(*args)?,
+        Some(Command::Switch { branch }) => {
             let cfg_root = config_root()?;
             let repo = RepoContext::detect_from_cwd()?;
-            let spec = match spec {
-                Some(s) => s,
+            let wt = match find_branch_worktree(&repo, &branch)? {
+                Some(wt) => {
+                    eprintln!("gw: {branch} is checked out in {}", wt.display());
+                    wt
+                }
                 None => {
-                    if !std::io::stdin().is_terminal() {
-                        anyhow::bail!("no SPEC provided and no TTY available to prompt");
-                    }
-                    use dialoguer::{Input, theme::ColorfulTheme};
-                    let theme = ColorfulTheme::default();
-                    Input::with_theme(&theme)
-                        .with_prompt("Branch name or GitHub PR URL")
-                        .interact_text()?
+                    eprintln!("gw: no worktree for {branch}; creating one");
+                    create_worktree_from_spec(
+                        &repo.toplevel,
+                        &cfg_root,
+                        &branch,
+                        &NewOptions::default(),
+                        std::io::stdin().is_terminal() && std::io::stderr().is_terminal(),
+                    )?
                 }
             };
-
-            // stdout is reserved for shell integration (`gw init zsh`) to `cd` into the created worktree.
-            let wt = create_worktree_from_spec(
-                &repo.toplevel,
-                &cfg_root,
-                &spec,
-                worktrees_dir,
-                path,
-                base,
-                no_hooks,
-                std::io::stdin().is_terminal() && std::io::stderr().is_terminal(),
-            )?;
             println!("{}", wt.to_string_lossy());
         }
-        None | Some(Command::Go) | Some(Command::Ls) => {
-            let repo = RepoContext::detect_from_cwd().ok();
+        Some(Command::Rename { old, new }) => {
             let cfg_root = config_root()?;
-            if let Some(sel) = picker::pick_worktree(&cfg_root, repo)? {
-                println!("{}", sel.worktree_path.to_string_lossy());
-            } else {
-                // Shell wrapper should treat this as cancel.
-                std::process::exit(1);
+            let repo = RepoContext::detect_from_cwd()?;
+            if let Some(moved) = rename_branch(&cfg_root, &repo, &old, &new, true)?
+                && moved.had_cwd
+            {
+                println!("{}", moved.path.to_string_lossy());
             }
         }
+        None => run_go(GoArgs::default())?,
+        Some(Command::Go(args)) | Some(Command::Ls(args)) => run_go(args)?,
         Some(Command::Rm {
             target,
             path,
             yes,
             force,
+            dry_run,
+            keep_config,
+            delete_branch,
+            keep_branch: _,
+            archive,
         }) => {
-            let effective = target.or(path);
+            let cfg_root = config_root()?;
+            let opts = RemoveOptions {
+                yes,
+                force,
+                dry_run,
+                keep_config,
+                delete_branch,
+                // Absolute up front: removal may chdir out of the worktree being removed.
+                archive: archive.map(std::path::absolute).transpose()?,
+            };
+            let effective = match target {
+                Some(t) => Some(resolve_rm_target(&t)?),
+                None => path,
+            };
             if let Some(path) = effective {
                 let repo = RepoContext::detect_from_cwd()?;
-                if let Some(cd_to) = remove_worktree(&repo.toplevel, &path, yes, force)? {
+                if let Some(cd_to) = remove_worktree(&cfg_root, &repo.toplevel, &path, &opts)? {
                     println!("{}", cd_to.to_string_lossy());
                 }
             } else {
                 let repo = RepoContext::detect_from_cwd().ok();
-                let cfg_root = config_root()?;
-                let Some(sel) = picker::pick_worktree(&cfg_root, repo)? else {
+                let pick = picker::PickOptions {
+                    color: color_enabled(),
+                    ..Default::default()
+                };
+                let Some(sel) = picker::pick_worktree(&cfg_root, repo, &pick)? else {
                     std::process::exit(1);
                 };
-                if let Some(cd_to) =
-                    remove_worktree(&sel.repo_anchor, &sel.worktree_path, yes, force)?
-                {
-                    println!("{}", cd_to.to_string_lossy());
-                }
-            }
-        }
-        Some(Command::Config) => {
-            let cfg_root = config_root()?;
-            println!("config_root={}", cfg_root.to_string_lossy());
-            println!(
-                "global_config={}",
-                cfg_root.join("config.toml").to_string_lossy()
-            );
-            if let Ok(repo) = RepoContext::detect_from_cwd() {
-                println!(
-                    "repo_config={}",
-                    repo_config_path(&cfg_root, &repo).to_string_lossy()
+                if let Some(cd_to) =
+                    remove_worktree(&cfg_root, &sel.repo_anchor, &sel.worktree_path, &opts)?
+                {
+                    println!("{}", cd_to.to_string_lossy());
+                }
+            }
+        }
+        Some(Command::Prune) => {
+            let cfg_root = config_root()?;
+            let repo = RepoContext::detect_from_cwd()
+                .context("gw prune must be run inside a git repository")?;
+            let pruned = prune_worktrees(&cfg_root, &repo)?;
+            if pruned.is_empty() {
+                eprintln!("gw: nothing to prune");
+            }
+            for p in pruned {
+                println!("{}", p.display());
+            }
+        }
+        Some(Command::PruneBranches { yes }) => {
+            let cfg_root = config_root()?;
+            let repo = RepoContext::detect_from_cwd()
+                .context("gw prune-branches must be run inside a git repository")?;
+            prune_branches(&cfg_root, &repo, yes)?;
+        }
+        Some(Command::Config {
+            action: Some(ConfigCommand::MigrateHash { old, new, force }),
+        }) => {
+            let cfg_root = config_root()?;
+            let repo = match new {
+                Some(p) => RepoContext::detect_from_path(&p)?,
+                None => RepoContext::detect_from_cwd()?,
+            };
+            let path = migrate_repo_hash(&cfg_root, &old, &repo, force)?;
+            eprintln!("gw: migrated repo config to {}", path.display());
+        }
+        Some(Command::Config {
+            action: Some(ConfigCommand::DoctorHooks),
+        }) => {
+            let cfg_root = config_root()?;
+            let mut hooks: Vec<(&str, Hook)> = load_global_config(&cfg_root)?
+                .hooks
+                .into_iter()
+                .map(|h| ("global", h))
+                .collect();
+            if let Ok(repo) = RepoContext::detect_from_cwd()
+                && let Some(cfg) = load_repo_config(&cfg_root, &repo)
+            {
+                hooks.extend(cfg.hooks.into_iter().map(|h| ("repo", h)));
+            }
+            let mut problems = 0;
+            for (scope, hook) in &hooks {
+                for warning in hooks::lint_hook_command(&hook.command) {
+                    println!("warning: {scope} hook `{}`: {warning}", hook.command);
+                    problems += 1;
+                }
+            }
+            if problems > 0 {
+                anyhow::bail!("{problems} problem(s) found in {} hook(s)", hooks.len());
+            }
+            eprintln!("gw: {} hook(s) look fine", hooks.len());
+        }
+        Some(Command::Config {
+            action:
+                Some(ConfigCommand::Hooks {
+                    action: ConfigHooksCommand::Reorder { order, global },
+                }),
+        }) => {
+            let cfg_root = config_root()?;
+            if global {
+                let mut cfg = load_global_config(&cfg_root)?;
+                cfg.hooks = reorder_hooks(cfg.hooks, order)?;
+                save_global_config(&cfg_root, &cfg)?;
+            } else {
+                let repo = RepoContext::detect_from_cwd()?;
+                let Some(mut cfg) = load_repo_config(&cfg_root, &repo) else {
+                    anyhow::bail!("no repo config for {} (use --global?)", repo.repo_name);
+                };
+                cfg.hooks = reorder_hooks(cfg.hooks, order)?;
+                save_repo_config(&cfg_root, &repo, &cfg)?;
+            }
+            eprintln!("gw: hooks reordered");
+        }
+        Some(Command::Config {
+            action: Some(ConfigCommand::Get { key, effective }),
+        }) => {
+            let cfg_root = config_root()?;
+            let global = load_global_config(&cfg_root)?;
+            let repo = RepoContext::detect_from_cwd().ok();
+            let repo_cfg = repo
+                .as_ref()
+                .and_then(|repo| load_repo_config(&cfg_root, repo));
+            // The hooks `gw new` would run, inherited ones included.
+            let hooks = match (&repo, &repo_cfg) {
+                _ if key != "hooks" => Vec::new(),
+                (Some(repo), Some(repo_cfg)) => {
+                    configured_hooks(&cfg_root, repo, &global, repo_cfg, None)?
+                }
+                _ => global.hooks.clone(),
+            };
+            let worktrees_dir = match &repo {
+                Some(repo) if key == "worktrees_dir" => {
+                    effective_worktrees_dir(&global, repo, &repo_cfg.clone().unwrap_or_default())?
+                        .map(|(dir, source)| (dir.display().to_string(), source))
+                }
+                _ => None,
+            };
+            let ctx = effective::Context {
+                global: &global,
+                repo: repo_cfg.as_ref(),
+                hooks: &hooks,
+                worktrees_dir,
+            };
+            let values = effective::resolve(&ctx, &key)?;
+            if values.is_empty() {
+                eprintln!("gw: {key} is not set");
+                std::process::exit(1);
+            }
+            for (value, source) in values {
+                if effective {
+                    println!("{value}\t{}", source.as_str());
+                } else {
+                    println!("{value}");
+                }
+            }
+        }
+        Some(Command::Config { action: None }) => {
+            let cfg_root = config_root()?;
+            println!("config_root={}", cfg_root.to_string_lossy());
+            println!(
+                "global_config={}",
+                cfg_root.join("config.toml").to_string_lossy()
+            );
+            if let Ok(repo) = RepoContext::detect_from_cwd() {
+                println!(
+                    "repo_config={}",
+                    repo_config_path(&cfg_root, &repo).to_string_lossy()
+                );
+                let cfg = load_repo_config(&cfg_root, &repo).unwrap_or_default();
+                if let Some(wd) = cfg.platform_worktrees_dir() {
+                    println!("worktrees_dir={wd}");
+                }
+                let global = load_global_config(&cfg_root)?;
+                if let Some((dir, source)) = effective_worktrees_dir(&global, &repo, &cfg)? {
+                    println!("effective_worktrees_dir={}", dir.display());
+                    println!("effective_worktrees_dir_source={source}");
+                }
+            }
+        }
+        Some(Command::Hooks {
+            action: Some(HooksCommand::Run { name }),
+        }) => {
+            let cfg_root = config_root()?;
+            run_named_hook(&cfg_root, &name)?;
+        }
+        Some(Command::Hooks { action: None }) => {
+            let cfg_root = config_root()?;
+            let global = load_global_config(&cfg_root)?;
+            for h in global.hooks {
+                println!("global: {}", h.label());
+            }
+            if let Ok(repo) = RepoContext::detect_from_cwd()
+                && let Some(cfg) = load_repo_config(&cfg_root, &repo)
+            {
+                for h in cfg.hooks {
+                    println!("repo: {}", h.label());
+                }
+            }
+        }
+        Some(Command::Repos { prune_duplicates }) => {
+            let cfg_root = config_root()?;
+            if prune_duplicates {
+                let merges = prune_duplicate_repos(&cfg_root)?;
+                if merges.is_empty() {
+                    eprintln!("gw: no duplicate repo registrations");
+                }
+                for m in merges {
+                    eprintln!(
+                        "gw: merged {} registrations of {} ({}) into {}",
+                        m.count, m.name, m.git_common_dir, m.hash
+                    );
+                }
+            } else {
+                for r in picker::list_known_repos(&cfg_root)? {
+                    println!(
+                        "{}\t{}\t{}",
+                        r.name,
+                        r.anchor.display(),
+                        r.git_common_dir.display()
+                    );
+                }
+            }
+        }
+        Some(Command::Sync {
+            jobs,
+            repo,
+            all_repos,
+        }) => {
+            let cfg_root = config_root()?;
+            let scope = match repo {
+                Some(name) => sync::Scope::Repo(name),
+                None if all_repos => sync::Scope::All,
+                None => sync::Scope::Current,
+            };
+            sync::run(&cfg_root, scope, jobs.into())?;
+        }
+        Some(Command::Stats { json }) => {
+            let cfg_root = config_root()?;
+            stats::print(&stats::collect(&cfg_root)?, json)?;
+        }
+        Some(Command::Session { action }) => {
+            let cfg_root = config_root()?;
+            match action {
+                SessionCommand::Save { name, paths } => {
+                    let paths = if paths.is_empty() {
+                        vec![RepoContext::detect_from_cwd()?.toplevel]
+                    } else {
+                        paths
+                    };
+                    let file = session::save(&cfg_root, &name, &paths)?;
+                    eprintln!(
+                        "gw: saved session {name} ({} paths) to {}",
+                        paths.len(),
+                        file.display()
+                    );
+                }
+                SessionCommand::Open { name } => {
+                    for p in session::load(&cfg_root, &name)?.paths {
+                        if !Path::new(&p).is_dir() {
+                            eprintln!("gw: warning: session path no longer exists: {p}");
+                        }
+                        println!("{p}");
+                    }
+                }
+                SessionCommand::List => {
+                    for name in session::list(&cfg_root)? {
+                        println!("{name}");
+                    }
+                }
+            }
+        }
+        Some(Command::WorktreeEnv { path }) => {
+            let wt = match path {
+                Some(p) => RepoContext::detect_from_path(&p)?,
+                None => RepoContext::detect_from_cwd()?,
+            };
+            // The repo as `create_worktree` sees it: the worktree `gw` runs in, when that's in
+            // the same repo, else the described worktree itself.
+            let repo = RepoContext::detect_from_cwd()
+                .ok()
+                .filter(|cwd| cwd.git_common_dir == wt.git_common_dir)
+                .unwrap_or_else(|| wt.clone());
+            let branch = git_stdout(&wt.toplevel, &["branch", "--show-current"])?;
+            let ctx = hooks::HookContext {
+                repo: &repo,
+                branch: branch.trim(),
+                wt_path: &wt.toplevel,
+                base: None,
+                extra_env: &[],
+            };
+            for (key, value) in hooks::hook_env(&ctx) {
+                println!("export {key}={}", shell_quote(&value));
+            }
+        }
+        Some(Command::Version) => {
+            println!("{}", env!("CARGO_PKG_VERSION"));
+        }
+        Some(Command::Bench { repos, worktrees }) => bench::run(repos, worktrees)?,
+    }
+
+    Ok(())
+}
+
+/// Delete the config root (`gw uninit --purge`), reporting what was removed on stderr.
+fn purge_config_root(cfg_root: &Path, yes: bool) -> anyhow::Result<()> {
+    if !cfg_root.exists() {
+        eprintln!(
+            "gw: nothing to purge: {} does not exist",
+            cfg_root.display()
+        );
+        return Ok(());
+    }
+    // Guard against a misconfigured root pointing at something that isn't gw's own directory.
+    if cfg_root.join(".git").exists()
+        || cfg_root.parent().is_none()
+        || dirs::home_dir().is_some_and(|h| h == cfg_root)
+    {
+        anyhow::bail!(
+            "refusing to purge {}: does not look like a gw config dir",
+            cfg_root.display()
+        );
+    }
+
+    if !yes {
+        if !(std::io::stdin().is_terminal() && std::io::stderr().is_terminal()) {
+            anyhow::bail!("refusing to prompt without a TTY; re-run with --yes");
+        }
+        let ok = dialoguer::Confirm::with_theme(&*prompt_theme())
+            .with_prompt(format!(
+                "Delete {} and all gw config in it?",
+                cfg_root.display()
+            ))
+            .default(false)
+            .interact()?;
+        if !ok {
+            anyhow::bail!("purge cancelled");
+        }
+    }
+
+    let repos = cfg_root.join("repos");
+    let repo_count = match std::fs::read_dir(&repos) {
+        Ok(rd) => rd.count(),
+        Err(_) => 0,
+    };
+    std::fs::remove_dir_all(cfg_root)
+        .with_context(|| format!("failed to remove {}", cfg_root.display()))?;
+    eprintln!(
+        "gw: removed {} (global config and {repo_count} repo configs); git repos and worktrees were not touched",
+        cfg_root.display()
+    );
+    Ok(())
+}
+
+/// `gw new`: create the worktree for SPEC (prompting for it on a TTY) and print its path.
+fn run_new(args: NewArgs) -> anyhow::Result<()> {
+    let NewArgs {
+        spec,
+        worktrees_dir,
+        path,
+        base,
+        no_hooks,
+        cd_file,
+        env_file,
+        env_override,
+        infer_dir,
+        resume,
+        base_remote_default,
+        branch_from_current,
+        worktree_prefix,
+        force,
+        replace_leftover,
+        json_hooks_output,
+        branch_exists_action,
+        prefetch,
+        worktree_readme,
+        no_worktree_readme,
+        link_worktree_git_hooks,
+        show_relation,
+        shallow_since,
+        print_remaining_disk,
+        copy_from_main,
+        inherit_hooks_from,
+        require_clean,
+        base_pr_merge,
+        track,
+        detach,
+        open_url,
+        keep_cwd,
+        set_main_branch,
+        set_description,
+    } = args;
+    let cfg_root = config_root()?;
+    let repo = RepoContext::detect_from_cwd()?;
+    if let Some(main_branch) = &set_main_branch {
+        set_repo_base(&cfg_root, &repo, main_branch)?;
+    }
+    if require_clean
+        || load_repo_config(&cfg_root, &repo).is_some_and(|c| c.require_clean == Some(true))
+    {
+        let n = count_uncommitted(&repo.toplevel)?;
+        if n > 0 {
+            anyhow::bail!(
+                "{} has {n} uncommitted entries; commit or `git stash` them first (require_clean)",
+                repo.toplevel.display()
+            );
+        }
+    }
+    let spec = match spec {
+        Some(s) => s,
+        None => {
+            if !std::io::stdin().is_terminal() {
+                anyhow::bail!("no SPEC provided and no TTY available to prompt");
+            }
+            let theme = prompt_theme();
+            dialoguer::Input::with_theme(&*theme)
+                .with_prompt("Branch name or GitHub PR URL")
+                .validate_with(|s: &String| check_spec_not_option(s.trim()))
+                .interact_text()?
+        }
+    };
+
+    let base = if branch_from_current {
+        Some(current_branch(&repo.toplevel)?)
+    } else {
+        base
+    };
+
+    let worktrees_dir = match worktrees_dir {
+        Some(p) if p.as_os_str() == "-" => Some(read_dir_from_stdin()?),
+        Some(p) => Some(expand_path(&p.to_string_lossy())?),
+        None => None,
+    };
+
+    // stdout is reserved for shell integration (`gw init zsh`) to `cd` into the created worktree.
+    let opts = NewOptions {
+        worktrees_dir,
+        path,
+        base,
+        no_hooks,
+        env_file,
+        env_override,
+        infer_dir,
+        resume,
+        base_remote_default,
+        worktree_prefix,
+        force,
+        replace_leftover,
+        json_hooks_output,
+        branch_exists_action,
+        prefetch,
+        worktree_readme: if worktree_readme {
+            Some(true)
+        } else if no_worktree_readme {
+            Some(false)
+        } else {
+            None
+        },
+        link_worktree_git_hooks,
+        show_relation,
+        shallow_since,
+        print_remaining_disk,
+        copy_source: copy_from_main.then_some(CopySource::Main),
+        inherit_hooks_from,
+        base_pr_merge,
+        track,
+        detach,
+        keep_cwd,
+        ..Default::default()
+    };
+    let wt = create_worktree_from_spec(
+        &repo.toplevel,
+        &cfg_root,
+        &spec,
+        &opts,
+        std::io::stdin().is_terminal() && std::io::stderr().is_terminal(),
+    )?;
+    if let Some(text) = &set_description {
+        set_branch_description(&wt, text)?;
+    }
+    if let Some(cd_file) = cd_file {
+        write_cd_file(&cd_file, &wt)?;
+    }
+    if open_url {
+        match new_worktree_url(&repo, &spec, &wt, opts.track.as_deref()) {
+            Some(url) => match open_in_browser(&url) {
+                Ok(()) => eprintln!("gw: opened {url}"),
+                Err(e) => eprintln!("gw: failed to open {url}: {e:#}"),
+            },
+            None => eprintln!(
+                "gw: --open-url: no PR URL or GitHub remote to build a URL from (with several remotes, pass --track or set the branch's upstream); skipping"
+            ),
+        }
+    }
+    println!("{}", wt.to_string_lossy());
+    Ok(())
+}
+
+fn run_list(args: ListArgs) -> anyhow::Result<()> {
+    let mut entries = list_worktrees(Path::new("."))?;
+
+    let relative_base = match &args.relative {
+        Some(base) => Some(
+            std::fs::canonicalize(base)
+                .with_context(|| format!("invalid --relative base {}", base.display()))?,
+        ),
+        None => None,
+    };
+
+    if args.json {
+        if let Some(base) = &relative_base {
+            for e in &mut entries {
+                e.path = relative_to(&e.path, base).into();
+            }
+        }
+        println!("{}", serde_json::to_string(&entries)?);
+        return Ok(());
+    }
+    if args.branches_only {
+        for branch in entries.iter().filter_map(|e| e.branch.as_deref()) {
+            println!("{branch}");
+        }
+        return Ok(());
+    }
+
+    // One `git status --porcelain` per worktree, in parallel.
+    let counts: Vec<Option<usize>> = if args.untracked_count {
+        std::thread::scope(|s| {
+            let handles: Vec<_> = entries
+                .iter()
+                .map(|e| s.spawn(|| count_uncommitted(&e.path).ok()))
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().ok().flatten())
+                .collect()
+        })
+    } else {
+        Vec::new()
+    };
+
+    let bases: Vec<Option<String>> = if args.group_by_base {
+        let candidates: Vec<&String> = args
+            .base_candidates
+            .iter()
+            .filter(|c| {
+                git_stdout(
+                    Path::new("."),
+                    &[
+                        "show-ref",
+                        "--verify",
+                        "--quiet",
+                        &format!("refs/heads/{c}"),
+                    ],
+                )
+                .is_ok()
+            })
+            .collect();
+        entries
+            .iter()
+            .map(|e| closest_base(&e.path, &candidates))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut rows = Vec::new();
+    for (i, entry) in entries.into_iter().enumerate() {
+        let head = entry.short_head().unwrap_or("-").to_string();
+        let path = match &relative_base {
+            Some(base) => relative_to(&entry.path, base),
+            None => entry.display_path().into_owned(),
+        };
+        let branch = entry.branch.unwrap_or_else(|| "(detached)".to_string());
+        let mut row = vec![path, branch, head];
+        match counts.get(i) {
+            Some(Some(n)) => row.push(n.to_string()),
+            Some(None) => row.push("?".to_string()),
+            None => {}
+        }
+        rows.push(row);
+    }
+    let lines = if args.table {
+        let width = std::io::stdout()
+            .is_terminal()
+            .then(|| crossterm::terminal::size().ok())
+            .flatten()
+            .map(|(cols, _)| cols as usize);
+        format_table(&rows, width)
+    } else {
+        rows.iter().map(|r| r.join("\t")).collect()
+    };
+
+    if !args.group_by_base {
+        for line in lines {
+            println!("{line}");
+        }
+        return Ok(());
+    }
+    // Sections in candidate order, then worktrees with no candidate in their history.
+    let sections = args.base_candidates.iter().map(Some).chain([None]);
+    let mut first = true;
+    for section in sections {
+        let members: Vec<&String> = lines
+            .iter()
+            .zip(&bases)
+            .filter(|(_, b)| b.as_ref() == section)
+            .map(|(l, _)| l)
+            .collect();
+        if members.is_empty() {
+            continue;
+        }
+        if !first {
+            println!();
+        }
+        first = false;
+        println!("{}:", section.map_or("(no base)", |s| s.as_str()));
+        for line in members {
+            println!("{line}");
+        }
+    }
+    Ok(())
+}
+
+/// Space-aligned `gw list --table` lines: each column padded to its widest cell (the last one is
+/// left unpadded). If that exceeds `max_width`, the first (path) column is shortened, keeping
+/// the end of each path behind a leading `…`.
+fn format_table(rows: &[Vec<String>], max_width: Option<usize>) -> Vec<String> {
+    const SEP: &str = "  ";
+    let ncols = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut widths = vec![0; ncols];
+    for row in rows {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+    if let Some(max) = max_width
+        && ncols > 0
+    {
+        let total = widths.iter().sum::<usize>() + SEP.len() * (ncols - 1);
+        if total > max {
+            // Never shrink below a few characters of path.
+            widths[0] = widths[0].saturating_sub(total - max).max(8);
+        }
+    }
+    rows.iter()
+        .map(|row| {
+            let mut line = String::new();
+            for (i, cell) in row.iter().enumerate() {
+                let cell = if i == 0 {
+                    truncate_start(cell, widths[0])
+                } else {
+                    cell.clone()
+                };
+                if i + 1 < row.len() {
+                    line.push_str(&format!("{cell:<width$}{SEP}", width = widths[i]));
+                } else {
+                    line.push_str(&cell);
+                }
+            }
+            line
+        })
+        .collect()
+}
+
+/// `s` cut to `width` characters by replacing its start with `…`.
+fn truncate_start(s: &str, width: usize) -> String {
+    let len = s.chars().count();
+    if len <= width {
+        return s.to_string();
+    }
+    let keep = width.saturating_sub(1);
+    format!("…{}", s.chars().skip(len - keep).collect::<String>())
+}
+
+/// The candidate branch the worktree's `HEAD` most likely forked from: the one whose merge-base
+/// with `HEAD` is the fewest commits behind `HEAD`, ties going to the candidate whose own tip is
+/// closest to that merge-base (so a branch off `main` isn't claimed by an untouched `develop`).
+fn closest_base(wt: &Path, candidates: &[&String]) -> Option<String> {
+    let count = |range: &str| -> Option<usize> {
+        git_stdout(wt, &["rev-list", "--count", range])
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+    };
+    candidates
+        .iter()
+        .filter_map(|c| {
+            let mb = git_stdout(wt, &["merge-base", "HEAD", c]).ok()?;
+            let mb = mb.trim();
+            let ahead = count(&format!("{mb}..HEAD"))?;
+            let candidate_ahead = count(&format!("{mb}..{c}"))?;
+            Some(((ahead, candidate_ahead), c.to_string()))
+        })
+        .min_by_key(|(key, _)| *key)
+        .map(|(_, c)| c)
+}
+
+/// `path` relative to `base` if it is under it (`.` for `base` itself), else unchanged.
+fn relative_to(path: &Path, base: &Path) -> String {
+    let canon = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    match canon.strip_prefix(base) {
+        Ok(rest) if rest.as_os_str().is_empty() => ".".to_string(),
+        Ok(rest) => rest.to_string_lossy().to_string(),
+        Err(_) => path.to_string_lossy().to_string(),
+    }
+}
+
+fn run_go(args: GoArgs) -> anyhow::Result<()> {
+    let repo = RepoContext::detect_from_cwd().ok();
+    let cfg_root = config_root()?;
+    let global = load_global_config(&cfg_root)?;
+    let opts = picker::PickOptions {
+        depth_first: args.depth_first,
+        color: color_enabled(),
+        filter: args.filter.clone(),
+        sort_repos: args.sort_repos.or(global.sort_repos).unwrap_or_default(),
+        instant_filter: global.instant_filter,
+        preview_cmd: global.preview_cmd.clone(),
+        keep_cwd: global.picker_keep_cwd,
+        confirm_quit: global.confirm_quit_with_selection,
+    };
+    if let Some(sel) = picker::pick_worktree(&cfg_root, repo, &opts)? {
+        if let Some(cd_file) = args.cd_file {
+            write_cd_file(&cd_file, &sel.worktree_path)?;
+        }
+        let open = if args.open {
+            true
+        } else if args.no_open {
+            false
+        } else {
+            global.open_on_go
+        };
+        if open {
+            match resolve_editor(&global) {
+                Some(editor) => {
+                    if let Err(e) = open_in_editor(&editor, &sel.worktree_path) {
+                        eprintln!("gw: failed to launch editor `{editor}`: {e:#}");
+                    }
+                }
+                None => eprintln!(
+                    "gw: open_on_go is set but no editor is configured ($VISUAL/$EDITOR or config `editor`)"
+                ),
+            }
+        }
+        if args.copy_cd {
+            let cmd = cd_command(&sel.worktree_path);
+            let via = copy_to_clipboard(&cmd)?;
+            eprintln!("gw: copied `{cmd}` to the clipboard ({via})");
+            return Ok(());
+        }
+        let mux = if args.tmux {
+            Some(Multiplexer::Tmux)
+        } else if args.wezterm {
+            Some(Multiplexer::Wezterm)
+        } else {
+            None
+        };
+        if let Some(mux) = mux {
+            if mux.detected() {
+                let name = worktree_window_name(&sel.worktree_path);
+                match open_in_multiplexer(mux, mux.program(), &sel.worktree_path, &name) {
+                    Ok(()) => return Ok(()),
+                    Err(e) => eprintln!("gw: {e:#}; printing path instead"),
+                }
+            } else {
+                eprintln!(
+                    "gw: not running inside {}; printing path instead",
+                    mux.program()
+                );
+            }
+        }
+        println!("{}", sel.worktree_path.to_string_lossy());
+    } else {
+        // Shell wrapper should treat this as cancel.
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Editor for `open_on_go`: config `editor`, then `$VISUAL`, then `$EDITOR`.
+fn resolve_editor(global: &GlobalConfig) -> Option<String> {
+    global
+        .editor
+        .clone()
+        .or_else(|| std::env::var("VISUAL").ok())
+        .or_else(|| std::env::var("EDITOR").ok())
+        .filter(|e| !e.trim().is_empty())
+}
+
+/// Launch `editor` on `path` detached, with all stdio redirected to null so nothing leaks into
+/// the stdout path capture used by the shell wrapper. `editor` may include arguments.
+fn open_in_editor(editor: &str, path: &Path) -> anyhow::Result<()> {
+    #[cfg(unix)]
+    let mut cmd = {
+        let mut c = std::process::Command::new("sh");
+        c.args(["-c", &format!("{editor} \"$1\""), "sh"]).arg(path);
+        c
+    };
+
+    #[cfg(windows)]
+    let mut cmd = {
+        let mut c = std::process::Command::new("cmd");
+        c.args(["/C", editor]).arg(path);
+        c
+    };
+
+    cmd.current_dir(path)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()?;
+    Ok(())
+}
+
+/// What `gw new --open-url` opens: the PR URL itself, else a GitHub compare page for the new
+/// worktree's branch on the `--track` remote, the only remote, or the branch's configured
+/// upstream remote (`branch.<b>.remote`). `None` when there is nothing to link to (detached
+/// worktree, no GitHub remote, several remotes and none picked).
+fn new_worktree_url(
+    repo: &RepoContext,
+    spec: &str,
+    wt: &Path,
+    track: Option<&str>,
+) -> Option<String> {
+    if parse_github_pr_url(spec).is_some() {
+        return Some(spec.trim().to_string());
+    }
+    let branch = git_stdout(wt, &["branch", "--show-current"]).ok()?;
+    let branch = branch.trim();
+    let remotes = list_remotes(repo).ok()?;
+    let remote = match track {
+        Some(r) => r.to_string(),
+        None if remotes.len() == 1 => remotes[0].clone(),
+        None => git_stdout(wt, &["config", &format!("branch.{branch}.remote")])
+            .ok()?
+            .trim()
+            .to_string(),
+    };
+    let remote_url = git_stdout(&repo.toplevel, &["remote", "get-url", &remote]).ok()?;
+    github_compare_url(&remote_url, branch)
+}
+
+/// `https://github.com/OWNER/REPO/compare/BRANCH?expand=1` for a GitHub remote URL.
+fn github_compare_url(remote_url: &str, branch: &str) -> Option<String> {
+    let (host, owner, repo) = parse_github_remote_url(remote_url)?;
+    if host != "github.com" || branch.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "https://{host}/{owner}/{repo}/compare/{branch}?expand=1"
+    ))
+}
+
+/// Open `url` with `$BROWSER` (a command, run through the shell) or the platform opener.
+fn open_in_browser(url: &str) -> anyhow::Result<()> {
+    let mut cmd = match std::env::var("BROWSER")
+        .ok()
+        .filter(|b| !b.trim().is_empty())
+    {
+        #[cfg(unix)]
+        Some(browser) => {
+            let mut c = std::process::Command::new("sh");
+            c.args(["-c", &format!("{browser} \"$1\""), "sh", url]);
+            c
+        }
+        #[cfg(windows)]
+        Some(browser) => {
+            let mut c = std::process::Command::new("cmd");
+            c.args(["/C", &browser, url]);
+            c
+        }
+        None if cfg!(target_os = "macos") => {
+            let mut c = std::process::Command::new("open");
+            c.arg(url);
+            c
+        }
+        None if cfg!(windows) => {
+            let mut c = std::process::Command::new("cmd");
+            c.args(["/C", "start", "", url]);
+            c
+        }
+        None => {
+            let mut c = std::process::Command::new("xdg-open");
+            c.arg(url);
+            c
+        }
+    };
+    // stdout is reserved for the worktree path.
+    let status = cmd
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .status()?;
+    if !status.success() {
+        anyhow::bail!("opener exited with {status}");
+    }
+    Ok(())
+}
+
+/// Expand `~`, `$VAR` and `${VAR}` in a user-supplied directory. Undefined variables are an
+/// error rather than silently expanding to nothing.
+fn expand_path(raw: &str) -> anyhow::Result<PathBuf> {
+    let expanded = shellexpand::full(raw)
+        .map_err(|e| anyhow::anyhow!("{raw}: environment variable ${} is not set", e.var_name))?;
+    Ok(PathBuf::from(expanded.as_ref()))
+}
+
+/// Read a single directory path line from stdin (`--worktrees-dir -`), expanded like
+/// [`expand_path`].
+fn read_dir_from_stdin() -> anyhow::Result<PathBuf> {
+    let mut line = String::new();
+    std::io::stdin()
+        .read_line(&mut line)
+        .context("failed to read worktrees dir from stdin")?;
+    let line = line.trim();
+    if line.is_empty() {
+        anyhow::bail!("--worktrees-dir -: no path on stdin");
+    }
+    expand_path(line)
+}
+
+/// Write `dest` to `cd_file` via temp file + rename, so a reader never sees a partial path.
+fn write_cd_file(cd_file: &Path, dest: &Path) -> anyhow::Result<()> {
+    let dir = match cd_file.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let name = cd_file
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("invalid --cd-file path: {}", cd_file.display()))?;
+    std::fs::create_dir_all(&dir)?;
+    let tmp = dir.join(format!(
+        ".{}.{}.tmp",
+        name.to_string_lossy(),
+        std::process::id()
+    ));
+    std::fs::write(&tmp, dest.to_string_lossy().as_bytes())?;
+    if let Err(e) = std::fs::rename(&tmp, cd_file) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Options for `gw new`, shared by the CLI and the picker's `n` flow.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct NewOptions {
+    pub(crate) worktrees_dir: Option<PathBuf>,
+    pub(crate) path: Option<PathBuf>,
+    pub(crate) base: Option<String>,
+    pub(crate) no_hooks: bool,
+    pub(crate) env_file: Option<PathBuf>,
+    pub(crate) env_override: bool,
+    pub(crate) infer_dir: bool,
+    pub(crate) resume: bool,
+    pub(crate) base_remote_default: bool,
+    pub(crate) worktree_prefix: Option<String>,
+    pub(crate) force: bool,
+    pub(crate) replace_leftover: bool,
+    pub(crate) json_hooks_output: Option<PathBuf>,
+    pub(crate) branch_exists_action: Option<BranchExistsAction>,
+    pub(crate) prefetch: bool,
+    /// `Some` overrides config `worktree_readme`.
+    pub(crate) worktree_readme: Option<bool>,
+    /// PR URL the worktree was created from (recorded in `.gw-worktree.md`).
+    pub(crate) pr_url: Option<String>,
+    /// Force-enables config `link_worktree_git_hooks`.
+    pub(crate) link_worktree_git_hooks: bool,
+    /// `--show-relation`: trunk to compare the new branch with (empty: detect `main`/`master`).
+    pub(crate) show_relation: Option<String>,
+    /// `git fetch --shallow-since` for remote-derived branches.
+    pub(crate) shallow_since: Option<String>,
+    pub(crate) print_remaining_disk: bool,
+    /// Resolved from config `min_free_disk_mb` (repo, then global) by [`create_worktree`].
+    pub(crate) min_free_disk_mb: Option<u64>,
+    /// Overrides config `copy_source`.
+    pub(crate) copy_source: Option<CopySource>,
+    /// Overrides config `inherit_hooks_from`.
+    pub(crate) inherit_hooks_from: Option<String>,
+    /// `--base-pr-merge`: use the PR's merge ref instead of its head.
+    pub(crate) base_pr_merge: bool,
+    /// Check out `base` (default `HEAD`) detached; the branch name only names the directory.
+    pub(crate) detach: bool,
+    /// `--track`: remote to use instead of [`choose_remote`].
+    pub(crate) track: Option<String>,
+    /// `--keep-cwd`: don't repoint `anchor_path` at the new worktree.
+    pub(crate) keep_cwd: bool,
+    /// Remote whose freshly fetched `<remote>/<branch>` the new local branch should track.
+    pub(crate) upstream_remote: Option<String>,
+    /// Remote already chosen for this branch, whose default branch `remote-default` means.
+    pub(crate) base_remote: Option<String>,
+}
+
+/// What `gw new` does when the requested branch already exists locally.
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum BranchExistsAction {
+    /// Check out the existing branch in the new worktree
+    #[default]
+    Use,
+    /// Fail
+    Error,
+    /// Create a new branch `<branch>-2` (or the next free suffix) instead
+    NewSuffix,
+}
+
+/// Initial order of the picker's repo list.
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum SortRepos {
+    /// Alphabetically by repo name
+    #[default]
+    Name,
+    /// Most recently opened first (repos never opened through the picker go last)
+    Recency,
+    /// By anchor path
+    Path,
+}
+
+/// Which worktree `copy_on_new` paths are copied from.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum CopySource {
+    /// The repo's anchor (the worktree last created or selected)
+    #[default]
+    Anchor,
+    /// The main worktree (first `git worktree list` entry)
+    Main,
+}
+
+pub(crate) fn create_worktree(
+    repo_cwd: &Path,
+    cfg_root: &Path,
+    branch: &str,
+    opts: &NewOptions,
+) -> anyhow::Result<PathBuf> {
+    let repo = RepoContext::detect_from_path(repo_cwd)?;
+    let global_cfg = load_global_config(cfg_root)?;
+
+    let mut repo_cfg = load_repo_config(cfg_root, &repo).unwrap_or_else(|| RepoConfig {
+        repo_name: repo.repo_name.clone(),
+        git_common_dir: repo.git_common_dir.to_string_lossy().to_string(),
+        anchor_path: repo.toplevel.to_string_lossy().to_string(),
+        ..Default::default()
+    });
+
+    let mut hooks = Vec::new();
+    let mut extra_env = Vec::new();
+    if !opts.no_hooks {
+        let env_file = opts.env_file.clone().or_else(|| {
+            repo_cfg
+                .env_file
+                .as_deref()
+                .map(|p| repo.toplevel.join(shellexpand::tilde(p).as_ref()))
+        });
+        if let Some(p) = env_file {
+            extra_env = hook_env_from_file(&p, opts.env_override)?;
+        }
+
+        hooks = configured_hooks(
+            cfg_root,
+            &repo,
+            &global_cfg,
+            &repo_cfg,
+            opts.inherit_hooks_from.as_deref(),
+        )?;
+    }
+    let mut hook_results = Vec::new();
+    let run_phase = |phase: HookPhase,
+                     wt_path: &Path,
+                     branch: &str,
+                     base: Option<&str>,
+                     results: &mut Vec<hooks::HookResult>|
+     -> anyhow::Result<()> {
+        let ctx = hooks::HookContext {
+            repo: &repo,
+            branch,
+            wt_path,
+            base,
+            extra_env: &extra_env,
+        };
+        let capture = opts.json_hooks_output.as_ref().map(|_| &mut *results);
+        let res = hooks::run_hooks(&hooks, phase, &ctx, &global_cfg, capture);
+        if res.is_err()
+            && let Some(out) = &opts.json_hooks_output
+        {
+            hooks::write_results_json(out, results)?;
+        }
+        res
+    };
+
+    // Serialize creating the worktree and writing the repo config with other `gw new`s in this
+    // repo. Pre-create hooks run under the lock (they may veto the path it guards), so a slow one
+    // holds up other runs, which give up after REPO_LOCK_TIMEOUT; post-create hooks run after the
+    // lock is released. Reload the config under the lock so a concurrent run's changes aren't
+    // overwritten.
+    let lock = RepoLock::acquire(cfg_root, &repo, REPO_LOCK_TIMEOUT)?;
+    if let Some(fresh) = load_repo_config(cfg_root, &repo) {
+        repo_cfg = fresh;
+    }
+    let existing = if opts.resume {
+        find_branch_worktree(&repo, branch)?
+    } else {
+        None
+    };
+    let (wt_path, base) = match existing {
+        Some(p) => {
+            eprintln!(
+                "gw: resuming: worktree for {branch} already exists at {}; re-running hooks",
+                p.display()
+            );
+            (p, None)
+        }
+        None => {
+            if let Some(max) = repo_cfg.max_worktrees.or(global_cfg.max_worktrees)
+                && !opts.force
+            {
+                // The main worktree doesn't count against the limit.
+                let existing = list_worktrees(&repo.toplevel)?.len().saturating_sub(1);
+                if existing >= max {
+                    anyhow::bail!(
+                        "{} already has {existing} worktrees (max_worktrees = {max}); remove some or re-run with --force",
+                        repo.repo_name
+                    );
+                }
+            }
+            let base = match &opts.base {
+                Some(b) => Some(b.clone()),
+                None if opts.base_remote_default => Some(REMOTE_DEFAULT_BASE.to_string()),
+                None => repo_cfg.base.clone().or_else(|| global_cfg.base.clone()),
+            };
+            let opts = NewOptions {
+                base,
+                min_free_disk_mb: repo_cfg.min_free_disk_mb.or(global_cfg.min_free_disk_mb),
+                ..opts.clone()
+            };
+            // A detached worktree has no branch yet when pre-create hooks run.
+            let pre_branch = if opts.detach { "" } else { branch };
+            let (wt_path, base) =
+                add_worktree(cfg_root, &repo, &mut repo_cfg, branch, &opts, |wt_path| {
+                    run_phase(
+                        HookPhase::PreCreate,
+                        wt_path,
+                        pre_branch,
+                        None,
+                        &mut hook_results,
+                    )
+                })?;
+            if let Some(trunk) = &opts.show_relation {
+                print_branch_relation(&repo, &wt_path, branch, trunk)?;
+            }
+            if !repo_cfg.copy_on_new.is_empty() {
+                let source = match opts
+                    .copy_source
+                    .or(repo_cfg.copy_source)
+                    .or(global_cfg.copy_source)
+                    .unwrap_or_default()
+                {
+                    CopySource::Main => main_worktree_path(&repo)?,
+                    CopySource::Anchor => PathBuf::from(&repo_cfg.anchor_path),
+                };
+                copy_into_worktree(&source, &wt_path, &repo_cfg.copy_on_new)?;
+            }
+            let readme = opts
+                .worktree_readme
+                .or(repo_cfg.worktree_readme)
+                .or(global_cfg.worktree_readme)
+                .unwrap_or(false);
+            if readme {
+                let base_desc = base
+                    .clone()
+                    .unwrap_or_else(|| format!("existing branch {branch}"));
+                write_worktree_readme(&repo, &wt_path, branch, &base_desc, opts.pr_url.as_deref())?;
+            }
+            let link_hooks = opts.link_worktree_git_hooks
+                || repo_cfg
+                    .link_worktree_git_hooks
+                    .or(global_cfg.link_worktree_git_hooks)
+                    .unwrap_or(false);
+            if link_hooks {
+                link_git_hooks(&repo, &wt_path)?;
+            }
+            (wt_path, base)
+        }
+    };
+
+    // Update anchor path to the created worktree so the picker can find it later.
+    if !opts.keep_cwd {
+        repo_cfg.anchor_path = wt_path.to_string_lossy().to_string();
+    }
+    save_repo_config(cfg_root, &repo, &repo_cfg)?;
+    drop(lock);
+
+    if !opts.no_hooks {
+        // A detached worktree has no branch; hooks get the commit it sits on.
+        let detached_sha = if opts.detach {
+            Some(git_stdout(&wt_path, &["rev-parse", "--short", "HEAD"])?)
+        } else {
+            None
+        };
+        let branch = detached_sha.as_deref().map_or(branch, str::trim);
+        // An existing branch wasn't created from any base.
+        let base = Some(base.as_deref().unwrap_or_default());
+        run_phase(
+            HookPhase::PostCreate,
+            &wt_path,
+            branch,
+            base,
+            &mut hook_results,
+        )?;
+    }
+    if let Some(out) = &opts.json_hooks_output {
+        hooks::write_results_json(out, &hook_results)?;
+    }
+
+    Ok(wt_path)
+}
+
+/// How long `gw new` waits for another run in the same repo to release [`RepoLock`].
+const REPO_LOCK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Advisory per-repo lock (`<config_root>/repos/<hash>/lock`), held while `gw new` runs its
+/// pre-create hooks and `git worktree add` and writes the repo config, so concurrent runs don't
+/// race on the git dir or overwrite each other's config. Released when dropped (or when the
+/// process exits).
+struct RepoLock {
+    _file: std::fs::File,
+}
+
+impl RepoLock {
+    fn acquire(
+        cfg_root: &Path,
+        repo: &RepoContext,
+        timeout: std::time::Duration,
+    ) -> anyhow::Result<Self> {
+        let dir = cfg_root.join("repos").join(&repo.repo_hash);
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join("lock");
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .with_context(|| format!("opening {}", path.display()))?;
+        let deadline = std::time::Instant::now() + timeout;
+        let mut waiting = false;
+        loop {
+            match fs2::FileExt::try_lock_exclusive(&file) {
+                Ok(()) => return Ok(Self { _file: file }),
+                Err(e) if e.raw_os_error() == fs2::lock_contended_error().raw_os_error() => {}
+                Err(e) => {
+                    return Err(e).with_context(|| format!("locking {}", path.display()));
+                }
+            }
+            if std::time::Instant::now() >= deadline {
+                anyhow::bail!(
+                    "timed out after {}s waiting for another gw run in {} (lock {})",
+                    timeout.as_secs(),
+                    repo.repo_name,
+                    path.display()
+                );
+            }
+            if !waiting {
+                eprintln!("gw: waiting for another gw run in {}", repo.repo_name);
+                waiting = true;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+    }
+}
+
+/// Hooks that apply to `repo`, in run order: global, inherited (`inherit_from`, else the repo
+/// config's `inherit_hooks_from`), then the repo's own.
+fn configured_hooks(
+    cfg_root: &Path,
+    repo: &RepoContext,
+    global_cfg: &GlobalConfig,
+    repo_cfg: &RepoConfig,
+    inherit_from: Option<&str>,
+) -> anyhow::Result<Vec<Hook>> {
+    let mut hooks = global_cfg.hooks.clone();
+    if let Some(parent) = inherit_from.or(repo_cfg.inherit_hooks_from.as_deref()) {
+        hooks.extend(inherited_hooks(cfg_root, &repo.repo_hash, parent)?);
+    }
+    hooks.extend(repo_cfg.hooks.iter().cloned());
+    Ok(hooks)
+}
+
+/// Run the `pre_remove` hooks for the worktree at `wt_path` (in `repo`, detected from its main
+/// worktree). Output is printed unless `results` is given, as for [`hooks::run_hooks`].
+pub(crate) fn run_pre_remove_hooks(
+    cfg_root: &Path,
+    repo: &RepoContext,
+    wt_path: &Path,
+    results: Option<&mut Vec<hooks::HookResult>>,
+) -> anyhow::Result<()> {
+    let global_cfg = load_global_config(cfg_root)?;
+    let repo_cfg = load_repo_config(cfg_root, repo).unwrap_or_default();
+    let hooks = configured_hooks(cfg_root, repo, &global_cfg, &repo_cfg, None)?;
+    if !hooks.iter().any(|h| h.when == HookPhase::PreRemove) {
+        return Ok(());
+    }
+    let extra_env = repo_env_file_vars(repo, &repo_cfg)?;
+    let branch = git_stdout(wt_path, &["branch", "--show-current"]).unwrap_or_default();
+    let ctx = hooks::HookContext {
+        repo,
+        branch: branch.trim(),
+        wt_path,
+        base: None,
+        extra_env: &extra_env,
+    };
+    hooks::run_hooks(&hooks, HookPhase::PreRemove, &ctx, &global_cfg, results)
+}
+
+/// Variables from the repo config's `env_file` (if set) for the hook environment.
+fn repo_env_file_vars(
+    repo: &RepoContext,
+    repo_cfg: &RepoConfig,
+) -> anyhow::Result<Vec<(String, String)>> {
+    match &repo_cfg.env_file {
+        Some(p) => hook_env_from_file(&repo.toplevel.join(shellexpand::tilde(p).as_ref()), false),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// `gw hooks run NAME`: run the named hook in the current worktree. A repo hook wins over a
+/// global one of the same name.
+fn run_named_hook(cfg_root: &Path, name: &str) -> anyhow::Result<()> {
+    let wt = RepoContext::detect_from_cwd()?;
+    let global_cfg = load_global_config(cfg_root)?;
+    let repo_cfg = load_repo_config(cfg_root, &wt).unwrap_or_default();
+    let hooks = configured_hooks(cfg_root, &wt, &global_cfg, &repo_cfg, None)?;
+    let Some(hook) = hooks.iter().rev().find(|h| h.name.as_deref() == Some(name)) else {
+        let names: Vec<&str> = hooks.iter().filter_map(|h| h.name.as_deref()).collect();
+        if names.is_empty() {
+            anyhow::bail!("no hook named {name:?}; no configured hook has a `name`");
+        }
+        anyhow::bail!("no hook named {name:?}; named hooks: {}", names.join(", "));
+    };
+    // Whatever its phase, run it like a post-create hook: in this worktree.
+    let hook = Hook {
+        when: HookPhase::PostCreate,
+        ..hook.clone()
+    };
+    let root = RepoContext {
+        toplevel: main_worktree_path(&wt)?,
+        ..wt.clone()
+    };
+    let branch = git_stdout(&wt.toplevel, &["branch", "--show-current"])?;
+    let extra_env = repo_env_file_vars(&root, &repo_cfg)?;
+    let ctx = hooks::HookContext {
+        repo: &root,
+        branch: branch.trim(),
+        wt_path: &wt.toplevel,
+        base: None,
+        extra_env: &extra_env,
+    };
+    hooks::run_hooks(&[hook], HookPhase::PostCreate, &ctx, &global_cfg, None)
+}
+
+/// Config/`--base` value meaning "the freshly fetched default branch of the remote".
+const REMOTE_DEFAULT_BASE: &str = "remote-default";
+
+/// Fetch the remote's default branch and return it as a base ref (e.g. `upstream/main`).
+///
+/// The remote is the one already chosen for the branch, else `--track`, else the only remote.
+/// Uses `refs/remotes/<remote>/HEAD` when set, else whichever of `main`/`master` it has.
+fn resolve_remote_default_base(repo: &RepoContext, opts: &NewOptions) -> anyhow::Result<String> {
+    let remote = match opts.base_remote.as_ref().or(opts.track.as_ref()) {
+        Some(remote) => remote.clone(),
+        None => {
+            let remotes = list_remotes(repo)?;
+            match remotes.as_slice() {
+                [] => anyhow::bail!("no git remotes configured; cannot use remote-default base"),
+                [remote] => remote.clone(),
+                _ => anyhow::bail!(
+                    "multiple remotes configured ({}); pass --track to choose one for remote-default",
+                    remotes.join(", ")
+                ),
+            }
+        }
+    };
+    let from_head = git_stdout(
+        &repo.toplevel,
+        &[
+            "symbolic-ref",
+            "--short",
+            &format!("refs/remotes/{remote}/HEAD"),
+        ],
+    )
+    .ok()
+    .and_then(|s| {
+        s.trim()
+            .strip_prefix(&format!("{remote}/"))
+            .map(str::to_string)
+            .filter(|b| !b.is_empty())
+    });
+    let branch = match from_head {
+        Some(b) => b,
+        None => {
+            let mut found = None;
+            for cand in ["main", "master"] {
+                if remote_has_branch(repo, &remote, cand)? {
+                    found = Some(cand.to_string());
+                    break;
+                }
+            }
+            found.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "could not determine the default branch of {remote} (no {remote}/HEAD, main or master)"
+                )
+            })?
+        }
+    };
+    eprintln!("gw: fetching {remote}/{branch} to use as base");
+    git_fetch_branch(repo, &remote, &branch, None)?;
+    Ok(format!("{remote}/{branch}"))
+}
+
+/// `--prefetch`: if `base` is `<remote>/<branch>` for a configured remote, fetch it so the
+/// worktree starts from the remote's current tip.
+fn prefetch_base(repo: &RepoContext, base: &str) -> anyhow::Result<()> {
+    let remote_ref = list_remotes(repo)?.into_iter().find_map(|remote| {
+        let branch = base.strip_prefix(&format!("{remote}/"))?;
+        (!branch.is_empty()).then(|| (remote.clone(), branch.to_string()))
+    });
+    match remote_ref {
+        Some((remote, branch)) => {
+            eprintln!("gw: prefetching {remote}/{branch}");
+            git_fetch_branch(repo, &remote, &branch, None)
+        }
+        None => {
+            eprintln!("gw: --prefetch: base {base} is not a remote ref; skipping fetch");
+            Ok(())
+        }
+    }
+}
+
+/// `gw rm` target: an existing path as-is, otherwise the worktree with that branch checked out.
+fn resolve_rm_target(target: &Path) -> anyhow::Result<PathBuf> {
+    if target.exists() {
+        return Ok(target.to_path_buf());
+    }
+    let repo = RepoContext::detect_from_cwd()?;
+    let branch = target.to_string_lossy();
+    find_branch_worktree(&repo, &branch)?.with_context(|| {
+        format!("{branch} is neither a path nor a branch checked out in a worktree")
+    })
+}
+
+/// `--show-relation`: report how the new worktree's `HEAD` relates to `trunk` (or local
+/// `main`/`master` when empty), warning when it has commits the trunk doesn't.
+fn print_branch_relation(
+    repo: &RepoContext,
+    wt_path: &Path,
+    branch: &str,
+    trunk: &str,
+) -> anyhow::Result<()> {
+    let trunk = if trunk.is_empty() {
+        let mut found = None;
+        for candidate in ["main", "master"] {
+            if repo.git_show_ref_head(candidate)? {
+                found = Some(candidate.to_string());
+                break;
+            }
+        }
+        match found {
+            Some(t) => t,
+            None => {
+                eprintln!(
+                    "gw: --show-relation: no local main or master branch; pass --show-relation=<trunk>"
+                );
+                return Ok(());
+            }
+        }
+    } else {
+        trunk.to_string()
+    };
+    let out = git_stdout(
+        wt_path,
+        &[
+            "rev-list",
+            "--count",
+            "--left-right",
+            &format!("{trunk}...HEAD"),
+        ],
+    )?;
+    let mut counts = out
+        .split_whitespace()
+        .map(|n| n.parse::<usize>().unwrap_or(0));
+    let (behind, ahead) = (counts.next().unwrap_or(0), counts.next().unwrap_or(0));
+    eprintln!("gw: {branch} is {ahead} commits ahead of {trunk} ({behind} behind)");
+    if ahead > 0 {
+        eprintln!(
+            "gw: warning: {branch} has commits not on {trunk}; is the base what you expected?"
+        );
+    }
+    Ok(())
+}
+
+/// `--print-remaining-disk` / `min_free_disk_mb`: report free space on the filesystem holding
+/// `dir` and refuse (unless `--force`) when it is below the configured minimum.
+fn check_free_disk(dir: &Path, opts: &NewOptions) -> anyhow::Result<()> {
+    if !opts.print_remaining_disk && opts.min_free_disk_mb.is_none() {
+        return Ok(());
+    }
+    let free_mb = fs2::available_space(dir)
+        .with_context(|| format!("failed to query free disk space for {}", dir.display()))?
+        / (1024 * 1024);
+    if opts.print_remaining_disk {
+        eprintln!("gw: {free_mb} MB free on {}", dir.display());
+    }
+    if let Some(min) = opts.min_free_disk_mb
+        && free_mb < min
+    {
+        if !opts.force {
+            anyhow::bail!(
+                "only {free_mb} MB free on {} (min_free_disk_mb = {min}); free some space or re-run with --force",
+                dir.display()
+            );
+        }
+        eprintln!("gw: only {free_mb} MB free (min_free_disk_mb = {min}); continuing (--force)");
+    }
+    Ok(())
+}
+
+/// Path of the worktree that has `branch` checked out, if any.
+fn find_branch_worktree(repo: &RepoContext, branch: &str) -> anyhow::Result<Option<PathBuf>> {
+    Ok(list_worktrees(&repo.toplevel)?
+        .into_iter()
+        .find(|e| e.branch.as_deref() == Some(branch))
+        .map(|e| e.path))
+}
+
+/// The branch checked out in the worktree at `wt_path` (`gw new --branch-from-current`).
+fn current_branch(wt_path: &Path) -> anyhow::Result<String> {
+    let branch = git_stdout(wt_path, &["rev-parse", "--abbrev-ref", "HEAD"])?;
+    match branch.trim() {
+        "HEAD" => anyhow::bail!(
+            "--branch-from-current: {} has a detached HEAD; pass --base instead",
+            wt_path.display()
+        ),
+        b => Ok(b.to_string()),
+    }
+}
+
+/// A worktree [`rename_branch`] moved to match its renamed branch.
+pub(crate) struct MovedWorktree {
+    pub(crate) path: PathBuf,
+    /// Whether the current directory was inside the worktree before the move.
+    pub(crate) had_cwd: bool,
+}
+
+/// `gw rename` (and picker `R`): rename branch `old` to `new` and, with `move_worktree`,
+/// `git worktree move` its worktree (unless it is the main one) to `<worktrees dir>/<new>`,
+/// keeping the repo config's anchor, per-worktree metadata and `last_branch` pointing at it.
+pub(crate) fn rename_branch(
+    cfg_root: &Path,
+    repo: &RepoContext,
+    old: &str,
+    new: &str,
+    move_worktree: bool,
+) -> anyhow::Result<Option<MovedWorktree>> {
+    let branch_exists = |b: &str| {
+        git_stdout(
+            &repo.toplevel,
+            &[
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("refs/heads/{b}"),
+            ],
+        )
+        .is_ok()
+    };
+    if !branch_exists(old) {
+        anyhow::bail!("no branch named {old}");
+    }
+    if branch_exists(new) {
+        anyhow::bail!("refusing to rename {old}: branch {new} already exists");
+    }
+
+    let entries = list_worktrees(&repo.toplevel)?;
+    let main = entries
+        .first()
+        .map(|e| e.path.clone())
+        .unwrap_or(repo.toplevel.clone());
+    let mut cfg = load_repo_config(cfg_root, repo);
+    let moved = match entries
+        .iter()
+        .skip(1)
+        .find(|e| move_worktree && e.branch.as_deref() == Some(old))
+    {
+        Some(e) => {
+            let repo_cfg = cfg.clone().unwrap_or_default();
+            let new_path =
+                match configured_worktrees_dir(&load_global_config(cfg_root)?, repo, &repo_cfg) {
+                    Some((base, _)) => {
+                        let prefix = repo_cfg.worktree_dir_prefix.as_deref().unwrap_or("");
+                        expand_path(&base)?
+                            .join(prefix_first_segment(&sanitize_branch_for_path(new), prefix))
+                    }
+                    None => renamed_worktree_path(&e.path, old, new),
+                };
+            if new_path.exists() {
+                anyhow::bail!(
+                    "refusing to rename {old}: {} already exists",
+                    new_path.display()
                 );
-                if let Some(cfg) = load_repo_config(&cfg_root, &repo)
-                    && let Some(wd) = cfg.worktrees_dir
-                {
-                    println!("worktrees_dir={wd}");
-                }
             }
+            Some((e.path.clone(), new_path))
         }
-        Some(Command::Hooks) => {
-            let cfg_root = config_root()?;
-            let global = load_global_config(&cfg_root)?;
-            for h in global.hooks {
-                println!("global: {}", h.command);
-            }
-            if let Ok(repo) = RepoContext::detect_from_cwd()
-                && let Some(cfg) = load_repo_config(&cfg_root, &repo)
-            {
-                for h in cfg.hooks {
-                    println!("repo: {}", h.command);
-                }
-            }
+        None => None,
+    };
+
+    git_stdout(&main, &["branch", "-m", old, new])?;
+    eprintln!("gw: renamed branch {old} to {new}");
+    // The branch is renamed even if moving its worktree fails below.
+    if let Some(cfg) = cfg.as_mut()
+        && cfg.last_branch.as_deref() == Some(old)
+    {
+        cfg.last_branch = Some(new.to_string());
+        save_repo_config(cfg_root, repo, cfg)?;
+    }
+    let Some((old_path, new_path)) = moved else {
+        if entries
+            .first()
+            .is_some_and(|e| e.branch.as_deref() == Some(old))
+        {
+            eprintln!("gw: {old} is checked out in the main worktree, which stays where it is");
         }
-        Some(Command::Version) => {
-            println!("{}", env!("CARGO_PKG_VERSION"));
+        return Ok(None);
+    };
+
+    let orig_cwd = std::env::current_dir()
+        .ok()
+        .and_then(|p| std::fs::canonicalize(p).ok());
+    let old_canon = normalize_path(&old_path);
+    let in_moved = orig_cwd
+        .as_ref()
+        .is_some_and(|cwd| cwd.starts_with(&old_canon));
+    if let Some(parent) = new_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    git_stdout(
+        &main,
+        &[
+            "worktree",
+            "move",
+            &old_path.to_string_lossy(),
+            &new_path.to_string_lossy(),
+        ],
+    )
+    .with_context(|| format!("branch renamed to {new}, but moving its worktree failed"))?;
+    eprintln!("gw: moved worktree to {}", new_path.display());
+
+    if let Some(cfg) = cfg.as_mut() {
+        let new_str = new_path.to_string_lossy().to_string();
+        let is_old = |p: &str| normalize_path(Path::new(p)) == old_canon;
+        let mut changed = false;
+        if is_old(&cfg.anchor_path) {
+            cfg.anchor_path = new_str.clone();
+            changed = true;
+        }
+        if let Some(key) = cfg.worktrees.keys().find(|k| is_old(k)).cloned() {
+            let meta = cfg.worktrees.remove(&key).unwrap_or_default();
+            cfg.worktrees.insert(new_str, meta);
+            changed = true;
+        }
+        if changed {
+            save_repo_config(cfg_root, repo, cfg)?;
         }
     }
+    Ok(Some(MovedWorktree {
+        path: new_path,
+        had_cwd: in_moved,
+    }))
+}
 
-    Ok(())
+/// Where `git worktree move` should put a worktree at `wt_path` whose branch is renamed from
+/// `old` to `new` when no worktrees dir is configured: the `old` suffix of the path is swapped
+/// for `new` (`<dir>/feat/x` -> `<dir>/feat/y`), otherwise only the last path component is
+/// replaced.
+fn renamed_worktree_path(wt_path: &Path, old: &str, new: &str) -> PathBuf {
+    match wt_path.to_string_lossy().strip_suffix(old) {
+        Some(prefix) if prefix.ends_with(std::path::MAIN_SEPARATOR) || prefix.ends_with('/') => {
+            PathBuf::from(format!("{prefix}{new}"))
+        }
+        _ => wt_path.with_file_name(new.rsplit('/').next().unwrap_or(new)),
+    }
 }
 
-pub(crate) fn create_worktree(
-    repo_cwd: &Path,
-    cfg_root: &Path,
-    branch: &str,
-    worktrees_dir: Option<PathBuf>,
-    path: Option<PathBuf>,
-    base: Option<String>,
-    no_hooks: bool,
-) -> anyhow::Result<PathBuf> {
-    let repo = RepoContext::detect_from_path(repo_cwd)?;
-    let global_cfg = load_global_config(cfg_root)?;
+/// Store `text` as the description of the branch checked out in `wt_path`
+/// (`gw new --set-description`).
+fn set_branch_description(wt_path: &Path, text: &str) -> anyhow::Result<()> {
+    let branch = git_stdout(wt_path, &["branch", "--show-current"])?;
+    let branch = branch.trim();
+    if branch.is_empty() {
+        anyhow::bail!(
+            "{} is detached; there is no branch to describe",
+            wt_path.display()
+        );
+    }
+    git_stdout(
+        wt_path,
+        &["config", &format!("branch.{branch}.description"), text],
+    )?;
+    Ok(())
+}
 
-    let mut repo_cfg = load_repo_config(cfg_root, &repo).unwrap_or_else(|| RepoConfig {
+/// Persist `base` as the repo's default base for new branches (`gw new --set-main-branch`).
+fn set_repo_base(cfg_root: &Path, repo: &RepoContext, base: &str) -> anyhow::Result<()> {
+    if base != REMOTE_DEFAULT_BASE
+        && git_stdout(
+            &repo.toplevel,
+            &[
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("{base}^{{commit}}"),
+            ],
+        )
+        .is_err()
+    {
+        anyhow::bail!("--set-main-branch: {base:?} is not a branch or commit in this repo");
+    }
+    let mut cfg = load_repo_config(cfg_root, repo).unwrap_or_else(|| RepoConfig {
         repo_name: repo.repo_name.clone(),
         git_common_dir: repo.git_common_dir.to_string_lossy().to_string(),
         anchor_path: repo.toplevel.to_string_lossy().to_string(),
-        worktrees_dir: None,
-        hooks: Vec::new(),
+        ..Default::default()
     });
+    cfg.base = Some(base.to_string());
+    save_repo_config(cfg_root, repo, &cfg)?;
+    eprintln!(
+        "gw: new branches in {} now start from {base}",
+        repo.repo_name
+    );
+    Ok(())
+}
 
-    if let Some(wd) = worktrees_dir {
+/// Resolve the worktree path (persisting the worktrees dir if needed) and run `git worktree add`.
+/// Also returns the ref the branch was created from (`None` if it already existed).
+fn add_worktree(
+    cfg_root: &Path,
+    repo: &RepoContext,
+    repo_cfg: &mut RepoConfig,
+    branch: &str,
+    opts: &NewOptions,
+    pre_create: impl FnOnce(&Path) -> anyhow::Result<()>,
+) -> anyhow::Result<(PathBuf, Option<String>)> {
+    if let Some(wd) = &opts.worktrees_dir {
         // If the user picks a shared base (e.g. ~/worktrees), keep per-repo isolation by nesting.
         let repo_base = wd.join(&repo.repo_name);
         std::fs::create_dir_all(&repo_base)?;
-        repo_cfg.worktrees_dir = Some(repo_base.to_string_lossy().to_string());
-        save_repo_config(cfg_root, &repo, &repo_cfg)?;
+        *repo_cfg.platform_worktrees_dir_mut() = Some(repo_base.to_string_lossy().to_string());
+        save_repo_config(cfg_root, repo, repo_cfg)?;
     }
 
-    let wt_base = match repo_cfg.worktrees_dir.clone() {
-        Some(w) => w,
+    let global = load_global_config(cfg_root)?;
+    let wt_base = match configured_worktrees_dir(&global, repo, repo_cfg) {
+        Some((w, _)) => w,
         None => {
-            let picked = prompt_worktrees_dir(&repo)?;
+            let inferred = infer_worktrees_dir(repo);
+            let picked = match inferred {
+                Some(dir) if opts.infer_dir => {
+                    eprintln!(
+                        "gw: using worktrees dir inferred from existing worktrees: {}",
+                        dir.display()
+                    );
+                    dir
+                }
+                _ => prompt_worktrees_dir(repo, inferred.as_deref())?,
+            };
             std::fs::create_dir_all(&picked)?;
+            let others = repos_sharing_worktrees_dir(cfg_root, &repo.repo_hash, &picked);
+            if !others.is_empty() {
+                eprintln!(
+                    "gw: warning: worktrees dir {} is also used by {}; worktrees of these repos can collide. `gw new --worktrees-dir {}` nests this repo's under {}",
+                    picked.display(),
+                    others.join(", "),
+                    shell_quote(&picked.to_string_lossy()),
+                    picked.join(&repo.repo_name).display()
+                );
+            }
             repo_cfg.worktrees_dir = Some(picked.to_string_lossy().to_string());
-            save_repo_config(cfg_root, &repo, &repo_cfg)?;
+            save_repo_config(cfg_root, repo, repo_cfg)?;
             picked.to_string_lossy().to_string()
         }
     };
 
-    let wt_path = match path {
+    let wt_path = match opts.path.clone() {
         Some(p) => p,
         None => {
-            let branch_path = sanitize_branch_for_path(branch);
-            PathBuf::from(wt_base).join(branch_path)
+            let prefix = opts
+                .worktree_prefix
+                .as_deref()
+                .or(repo_cfg.worktree_dir_prefix.as_deref())
+                .unwrap_or("");
+            if prefix.contains(['/', '\\']) {
+                anyhow::bail!("worktree dir prefix must not contain path separators: {prefix:?}");
+            }
+            let branch_path = prefix_first_segment(&sanitize_branch_for_path(branch), prefix);
+            expand_path(&wt_base)?.join(branch_path)
         }
     };
     if let Some(parent) = wt_path.parent() {
         std::fs::create_dir_all(parent)?;
+        check_free_disk(parent, opts)?;
+    }
+    // Before clearing a leftover directory, so a hook that aborts leaves it alone.
+    pre_create(&wt_path)?;
+    clear_orphaned_path(repo, &wt_path, opts.replace_leftover)?;
+
+    if opts.detach {
+        let base = match opts.base.as_deref() {
+            Some(REMOTE_DEFAULT_BASE) => resolve_remote_default_base(repo, opts)?,
+            Some(base) => {
+                if opts.prefetch {
+                    prefetch_base(repo, base)?;
+                }
+                base.to_string()
+            }
+            None => "HEAD".to_string(),
+        };
+        repo.run_git_strings(&[
+            "worktree".into(),
+            "add".into(),
+            "--detach".into(),
+            wt_path.to_string_lossy().to_string(),
+            base.clone(),
+        ])?;
+        return Ok((wt_path, Some(base)));
+    }
+
+    let mut tracking_base = None;
+    if let Some(remote) = &opts.upstream_remote
+        && !repo.git_show_ref_head(branch)?
+    {
+        // One step when git can do it: `--guess-remote` derives the branch from the directory
+        // name and only picks a remote-tracking branch that exactly one remote has.
+        if wt_path.file_name().is_some_and(|n| n == branch)
+            && git_supports_guess_remote(repo)
+            && remotes_with_tracking_branch(repo, branch)? == [remote.as_str()]
+        {
+            eprintln!(
+                "gw: creating local tracking branch {branch} -> {remote}/{branch} (git worktree add --guess-remote)"
+            );
+            repo.run_git_strings(&[
+                "worktree".into(),
+                "add".into(),
+                "--guess-remote".into(),
+                wt_path.to_string_lossy().to_string(),
+            ])?;
+            return Ok((wt_path, Some(format!("{remote}/{branch}"))));
+        }
+        eprintln!("gw: creating local tracking branch {branch} -> {remote}/{branch}");
+        git_create_tracking_branch(repo, branch, remote)?;
+        tracking_base = Some(format!("{remote}/{branch}"));
     }
 
     let branch_exists = repo.git_show_ref_head(branch)?;
@@ -297,26 +2681,252 @@ pub(crate) fn create_worktree(
         args.push(branch.to_string());
     }
     args.push(wt_path.to_string_lossy().to_string());
-    if branch_exists {
+    let base = if branch_exists {
         args.push(branch.to_string());
-    } else if let Some(base) = base.clone() {
-        args.push(base);
-    }
+        tracking_base
+    } else if let Some(base) = opts.base.as_deref() {
+        let base = if base == REMOTE_DEFAULT_BASE {
+            resolve_remote_default_base(repo, opts)?
+        } else {
+            if opts.prefetch {
+                prefetch_base(repo, base)?;
+            }
+            base.to_string()
+        };
+        args.push(base.clone());
+        Some(base)
+    } else {
+        Some("HEAD".to_string())
+    };
 
     repo.run_git_strings(&args)?;
 
-    // Update anchor path to the created worktree so the picker can find it later.
-    repo_cfg.anchor_path = wt_path.to_string_lossy().to_string();
-    save_repo_config(cfg_root, &repo, &repo_cfg)?;
+    Ok((wt_path, base))
+}
+
+/// `git worktree add` refuses a non-empty existing path. Such a directory left behind by a crash
+/// is removed with `replace`; one that is (or contains) a registered worktree, or any git
+/// checkout at all (another repo's worktree can share the path), never is.
+fn clear_orphaned_path(repo: &RepoContext, wt_path: &Path, replace: bool) -> anyhow::Result<()> {
+    let occupied = match std::fs::read_dir(wt_path) {
+        Ok(mut entries) => entries.next().is_some(),
+        Err(_) => wt_path.exists(),
+    };
+    if !occupied {
+        return Ok(());
+    }
+    let target = std::fs::canonicalize(wt_path)?;
+    if let Some(wt) = list_worktrees(&repo.toplevel)?.iter().find(|e| {
+        std::fs::canonicalize(&e.path)
+            .unwrap_or_else(|_| e.path.clone())
+            .starts_with(&target)
+    }) {
+        anyhow::bail!(
+            "{} already holds the registered worktree {}; pick another path or `gw rm` it first",
+            wt_path.display(),
+            wt.path.display()
+        );
+    }
+    if let Some(git) = find_git_entry(wt_path)? {
+        anyhow::bail!(
+            "{} contains a git checkout ({}); pick another path or remove it yourself",
+            wt_path.display(),
+            git.display()
+        );
+    }
+    if !replace {
+        anyhow::bail!(
+            "{} already exists and is not a registered worktree (left over from a crash?); remove it or re-run with --replace-leftover to replace it",
+            wt_path.display()
+        );
+    }
+    eprintln!(
+        "gw: removing leftover directory {} (--replace-leftover)",
+        wt_path.display()
+    );
+    // A symlink is removed itself, never what it points to.
+    if std::fs::symlink_metadata(wt_path)?.is_dir() {
+        std::fs::remove_dir_all(wt_path)?;
+    } else {
+        std::fs::remove_file(wt_path)?;
+    }
+    Ok(())
+}
 
-    if !no_hooks {
-        let mut hooks = Vec::new();
-        hooks.extend(global_cfg.hooks);
-        hooks.extend(repo_cfg.hooks);
-        run_hooks(&hooks, &repo, branch, &wt_path)?;
+/// The first `.git` file or directory at or below `dir`, without following symlinks.
+fn find_git_entry(dir: &Path) -> anyhow::Result<Option<PathBuf>> {
+    if !std::fs::symlink_metadata(dir)?.is_dir() {
+        return Ok(None);
     }
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_name() == ".git" {
+            return Ok(Some(entry.path()));
+        }
+        if entry.file_type()?.is_dir()
+            && let Some(found) = find_git_entry(&entry.path())?
+        {
+            return Ok(Some(found));
+        }
+    }
+    Ok(None)
+}
 
-    Ok(wt_path)
+const WORKTREE_README: &str = ".gw-worktree.md";
+
+/// Write `.gw-worktree.md` into a new worktree and make sure git ignores it (via the shared
+/// `info/exclude`, so it never shows up as untracked).
+fn write_worktree_readme(
+    repo: &RepoContext,
+    wt_path: &Path,
+    branch: &str,
+    base: &str,
+    pr_url: Option<&str>,
+) -> anyhow::Result<()> {
+    let created = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| format_utc(d.as_secs()))
+        .unwrap_or_default();
+    let mut body = format!(
+        "# {branch}\n\n- Branch: `{branch}`\n- Base: `{base}`\n- Created: {created}\n- Repo: {}\n",
+        repo.toplevel.display()
+    );
+    if let Some(url) = pr_url {
+        body.push_str(&format!("- PR: {url}\n"));
+    }
+    std::fs::write(wt_path.join(WORKTREE_README), body)?;
+
+    let exclude = repo.git_common_dir.join("info").join("exclude");
+    let current = std::fs::read_to_string(&exclude).unwrap_or_default();
+    if !current.lines().any(|l| l.trim() == WORKTREE_README) {
+        if let Some(parent) = exclude.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let sep = if current.is_empty() || current.ends_with('\n') {
+            ""
+        } else {
+            "\n"
+        };
+        std::fs::write(&exclude, format!("{current}{sep}{WORKTREE_README}\n"))?;
+    }
+    Ok(())
+}
+
+/// The repo's main worktree, or its git dir for a bare repo.
+fn main_worktree_path(repo: &RepoContext) -> anyhow::Result<PathBuf> {
+    let entries = list_worktrees(&repo.toplevel)?;
+    Ok(match main_worktree_idx(&entries) {
+        Some(i) => entries[i].path.clone(),
+        None => repo.git_common_dir.clone(),
+    })
+}
+
+/// Copy `paths` (relative files or directories) from `source` into the new worktree, skipping
+/// ones missing in `source` and never overwriting what the checkout already has.
+fn copy_into_worktree(source: &Path, wt_path: &Path, paths: &[String]) -> anyhow::Result<()> {
+    for rel in paths {
+        let from = source.join(rel);
+        let to = wt_path.join(rel);
+        if !from.exists() {
+            eprintln!("gw: copy_on_new: {} not found; skipping", from.display());
+            continue;
+        }
+        if to.exists() {
+            eprintln!("gw: copy_on_new: {rel} already exists in the new worktree; skipping");
+            continue;
+        }
+        copy_recursive(&from, &to).with_context(|| format!("copy_on_new: copying {rel}"))?;
+        eprintln!("gw: copied {rel} from {}", source.display());
+    }
+    Ok(())
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> anyhow::Result<()> {
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if from.is_dir() {
+        std::fs::create_dir_all(to)?;
+        for ent in std::fs::read_dir(from)? {
+            let ent = ent?;
+            copy_recursive(&ent.path(), &to.join(ent.file_name()))?;
+        }
+    } else {
+        std::fs::copy(from, to)?;
+    }
+    Ok(())
+}
+
+/// Absolute hooks dir git uses in `cwd` (honors `core.hooksPath`).
+fn git_hooks_dir(cwd: &Path) -> anyhow::Result<PathBuf> {
+    let out = git_stdout(
+        cwd,
+        &["rev-parse", "--path-format=absolute", "--git-path", "hooks"],
+    )?;
+    Ok(PathBuf::from(out.trim()))
+}
+
+/// Make the main worktree's git hooks available in `wt_path` when its effective hooks dir is a
+/// different one (e.g. a relative `core.hooksPath` pointing at an untracked dir). Hooks are
+/// symlinked (copied on Windows); existing files in the target are left alone.
+fn link_git_hooks(repo: &RepoContext, wt_path: &Path) -> anyhow::Result<()> {
+    let src = git_hooks_dir(&main_worktree_path(repo)?)?;
+    let dst = git_hooks_dir(wt_path)?;
+    if normalize_path(&src) == normalize_path(&dst) {
+        eprintln!("gw: worktree already uses the shared git hooks dir");
+        return Ok(());
+    }
+    if !src.is_dir() {
+        eprintln!("gw: no git hooks dir at {}; nothing to link", src.display());
+        return Ok(());
+    }
+
+    std::fs::create_dir_all(&dst)?;
+    let mut linked = 0;
+    for ent in std::fs::read_dir(&src)? {
+        let ent = ent?;
+        let name = ent.file_name();
+        if !ent.file_type()?.is_file() || name.to_string_lossy().ends_with(".sample") {
+            continue;
+        }
+        let target = dst.join(&name);
+        if target.exists() || target.symlink_metadata().is_ok() {
+            eprintln!(
+                "gw: keeping existing git hook {} (not linked)",
+                target.display()
+            );
+            continue;
+        }
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(ent.path(), &target)?;
+        #[cfg(windows)]
+        std::fs::copy(ent.path(), &target)?;
+        linked += 1;
+    }
+    eprintln!("gw: linked {linked} git hook(s) into {}", dst.display());
+    Ok(())
+}
+
+/// Format Unix seconds as an RFC 3339 UTC timestamp (`2024-01-31T12:00:00Z`).
+fn format_utc(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    // Civil-from-days (Howard Hinnant's algorithm).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
 }
 
 #[derive(Debug, Clone)]
@@ -412,9 +3022,8 @@ fn choose_remote(repo: &RepoContext, interactive: bool) -> anyhow::Result<Option
             remotes.join(", ")
         );
     }
-    use dialoguer::{Select, theme::ColorfulTheme};
-    let theme = ColorfulTheme::default();
-    let idx = Select::with_theme(&theme)
+    let theme = prompt_theme();
+    let idx = dialoguer::Select::with_theme(&*theme)
         .with_prompt("Select remote")
         .items(&remotes)
         .default(0)
@@ -422,6 +3031,29 @@ fn choose_remote(repo: &RepoContext, interactive: bool) -> anyhow::Result<Option
     Ok(remotes.get(idx).cloned())
 }
 
+/// The `--track` remote (which must exist), else [`choose_remote`].
+fn select_remote(
+    repo: &RepoContext,
+    opts: &NewOptions,
+    interactive: bool,
+) -> anyhow::Result<Option<String>> {
+    let Some(remote) = &opts.track else {
+        return choose_remote(repo, interactive);
+    };
+    let remotes = list_remotes(repo)?;
+    if !remotes.contains(remote) {
+        anyhow::bail!(
+            "no remote named {remote} (--track); configured: {}",
+            if remotes.is_empty() {
+                "none".to_string()
+            } else {
+                remotes.join(", ")
+            }
+        );
+    }
+    Ok(Some(remote.clone()))
+}
+
 fn remote_has_branch(repo: &RepoContext, remote: &str, branch: &str) -> anyhow::Result<bool> {
     let status = std::process::Command::new("git")
         .current_dir(&repo.toplevel)
@@ -436,17 +3068,72 @@ fn remote_has_branch(repo: &RepoContext, remote: &str, branch: &str) -> anyhow::
     Ok(status.success())
 }
 
-fn git_fetch_branch(repo: &RepoContext, remote: &str, branch: &str) -> anyhow::Result<()> {
-    let status = std::process::Command::new("git")
-        .current_dir(&repo.toplevel)
-        .args(["fetch", remote, branch])
-        .status()?;
+fn git_fetch_branch(
+    repo: &RepoContext,
+    remote: &str,
+    branch: &str,
+    shallow_since: Option<&str>,
+) -> anyhow::Result<()> {
+    let mut cmd = std::process::Command::new("git");
+    cmd.current_dir(&repo.toplevel).arg("fetch");
+    if let Some(date) = shallow_since {
+        // `.git/shallow` is shared by every worktree of the repo.
+        eprintln!(
+            "gw: warning: --shallow-since makes the whole repository shallow (the main checkout and every worktree), not just this worktree, until `git fetch --unshallow`; merge-base based features (--show-relation, prune-branches, list --group-by-base) may be wrong for {branch}"
+        );
+        cmd.arg(format!("--shallow-since={date}"));
+    }
+    let status = cmd.args([remote, branch]).status()?;
     if !status.success() {
         anyhow::bail!("git fetch {remote} {branch} failed");
     }
     Ok(())
 }
 
+/// `git worktree add --guess-remote` exists since git 2.16.
+fn git_supports_guess_remote(repo: &RepoContext) -> bool {
+    git_stdout(&repo.toplevel, &["--version"])
+        .ok()
+        .and_then(|v| parse_git_version(&v))
+        .is_some_and(|v| v >= (2, 16))
+}
+
+/// `(major, minor)` from `git --version` output, e.g. `git version 2.39.5 (Apple Git-154)`.
+fn parse_git_version(out: &str) -> Option<(u32, u32)> {
+    let version = out.trim().strip_prefix("git version ")?;
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts
+        .next()?
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect::<String>()
+        .parse()
+        .ok()?;
+    Some((major, minor))
+}
+
+/// Configured remotes that have a remote-tracking `<remote>/<branch>`.
+fn remotes_with_tracking_branch(repo: &RepoContext, branch: &str) -> anyhow::Result<Vec<String>> {
+    let mut found = Vec::new();
+    for remote in list_remotes(repo)? {
+        let ok = std::process::Command::new("git")
+            .current_dir(&repo.toplevel)
+            .args([
+                "show-ref",
+                "--verify",
+                "--quiet",
+                &format!("refs/remotes/{remote}/{branch}"),
+            ])
+            .status()?
+            .success();
+        if ok {
+            found.push(remote);
+        }
+    }
+    Ok(found)
+}
+
 fn git_create_tracking_branch(
     repo: &RepoContext,
     branch: &str,
@@ -482,14 +3169,45 @@ fn git_fetch_pr(
     Ok(())
 }
 
+/// Fetch `refs/pull/<N>/merge` and return its commit, or `None` if the remote doesn't have it
+/// (GitHub drops it while a PR has conflicts).
+fn git_fetch_pr_merge(
+    repo: &RepoContext,
+    remote: &str,
+    pr_number: u64,
+) -> anyhow::Result<Option<String>> {
+    let merge_ref = format!("refs/pull/{pr_number}/merge");
+    let listed = git_stdout(&repo.toplevel, &["ls-remote", remote, &merge_ref])?;
+    if listed.trim().is_empty() {
+        return Ok(None);
+    }
+    let status = std::process::Command::new("git")
+        .current_dir(&repo.toplevel)
+        .args(["fetch", remote, &merge_ref])
+        .status()?;
+    if !status.success() {
+        anyhow::bail!("git fetch {remote} {merge_ref} failed");
+    }
+    let sha = git_stdout(&repo.toplevel, &["rev-parse", "FETCH_HEAD"])?;
+    Ok(Some(sha.trim().to_string()))
+}
+
+/// Reject a `gw new` SPEC that starts with `-`: it was most likely meant as an option, and git
+/// refuses such branch names anyway (with a much less helpful error).
+pub(crate) fn check_spec_not_option(spec: &str) -> Result<(), String> {
+    if spec.starts_with('-') {
+        return Err(format!(
+            "{spec:?} looks like an option, not a branch name (branch names can't start with '-'); put options before the branch and end them with `--`, e.g. `gw new --base main -- my-branch`"
+        ));
+    }
+    Ok(())
+}
+
 pub(crate) fn create_worktree_from_spec(
     repo_cwd: &Path,
     cfg_root: &Path,
     spec: &str,
-    worktrees_dir: Option<PathBuf>,
-    path: Option<PathBuf>,
-    base: Option<String>,
-    no_hooks: bool,
+    opts: &NewOptions,
     interactive: bool,
 ) -> anyhow::Result<PathBuf> {
     let repo = RepoContext::detect_from_path(repo_cwd)?;
@@ -497,30 +3215,57 @@ pub(crate) fn create_worktree_from_spec(
     if spec.is_empty() {
         anyhow::bail!("empty spec");
     }
+    check_spec_not_option(spec).map_err(anyhow::Error::msg)?;
 
     if let Some(pr) = parse_github_pr_url(spec) {
-        let remote = choose_remote(&repo, interactive)?
+        if opts.detach {
+            anyhow::bail!(
+                "--detach can't be used with a PR URL; use --base-pr-merge for a detached checkout"
+            );
+        }
+        let remote = select_remote(&repo, opts, interactive)?
             .ok_or_else(|| anyhow::anyhow!("no git remotes configured; cannot fetch PR"))?;
 
         // Best-effort: if the remote URL looks like a GitHub URL, require it to match the PR URL.
-        if let Ok(remote_url) = git_stdout(&repo.toplevel, &["remote", "get-url", &remote]) {
-            if let Some((host, owner, rrepo)) = parse_github_remote_url(remote_url.trim()) {
-                if host == "github.com" && (owner != pr.owner || rrepo != pr.repo) {
-                    anyhow::bail!(
-                        "PR URL is for {}/{} but remote {} points to {}/{}",
-                        pr.owner,
-                        pr.repo,
-                        remote,
-                        owner,
-                        rrepo
-                    );
-                }
+        if let Ok(remote_url) = git_stdout(&repo.toplevel, &["remote", "get-url", &remote])
+            && let Some((host, owner, rrepo)) = parse_github_remote_url(remote_url.trim())
+            && host == "github.com"
+            && (owner != pr.owner || rrepo != pr.repo)
+        {
+            anyhow::bail!(
+                "PR URL is for {}/{} but remote {} points to {}/{}",
+                pr.owner,
+                pr.repo,
+                remote,
+                owner,
+                rrepo
+            );
+        }
+
+        eprintln!("gw: creating worktree from PR URL {}", spec);
+        eprintln!("gw: selected remote {remote}");
+        if opts.base_pr_merge {
+            if let Some(merge) = git_fetch_pr_merge(&repo, &remote, pr.number)? {
+                let name = format!("pr/{}-merge", pr.number);
+                eprintln!("gw: creating detached worktree {name} at merge ref {merge}");
+                return create_worktree(
+                    &repo.toplevel,
+                    cfg_root,
+                    &name,
+                    &NewOptions {
+                        base: Some(merge),
+                        detach: true,
+                        pr_url: Some(spec.to_string()),
+                        ..opts.clone()
+                    },
+                );
             }
+            eprintln!(
+                "gw: warning: {remote} has no refs/pull/{}/merge (PR not mergeable?); using the PR head instead",
+                pr.number
+            );
         }
-
         let branch = format!("pr/{}", pr.number);
-        eprintln!("gw: creating worktree from PR URL {}", spec);
-        eprintln!("gw: selected remote {remote}");
         eprintln!("gw: fetching PR #{} into branch {}", pr.number, branch);
         git_fetch_pr(&repo, &remote, pr.number, &branch)?;
         eprintln!("gw: creating worktree for {}", branch);
@@ -528,86 +3273,161 @@ pub(crate) fn create_worktree_from_spec(
             &repo.toplevel,
             cfg_root,
             &branch,
-            worktrees_dir,
-            path,
-            None,
-            no_hooks,
+            &NewOptions {
+                base: None,
+                pr_url: Some(spec.to_string()),
+                ..opts.clone()
+            },
         );
     }
 
+    if opts.base_pr_merge {
+        anyhow::bail!("--base-pr-merge needs a GitHub PR URL, got {spec:?}");
+    }
     let branch = spec.to_string();
-    if repo.git_show_ref_head(&branch)? {
+    let branch_exists = repo.git_show_ref_head(&branch)?;
+    if opts.detach {
+        let base = match (&opts.base, branch_exists) {
+            (Some(base), true) => anyhow::bail!(
+                "--detach with --base {base}: {branch} is an existing branch, so it would name the directory but not be checked out; drop --base to detach at {branch}"
+            ),
+            (Some(base), false) => Some(base.clone()),
+            (None, true) => Some(branch.clone()),
+            (None, false) => None,
+        };
+        warn_shallow_since_ignored(opts);
+        return create_worktree(
+            &repo.toplevel,
+            cfg_root,
+            &branch,
+            &NewOptions {
+                base,
+                ..opts.clone()
+            },
+        );
+    }
+    if branch_exists && !opts.resume {
+        let action = match opts.branch_exists_action {
+            Some(a) => a,
+            None => load_repo_config(cfg_root, &repo)
+                .and_then(|c| c.branch_exists_action)
+                .or(load_global_config(cfg_root)?.branch_exists_action)
+                .unwrap_or_default(),
+        };
+        match action {
+            BranchExistsAction::Use => {}
+            BranchExistsAction::Error => {
+                anyhow::bail!("branch {branch} already exists (branch-exists-action = error)")
+            }
+            BranchExistsAction::NewSuffix => {
+                let mut n = 2;
+                while repo.git_show_ref_head(&format!("{branch}-{n}"))? {
+                    n += 1;
+                }
+                let suffixed = format!("{branch}-{n}");
+                eprintln!("gw: branch {branch} already exists; creating {suffixed} instead");
+                return create_worktree(&repo.toplevel, cfg_root, &suffixed, opts);
+            }
+        }
+    }
+    if branch_exists {
         eprintln!("gw: using existing local branch {branch}");
+        warn_shallow_since_ignored(opts);
+        if let Some(remote) = &opts.track {
+            eprintln!(
+                "gw: --track {remote} only applies to branches fetched from a remote; ignoring"
+            );
+        }
         return create_worktree(
             &repo.toplevel,
             cfg_root,
             &branch,
-            worktrees_dir,
-            path,
-            None,
-            no_hooks,
+            &NewOptions {
+                base: None,
+                ..opts.clone()
+            },
         );
     }
 
     // Branch doesn't exist locally: see if it exists on a remote. If no remote, treat as new.
-    if let Some(remote) = choose_remote(&repo, interactive)? {
+    if let Some(remote) = select_remote(&repo, opts, interactive)? {
         eprintln!("gw: branch {branch} not found locally");
         eprintln!("gw: selected remote {remote}");
         if remote_has_branch(&repo, &remote, &branch)? {
             eprintln!("gw: found {branch} on {remote}; fetching");
-            git_fetch_branch(&repo, &remote, &branch)?;
-            eprintln!("gw: creating local tracking branch {branch} -> {remote}/{branch}");
-            git_create_tracking_branch(&repo, &branch, &remote)?;
+            git_fetch_branch(&repo, &remote, &branch, opts.shallow_since.as_deref())?;
             eprintln!("gw: creating worktree for {branch}");
             return create_worktree(
                 &repo.toplevel,
                 cfg_root,
                 &branch,
-                worktrees_dir,
-                path,
-                None,
-                no_hooks,
+                &NewOptions {
+                    base: None,
+                    upstream_remote: Some(remote),
+                    ..opts.clone()
+                },
             );
         }
+        if opts.track.is_some() {
+            anyhow::bail!("branch {branch} not found on remote {remote} (--track {remote})");
+        }
         eprintln!("gw: branch {branch} not found on {remote}; creating new branch");
-    } else {
-        eprintln!("gw: no remotes configured; creating new branch {branch}");
+        warn_shallow_since_ignored(opts);
+        return create_worktree(
+            &repo.toplevel,
+            cfg_root,
+            &branch,
+            &NewOptions {
+                base_remote: Some(remote),
+                ..opts.clone()
+            },
+        );
     }
+    eprintln!("gw: no remotes configured; creating new branch {branch}");
+    warn_shallow_since_ignored(opts);
 
-    create_worktree(
-        &repo.toplevel,
-        cfg_root,
-        &branch,
-        worktrees_dir,
-        path,
-        base,
-        no_hooks,
-    )
+    create_worktree(&repo.toplevel, cfg_root, &branch, opts)
+}
+
+fn warn_shallow_since_ignored(opts: &NewOptions) {
+    if opts.shallow_since.is_some() {
+        eprintln!("gw: --shallow-since only applies to branches fetched from a remote; ignoring");
+    }
+}
+
+/// Options for `gw rm`.
+#[derive(Debug, Clone, Default)]
+struct RemoveOptions {
+    yes: bool,
+    force: bool,
+    dry_run: bool,
+    keep_config: bool,
+    /// `git branch -D` the worktree's branch once it is removed.
+    delete_branch: bool,
+    /// Directory to save the worktree's uncommitted changes into before removing it.
+    archive: Option<PathBuf>,
 }
 
 fn remove_worktree(
+    cfg_root: &Path,
     repo_cwd: &Path,
     path: &Path,
-    yes: bool,
-    force: bool,
+    opts: &RemoveOptions,
 ) -> anyhow::Result<Option<PathBuf>> {
+    let RemoveOptions {
+        yes,
+        mut force,
+        dry_run,
+        keep_config,
+        delete_branch,
+        ref archive,
+    } = *opts;
     let repo = RepoContext::detect_from_path(repo_cwd)?;
 
-    let out = std::process::Command::new("git")
-        .current_dir(&repo.toplevel)
-        .args(["worktree", "list", "--porcelain"])
-        .output()?;
-    if !out.status.success() {
-        anyhow::bail!(
-            "git worktree list failed: {}",
-            String::from_utf8_lossy(&out.stderr)
-        );
-    }
-
-    let entries = parse_worktree_porcelain(&String::from_utf8(out.stdout)?);
+    let entries = list_worktrees(&repo.toplevel)?;
     let main_path = entries
         .first()
-        .map(|e| PathBuf::from(&e.path))
+        .map(|e| e.path.clone())
         .unwrap_or(repo.toplevel.clone());
 
     let target = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
@@ -620,6 +3440,66 @@ fn remove_worktree(
         );
     }
 
+    let canonical = |e: &WorktreeEntry| std::fs::canonicalize(&e.path).unwrap_or(e.path.clone());
+    let entry = entries.iter().find(|e| canonical(e) == target);
+    // Resolved before removal: the worktree's branch is gone from the list afterwards.
+    let branch = entry.and_then(|e| e.branch.clone());
+    if delete_branch
+        && let Some(b) = &branch
+        && let Some(other) = entries
+            .iter()
+            .find(|e| e.branch.as_ref() == Some(b) && canonical(e) != target)
+    {
+        anyhow::bail!(
+            "refusing to delete branch {b}: it is also checked out in {}; re-run without --delete-branch",
+            other.display_path()
+        );
+    }
+
+    if dry_run {
+        if entry.is_none() {
+            eprintln!(
+                "gw: dry-run: {} is not a registered worktree; git would refuse",
+                target.display()
+            );
+        }
+        let mut cmd = vec!["git", "worktree", "remove"];
+        if force {
+            cmd.push("--force");
+        }
+        let target_str = target.to_string_lossy();
+        let quoted = shell_quote(&target_str);
+        cmd.push(&quoted);
+        eprintln!("gw: dry-run: would run: {}", cmd.join(" "));
+        match branch.as_deref() {
+            Some(b) if delete_branch => {
+                eprintln!("gw: dry-run: branch {b} would be deleted (git branch -D)")
+            }
+            Some(b) => eprintln!("gw: dry-run: branch {b} would be kept"),
+            None => eprintln!("gw: dry-run: worktree is detached; no branch affected"),
+        }
+        match count_uncommitted(&target) {
+            Ok(0) => eprintln!("gw: dry-run: worktree is clean"),
+            Ok(n) if force || archive.is_some() => eprintln!(
+                "gw: dry-run: worktree is dirty ({n} uncommitted entries); removal would discard them"
+            ),
+            Ok(n) => eprintln!(
+                "gw: dry-run: worktree is dirty ({n} uncommitted entries); removal would be refused without --force"
+            ),
+            Err(e) => eprintln!("gw: dry-run: could not check worktree status: {e:#}"),
+        }
+        if let Some(dir) = archive {
+            eprintln!(
+                "gw: dry-run: uncommitted changes would be archived to {}",
+                dir.display()
+            );
+        }
+        if !keep_config {
+            eprintln!("gw: dry-run: per-worktree config metadata would be pruned");
+        }
+        return Ok(None);
+    }
+
     let can_prompt = std::io::stdin().is_terminal() && std::io::stderr().is_terminal();
 
     if !yes {
@@ -636,6 +3516,42 @@ fn remove_worktree(
         }
     }
 
+    // `git worktree remove` only runs with `--force` when asked for (or once `--archive` saved
+    // the changes), so uncommitted work is never discarded implicitly.
+    if !force && archive.is_none() && entry.is_some() {
+        let dirty = uncommitted_paths(&target)?;
+        if !dirty.is_empty() {
+            let listing = dirty
+                .iter()
+                .map(|p| format!("  {p}"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            if !can_prompt {
+                anyhow::bail!(
+                    "{} has uncommitted changes; re-run with --force to discard them:\n{listing}",
+                    target.display()
+                );
+            }
+            eprintln!("{} has uncommitted changes:\n{listing}", target.display());
+            let ok = dialoguer::Confirm::new()
+                .with_prompt("Force remove and discard them?")
+                .default(false)
+                .interact()?;
+            if !ok {
+                let go = dialoguer::Confirm::new()
+                    .with_prompt("Go to the worktree directory instead?")
+                    .default(true)
+                    .interact()?;
+                if go {
+                    eprintln!("Worktree not removed.");
+                    return Ok(Some(target));
+                }
+                anyhow::bail!("worktree not removed");
+            }
+            force = true;
+        }
+    }
+
     // If we're currently inside the target, move to the main worktree so the OS doesn't
     // reject directory deletion as "busy", and so git has a stable CWD.
     let orig_cwd = std::env::current_dir()
@@ -648,47 +3564,47 @@ fn remove_worktree(
         let _ = std::env::set_current_dir(&main);
     }
 
-    let run_remove = |use_force: bool| -> anyhow::Result<std::process::Output> {
-        let mut cmd = std::process::Command::new("git");
-        cmd.current_dir(&main).args(["worktree", "remove"]);
-        if use_force {
-            cmd.arg("--force");
+    run_pre_remove_hooks(
+        cfg_root,
+        &RepoContext::detect_from_path(&main)?,
+        &target,
+        None,
+    )?;
+
+    if let Some(dir) = archive {
+        let files = archive_uncommitted(&target, dir, branch.as_deref())?;
+        if files.is_empty() {
+            eprintln!("gw: worktree is clean; nothing to archive");
         }
-        Ok(cmd.arg(&target).output()?)
-    };
+        for f in files {
+            eprintln!("gw: archived uncommitted changes to {}", f.display());
+        }
+        // Everything `--force` would discard is saved now.
+        force = true;
+    }
 
-    let mut output = run_remove(force)?;
+    let mut cmd = std::process::Command::new("git");
+    cmd.current_dir(&main).args(["worktree", "remove"]);
+    if force {
+        cmd.arg("--force");
+    }
+    let output = cmd.arg(&target).output()?;
     if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        let is_dirty = stderr.contains("contains modified or untracked files");
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr));
+    }
 
-        if is_dirty && !force {
-            if !can_prompt {
-                anyhow::bail!("{stderr}");
-            }
+    if !keep_config {
+        prune_worktree_config(cfg_root, &repo, &target, &main)?;
+    }
 
-            let ok = dialoguer::Confirm::new()
-                .with_prompt("Worktree has modified/untracked files. Force remove?")
-                .default(false)
-                .interact()?;
-            if ok {
-                output = run_remove(true)?;
-                if !output.status.success() {
-                    anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr));
-                }
-            } else {
-                let go = dialoguer::Confirm::new()
-                    .with_prompt("Go to the worktree directory instead?")
-                    .default(true)
-                    .interact()?;
-                if go {
-                    eprintln!("Worktree not removed.");
-                    return Ok(Some(target));
-                }
-                anyhow::bail!("worktree not removed");
+    if delete_branch {
+        match &branch {
+            Some(b) => {
+                git_stdout(&main, &["branch", "-D", b])
+                    .with_context(|| format!("worktree removed, but deleting branch {b} failed"))?;
+                eprintln!("gw: deleted branch {b}");
             }
-        } else {
-            anyhow::bail!("{stderr}");
+            None => eprintln!("gw: worktree was detached; no branch to delete"),
         }
     }
 
@@ -700,35 +3616,351 @@ fn remove_worktree(
     Ok(None)
 }
 
-#[derive(Debug, Clone)]
+/// Save `wt_path`'s uncommitted changes into `dir`: tracked changes as
+/// `<branch>-<timestamp>.patch` (`git diff --binary HEAD`) and untracked files as a `.tar` of the
+/// same name. Returns the files written; none when the worktree is clean.
+fn archive_uncommitted(
+    wt_path: &Path,
+    dir: &Path,
+    branch: Option<&str>,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let diff = std::process::Command::new("git")
+        .current_dir(wt_path)
+        .args(["diff", "--binary", "HEAD"])
+        .output()?;
+    if !diff.status.success() {
+        anyhow::bail!(
+            "git diff HEAD failed: {}",
+            String::from_utf8_lossy(&diff.stderr)
+        );
+    }
+    let untracked = git_stdout(
+        wt_path,
+        &["ls-files", "--others", "--exclude-standard", "-z"],
+    )?;
+    if diff.stdout.is_empty() && untracked.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("creating archive dir {}", dir.display()))?;
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| format_utc(d.as_secs()).replace(['-', ':'], ""))
+        .unwrap_or_default();
+    let stem = format!("{}-{stamp}", branch.unwrap_or("detached").replace('/', "-"));
+    let mut written = Vec::new();
+
+    if !diff.stdout.is_empty() {
+        let patch = dir.join(format!("{stem}.patch"));
+        std::fs::write(&patch, &diff.stdout)
+            .with_context(|| format!("writing {}", patch.display()))?;
+        written.push(patch);
+    }
+    if !untracked.is_empty() {
+        let tar = dir.join(format!("{stem}.tar"));
+        let mut child = std::process::Command::new("tar")
+            .current_dir(wt_path)
+            .arg("-cf")
+            .arg(&tar)
+            .args(["--null", "-T", "-"])
+            .stdin(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .context("running tar")?;
+        child
+            .stdin
+            .take()
+            .expect("piped stdin")
+            .write_all(untracked.as_bytes())?;
+        let out = child.wait_with_output()?;
+        if !out.status.success() {
+            anyhow::bail!(
+                "archiving untracked files failed: {}",
+                String::from_utf8_lossy(&out.stderr)
+            );
+        }
+        written.push(tar);
+    }
+    Ok(written)
+}
+
+/// `git worktree prune -v`, then drop config metadata of the pruned worktrees and move the
+/// anchor to the main worktree if it no longer exists. Returns the pruned worktree paths.
+fn prune_worktrees(cfg_root: &Path, repo: &RepoContext) -> anyhow::Result<Vec<PathBuf>> {
+    let list = || -> anyhow::Result<Vec<PathBuf>> {
+        Ok(list_worktrees(&repo.toplevel)?
+            .into_iter()
+            .map(|e| e.path)
+            .collect())
+    };
+    let before = list()?;
+    // git reports what it prunes (and why) on stderr.
+    let status = std::process::Command::new("git")
+        .current_dir(&repo.toplevel)
+        .args(["worktree", "prune", "-v"])
+        .status()?;
+    if !status.success() {
+        anyhow::bail!("git worktree prune failed");
+    }
+    let after = list()?;
+    let pruned: Vec<PathBuf> = before.into_iter().filter(|p| !after.contains(p)).collect();
+
+    let main = main_worktree_path(repo)?;
+    for p in &pruned {
+        prune_worktree_config(cfg_root, repo, p, &main)?;
+    }
+    if let Some(cfg) = load_repo_config(cfg_root, repo)
+        && !Path::new(&cfg.anchor_path).exists()
+    {
+        prune_worktree_config(cfg_root, repo, Path::new(&cfg.anchor_path), &main)?;
+    }
+    Ok(pruned)
+}
+
+/// `gw prune-branches`: list the local branches [`prunable_branches`] finds, confirm, and delete
+/// them with `git branch -d` from a worktree that has the default branch checked out (so git's
+/// own merged check agrees).
+fn prune_branches(cfg_root: &Path, repo: &RepoContext, yes: bool) -> anyhow::Result<()> {
+    let global = load_global_config(cfg_root)?;
+    let repo_cfg = load_repo_config(cfg_root, repo);
+    let configured = repo_cfg
+        .as_ref()
+        .and_then(|c| c.base.clone())
+        .or(global.base);
+    let default = default_branch(repo, configured.as_deref())?;
+    let entries = list_worktrees(&repo.toplevel)?;
+    let branches = prunable_branches(repo, &default, &entries)?;
+    if branches.is_empty() {
+        eprintln!("gw: no merged branches without a worktree (default branch: {default})");
+        return Ok(());
+    }
+    for b in &branches {
+        println!("{b}");
+    }
+
+    if !yes {
+        if !(std::io::stdin().is_terminal() && std::io::stderr().is_terminal()) {
+            anyhow::bail!("refusing to prompt without a TTY; re-run with --yes");
+        }
+        let ok = dialoguer::Confirm::with_theme(&*prompt_theme())
+            .with_prompt(format!(
+                "Delete {} branch(es) merged into {default}?",
+                branches.len()
+            ))
+            .default(false)
+            .interact()?;
+        if !ok {
+            anyhow::bail!("prune-branches cancelled");
+        }
+    }
+
+    let cwd = entries
+        .iter()
+        .find(|e| e.branch.as_deref() == Some(default.as_str()))
+        .map_or(repo.toplevel.as_path(), |e| e.path.as_path());
+    let mut failed = 0;
+    for b in &branches {
+        match git_stdout(cwd, &["branch", "-d", b]) {
+            Ok(_) => eprintln!("gw: deleted branch {b}"),
+            Err(e) => {
+                eprintln!("gw: could not delete {b}: {e:#}");
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        anyhow::bail!("{failed} branch(es) couldn't be deleted");
+    }
+    Ok(())
+}
+
+/// The branch `gw prune-branches` treats as the default: `configured` (config `base`) if it is
+/// a local branch, else origin's default branch if there is a local one of that name, else the
+/// branch checked out in the main worktree.
+fn default_branch(repo: &RepoContext, configured: Option<&str>) -> anyhow::Result<String> {
+    if let Some(base) = configured.filter(|b| *b != REMOTE_DEFAULT_BASE)
+        && repo.git_show_ref_head(base)?
+    {
+        return Ok(base.to_string());
+    }
+    if let Some(b) = git_stdout(
+        &repo.toplevel,
+        &["symbolic-ref", "--short", "refs/remotes/origin/HEAD"],
+    )
+    .ok()
+    .and_then(|s| s.trim().strip_prefix("origin/").map(str::to_string))
+        && repo.git_show_ref_head(&b)?
+    {
+        return Ok(b);
+    }
+    let entries = list_worktrees(&repo.toplevel)?;
+    main_worktree_idx(&entries)
+        .and_then(|i| entries[i].branch.clone())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "could not determine the default branch; set one with `gw new --set-main-branch`"
+            )
+        })
+}
+
+/// Local branches fully merged into `default`, excluding `default` itself and every branch
+/// checked out in one of `entries`.
+fn prunable_branches(
+    repo: &RepoContext,
+    default: &str,
+    entries: &[WorktreeEntry],
+) -> anyhow::Result<Vec<String>> {
+    let merged = git_stdout(
+        &repo.toplevel,
+        &[
+            "branch",
+            "--merged",
+            &format!("refs/heads/{default}"),
+            "--format=%(refname:short)",
+        ],
+    )?;
+    Ok(merged
+        .lines()
+        .map(str::trim)
+        .filter(|b| !b.is_empty() && *b != default)
+        .filter(|b| !entries.iter().any(|e| e.branch.as_deref() == Some(*b)))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Drop config metadata keyed by a removed worktree's path, and move the anchor off it.
+/// Returns whether the repo config changed.
+pub(crate) fn prune_worktree_config(
+    cfg_root: &Path,
+    repo: &RepoContext,
+    removed: &Path,
+    main: &Path,
+) -> anyhow::Result<bool> {
+    let Some(mut cfg) = load_repo_config(cfg_root, repo) else {
+        return Ok(false);
+    };
+    // The worktree dir is gone by now, so canonicalize via its parent.
+    let removed = normalize_path(removed);
+    let is_removed = |p: &str| normalize_path(Path::new(p)) == removed;
+
+    let before = cfg.worktrees.len();
+    cfg.worktrees.retain(|p, _| !is_removed(p));
+    let mut changed = cfg.worktrees.len() != before;
+    if is_removed(&cfg.anchor_path) {
+        cfg.anchor_path = main.to_string_lossy().to_string();
+        changed = true;
+    }
+    if changed {
+        save_repo_config(cfg_root, repo, &cfg)?;
+    }
+    Ok(changed)
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
 pub(crate) struct WorktreeEntry {
-    pub(crate) path: String,
+    /// Exactly as git reports it, so non-UTF-8 paths still work for git and `cd`; use
+    /// [`WorktreeEntry::display_path`] for output.
+    #[serde(serialize_with = "serialize_path_lossy")]
+    pub(crate) path: PathBuf,
     pub(crate) branch: Option<String>,
+    /// Full SHA of the checked-out commit (`None` for the bare entry or an unborn branch).
+    pub(crate) head: Option<String>,
+    /// The bare repository entry (no checkout), listed first for bare repos.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) bare: bool,
+}
+
+impl WorktreeEntry {
+    /// `path` for display (invalid UTF-8 replaced with `\u{FFFD}`).
+    pub(crate) fn display_path(&self) -> std::borrow::Cow<'_, str> {
+        self.path.to_string_lossy()
+    }
+
+    /// Abbreviated `head` for display.
+    pub(crate) fn short_head(&self) -> Option<&str> {
+        self.head.as_deref().map(|h| h.get(..7).unwrap_or(h))
+    }
+}
+
+/// Index of the main worktree: `git worktree list` puts it first, unless the repo is bare
+/// (then the first entry is the bare repo itself and there is no main checkout).
+pub(crate) fn main_worktree_idx(entries: &[WorktreeEntry]) -> Option<usize> {
+    entries.first().filter(|e| !e.bare).map(|_| 0)
+}
+
+fn serialize_path_lossy<S: serde::Serializer>(p: &Path, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(&p.to_string_lossy())
+}
+
+/// A path from raw bytes: byte-exact on unix, lossily decoded elsewhere.
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        PathBuf::from(std::ffi::OsStr::from_bytes(bytes))
+    }
+    #[cfg(not(unix))]
+    {
+        PathBuf::from(String::from_utf8_lossy(bytes).as_ref())
+    }
 }
 
-pub(crate) fn parse_worktree_porcelain(s: &str) -> Vec<WorktreeEntry> {
+/// `git worktree list --porcelain` run in `cwd`, parsed.
+pub(crate) fn list_worktrees(cwd: &Path) -> anyhow::Result<Vec<WorktreeEntry>> {
+    let out = std::process::Command::new("git")
+        .current_dir(cwd)
+        .args(["worktree", "list", "--porcelain"])
+        .output()?;
+    if !out.status.success() {
+        anyhow::bail!(
+            "git worktree list failed: {}",
+            String::from_utf8_lossy(&out.stderr).trim()
+        );
+    }
+    Ok(parse_worktree_porcelain(&out.stdout))
+}
+
+/// Parse `git worktree list --porcelain` output. It is read as bytes because paths need not be
+/// UTF-8; branch names are decoded lossily.
+pub(crate) fn parse_worktree_porcelain(s: &[u8]) -> Vec<WorktreeEntry> {
     let mut entries = Vec::new();
-    let mut cur_path: Option<String> = None;
+    let mut cur_path: Option<PathBuf> = None;
     let mut cur_branch: Option<String> = None;
+    let mut cur_head: Option<String> = None;
+    let mut cur_bare = false;
 
-    for line in s.lines() {
-        let line = line.trim_end();
+    for line in s.split(|&b| b == b'\n') {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
         if line.is_empty() {
             if let Some(path) = cur_path.take() {
                 entries.push(WorktreeEntry {
                     path,
                     branch: cur_branch.take(),
+                    head: cur_head.take(),
+                    bare: std::mem::take(&mut cur_bare),
                 });
             }
             continue;
         }
-        if let Some(rest) = line.strip_prefix("worktree ") {
-            cur_path = Some(rest.to_string());
+        if line == b"bare" {
+            cur_bare = true;
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix(b"worktree ") {
+            cur_path = Some(path_from_bytes(rest));
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix(b"HEAD ") {
+            // An unborn branch reports the all-zero object id.
+            if rest.iter().any(|&b| b != b'0') {
+                cur_head = Some(String::from_utf8_lossy(rest).into_owned());
+            }
             continue;
         }
-        if let Some(rest) = line.strip_prefix("branch ") {
-            let b = rest.strip_prefix("refs/heads/").unwrap_or(rest).to_string();
-            cur_branch = Some(b);
+        if let Some(rest) = line.strip_prefix(b"branch ") {
+            let b = rest.strip_prefix(b"refs/heads/").unwrap_or(rest);
+            cur_branch = Some(String::from_utf8_lossy(b).into_owned());
             continue;
         }
     }
@@ -737,6 +3969,8 @@ pub(crate) fn parse_worktree_porcelain(s: &str) -> Vec<WorktreeEntry> {
         entries.push(WorktreeEntry {
             path,
             branch: cur_branch.take(),
+            head: cur_head.take(),
+            bare: cur_bare,
         });
     }
 
@@ -791,18 +4025,110 @@ impl RepoContext {
         git_stdout(&self.toplevel, &args_ref)
     }
 
-    pub(crate) fn git_show_ref_head(&self, branch: &str) -> anyhow::Result<bool> {
-        let status = std::process::Command::new("git")
-            .current_dir(&self.toplevel)
-            .args([
-                "show-ref",
-                "--verify",
-                "--quiet",
-                &format!("refs/heads/{branch}"),
-            ])
-            .status()?;
-        Ok(status.success())
-    }
+    pub(crate) fn git_show_ref_head(&self, branch: &str) -> anyhow::Result<bool> {
+        let status = std::process::Command::new("git")
+            .current_dir(&self.toplevel)
+            .args([
+                "show-ref",
+                "--verify",
+                "--quiet",
+                &format!("refs/heads/{branch}"),
+            ])
+            .status()?;
+        Ok(status.success())
+    }
+}
+
+/// Quote `s` for POSIX shells, leaving simple words (paths without spaces/specials) as-is.
+pub(crate) fn shell_quote(s: &str) -> String {
+    let safe = !s.is_empty()
+        && s.chars().all(|c| {
+            c.is_ascii_alphanumeric()
+                || matches!(c, '/' | '.' | '_' | '-' | ':' | '@' | '+' | ',' | '=' | '%')
+        });
+    if safe {
+        return s.to_string();
+    }
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// Shell command that changes into `path`.
+fn cd_command(path: &Path) -> String {
+    format!("cd {}", shell_quote(&path.to_string_lossy()))
+}
+
+/// Put `text` on the clipboard with the first available platform tool, or via an OSC 52 escape
+/// sequence (which the terminal emulator handles, even across SSH). Returns the method used.
+fn copy_to_clipboard(text: &str) -> anyhow::Result<&'static str> {
+    let over_ssh =
+        std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some();
+    if !over_ssh {
+        let tools: &[(&str, &[&str])] = &[
+            ("pbcopy", &[]),
+            ("wl-copy", &[]),
+            ("xclip", &["-selection", "clipboard"]),
+            ("xsel", &["--clipboard", "--input"]),
+            ("clip.exe", &[]),
+        ];
+        for (program, args) in tools {
+            let Ok(mut child) = std::process::Command::new(program)
+                .args(*args)
+                .stdin(std::process::Stdio::piped())
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .spawn()
+            else {
+                continue;
+            };
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(text.as_bytes())?;
+            }
+            if child.wait()?.success() {
+                return Ok(program);
+            }
+        }
+    }
+    let mut err = std::io::stderr();
+    write!(err, "\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))?;
+    err.flush()?;
+    Ok("OSC 52")
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, &b)| acc | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Number of modified + untracked entries in a worktree (`git status --porcelain` lines).
+pub(crate) fn count_uncommitted(wt_path: &Path) -> anyhow::Result<usize> {
+    let out = git_stdout(wt_path, &["status", "--porcelain"])?;
+    Ok(out.lines().filter(|l| !l.trim().is_empty()).count())
+}
+
+/// Paths with uncommitted changes (modified, staged or untracked) in `wt_path`, as
+/// `git status --porcelain` reports them.
+pub(crate) fn uncommitted_paths(wt_path: &Path) -> anyhow::Result<Vec<String>> {
+    let out = git_stdout(wt_path, &["status", "--porcelain"])?;
+    Ok(out
+        .lines()
+        .filter_map(|l| l.get(3..))
+        .filter(|p| !p.is_empty())
+        .map(str::to_string)
+        .collect())
 }
 
 fn git_stdout(cwd: &Path, args: &[&str]) -> anyhow::Result<String> {
@@ -820,7 +4146,13 @@ fn git_stdout(cwd: &Path, args: &[&str]) -> anyhow::Result<String> {
     Ok(String::from_utf8(out.stdout)?)
 }
 
+/// Set from `--config-dir`; takes precedence over `GW_CONFIG_DIR`.
+static CONFIG_DIR_OVERRIDE: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+
 pub(crate) fn config_root() -> anyhow::Result<PathBuf> {
+    if let Some(p) = CONFIG_DIR_OVERRIDE.get() {
+        return Ok(p.clone());
+    }
     if let Ok(p) = std::env::var("GW_CONFIG_DIR") {
         return Ok(PathBuf::from(p));
     }
@@ -831,22 +4163,221 @@ pub(crate) fn config_root() -> anyhow::Result<PathBuf> {
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub(crate) struct Hook {
     pub(crate) command: String,
+    /// Keep running later hooks if this one fails (the run still fails overall).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) continue_on_error: bool,
+    /// When the hook runs relative to `git worktree add`.
+    #[serde(default, skip_serializing_if = "HookPhase::is_default")]
+    pub(crate) when: HookPhase,
+    /// Lets the hook be run on demand with `gw hooks run <name>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) name: Option<String>,
+    /// Kill the hook and fail once it has run this long (default: no limit).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) timeout_secs: Option<u64>,
+}
+
+impl Hook {
+    /// `[name] command`, or just the command for an unnamed hook.
+    fn label(&self) -> String {
+        match &self.name {
+            Some(name) => format!("[{name}] {}", self.command),
+            None => self.command.clone(),
+        }
+    }
+}
+
+/// The point in `gw new` at which a hook runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum HookPhase {
+    /// In the repo toplevel before `git worktree add`; failing aborts the creation.
+    PreCreate,
+    /// In the new worktree after it was created.
+    #[default]
+    PostCreate,
+    /// In a worktree about to be removed (`gw rm`, picker delete); failing aborts the removal.
+    PreRemove,
+}
+
+impl HookPhase {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The phase as written in config (`when = "..."`).
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            HookPhase::PreCreate => "pre_create",
+            HookPhase::PostCreate => "post_create",
+            HookPhase::PreRemove => "pre_remove",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub(crate) struct GlobalConfig {
     #[serde(default)]
     pub(crate) hooks: Vec<Hook>,
+    /// Open the selected worktree in `editor` after `gw go` (in addition to printing the path).
+    #[serde(default)]
+    pub(crate) open_on_go: bool,
+    /// Editor command for `open_on_go` (default: `$VISUAL`, then `$EDITOR`).
+    #[serde(default)]
+    pub(crate) editor: Option<String>,
+    /// In the picker, typing filters right away (no `/` needed); disables letter hotkeys.
+    #[serde(default)]
+    pub(crate) instant_filter: bool,
+    /// Shell command previewing the highlighted worktree beside the picker's worktree list;
+    /// `{path}` and `{branch}` are replaced with the worktree's (quoted) path and branch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) preview_cmd: Option<String>,
+    /// Worktrees created with `n` in the picker don't become the repo's anchor or update its
+    /// recency (like `gw new --keep-cwd`).
+    #[serde(default)]
+    pub(crate) picker_keep_cwd: bool,
+    /// In the picker, `q` asks before quitting while a filter (text or dirty) narrows the list.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) confirm_quit_with_selection: bool,
+    /// Default base for new branches: a ref, or `remote-default` for the fetched remote default
+    /// branch. Per-repo `base` takes precedence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) base: Option<String>,
+    /// `gw new` refuses (without `--force`) once a repo has this many non-main worktrees.
+    /// Per-repo `max_worktrees` takes precedence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) max_worktrees: Option<usize>,
+    /// Default for `gw new --branch-exists-action`. Per-repo value takes precedence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) branch_exists_action: Option<BranchExistsAction>,
+    /// Write a `.gw-worktree.md` context file into new worktrees. Per-repo value takes precedence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) worktree_readme: Option<bool>,
+    /// Default for `gw new --link-worktree-git-hooks`. Per-repo value takes precedence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) link_worktree_git_hooks: Option<bool>,
+    /// Refuse `gw new` (without `--force`) when the target filesystem has less free space than
+    /// this. Per-repo value takes precedence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) min_free_disk_mb: Option<u64>,
+    /// Where repo `copy_on_new` paths are copied from. Per-repo value takes precedence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) copy_source: Option<CopySource>,
+    /// Run up to this many hooks of a phase at once (default 1: one after another). Their output
+    /// is then buffered and printed in config order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) hook_concurrency: Option<usize>,
+    /// Don't print the output of hooks that succeed (failed hooks' output is in the error).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) hook_quiet: bool,
+    /// Initial order of the picker's repo list (`gw go --sort-repos`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) sort_repos: Option<SortRepos>,
+    /// Worktrees of repos without a `worktrees_dir` go under `<this>/<repo name>` instead of
+    /// prompting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) default_worktrees_parent: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub(crate) struct RepoConfig {
     pub(crate) repo_name: String,
     pub(crate) git_common_dir: String,
     pub(crate) anchor_path: String,
     pub(crate) worktrees_dir: Option<String>,
+    /// Used instead of `worktrees_dir` on Windows, for configs shared across OSes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) worktrees_dir_windows: Option<String>,
+    /// Used instead of `worktrees_dir` everywhere but Windows.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) worktrees_dir_unix: Option<String>,
     #[serde(default)]
     pub(crate) hooks: Vec<Hook>,
+    /// `KEY=value` file whose variables are injected into hook environments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) env_file: Option<String>,
+    /// Default base for new branches (see [`GlobalConfig::base`]). Also read as `main_branch`.
+    #[serde(
+        default,
+        alias = "main_branch",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub(crate) base: Option<String>,
+    /// Prepended to the worktree directory name (not the branch), e.g. `wt-` -> `<dir>/wt-feat`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) worktree_dir_prefix: Option<String>,
+    /// Limit on non-main worktrees (see [`GlobalConfig::max_worktrees`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) max_worktrees: Option<usize>,
+    /// Default for `gw new --branch-exists-action`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) branch_exists_action: Option<BranchExistsAction>,
+    /// Write a `.gw-worktree.md` context file into new worktrees.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) worktree_readme: Option<bool>,
+    /// Default for `gw new --link-worktree-git-hooks`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) link_worktree_git_hooks: Option<bool>,
+    /// Minimum free disk space for `gw new` (see [`GlobalConfig::min_free_disk_mb`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) min_free_disk_mb: Option<u64>,
+    /// Untracked files/dirs (relative paths, e.g. `.env`) copied into each new worktree.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) copy_on_new: Vec<String>,
+    /// Where `copy_on_new` paths are copied from (default: `anchor`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) copy_source: Option<CopySource>,
+    /// Name or config hash of another known repo whose hooks also run on `gw new` here.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) inherit_hooks_from: Option<String>,
+    /// Default for `gw new --require-clean`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) require_clean: Option<bool>,
+    /// When a worktree of this repo was last picked in `gw go` (unix seconds).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) last_opened: Option<u64>,
+    /// Branch of the worktree last picked in `gw go`; the picker starts on it next time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) last_branch: Option<String>,
+    /// Per-worktree metadata keyed by worktree path; pruned when the worktree is removed.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) worktrees: BTreeMap<String, WorktreeMeta>,
+}
+
+impl RepoConfig {
+    /// The worktrees dir in effect on this OS: `worktrees_dir_windows`/`worktrees_dir_unix` when
+    /// set, else `worktrees_dir`.
+    pub(crate) fn platform_worktrees_dir(&self) -> Option<&str> {
+        let os_specific = if cfg!(windows) {
+            &self.worktrees_dir_windows
+        } else {
+            &self.worktrees_dir_unix
+        };
+        os_specific.as_deref().or(self.worktrees_dir.as_deref())
+    }
+
+    /// The key [`Self::platform_worktrees_dir`] reads, for storing a new dir.
+    fn platform_worktrees_dir_mut(&mut self) -> &mut Option<String> {
+        let os_specific = if cfg!(windows) {
+            &mut self.worktrees_dir_windows
+        } else {
+            &mut self.worktrees_dir_unix
+        };
+        if os_specific.is_some() {
+            os_specific
+        } else {
+            &mut self.worktrees_dir
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub(crate) struct WorktreeMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) label: Option<String>,
+    /// Unix seconds when the worktree was last picked; orders the picker's recency sort.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) last_opened: Option<u64>,
 }
 
 pub(crate) fn load_global_config(cfg_root: &Path) -> anyhow::Result<GlobalConfig> {
@@ -858,6 +4389,41 @@ pub(crate) fn load_global_config(cfg_root: &Path) -> anyhow::Result<GlobalConfig
     Ok(toml::from_str(&s)?)
 }
 
+fn save_global_config(cfg_root: &Path, cfg: &GlobalConfig) -> anyhow::Result<()> {
+    std::fs::create_dir_all(cfg_root)?;
+    std::fs::write(cfg_root.join("config.toml"), toml::to_string_pretty(cfg)?)?;
+    Ok(())
+}
+
+/// Put `hooks` in `order` (a permutation of their indices), or let the user sort them
+/// interactively when no order is given.
+fn reorder_hooks(hooks: Vec<Hook>, order: Option<Vec<usize>>) -> anyhow::Result<Vec<Hook>> {
+    if hooks.len() < 2 {
+        anyhow::bail!("nothing to reorder ({} hook(s))", hooks.len());
+    }
+    let order = match order {
+        Some(order) => order,
+        None => {
+            let items: Vec<&str> = hooks.iter().map(|h| h.command.as_str()).collect();
+            dialoguer::Sort::with_theme(&*prompt_theme())
+                .with_prompt("Reorder hooks (space to grab, arrows to move, enter to save)")
+                .items(&items)
+                .interact()?
+        }
+    };
+    let mut sorted = order.clone();
+    sorted.sort_unstable();
+    if sorted != (0..hooks.len()).collect::<Vec<_>>() {
+        anyhow::bail!(
+            "--order must list each index 0..{} exactly once (got {:?})",
+            hooks.len() - 1,
+            order
+        );
+    }
+    let mut slots: Vec<Option<Hook>> = hooks.into_iter().map(Some).collect();
+    Ok(order.iter().filter_map(|&i| slots[i].take()).collect())
+}
+
 fn repo_config_path(cfg_root: &Path, repo: &RepoContext) -> PathBuf {
     cfg_root
         .join("repos")
@@ -885,6 +4451,256 @@ pub(crate) fn save_repo_config(
     Ok(())
 }
 
+/// Find a known repo's config by config hash or repo name. Errors if the name is ambiguous.
+pub(crate) fn find_repo_config(
+    cfg_root: &Path,
+    name_or_hash: &str,
+) -> anyhow::Result<(String, RepoConfig)> {
+    let repos_dir = cfg_root.join("repos");
+    let by_hash = repos_dir.join(name_or_hash).join("config.toml");
+    if let Ok(s) = std::fs::read_to_string(&by_hash) {
+        return Ok((name_or_hash.to_string(), toml::from_str(&s)?));
+    }
+    let mut matches = Vec::new();
+    if repos_dir.exists() {
+        for ent in std::fs::read_dir(&repos_dir)? {
+            let dir = ent?.path();
+            let Ok(s) = std::fs::read_to_string(dir.join("config.toml")) else {
+                continue;
+            };
+            let Ok(cfg) = toml::from_str::<RepoConfig>(&s) else {
+                continue;
+            };
+            if cfg.repo_name == name_or_hash {
+                let hash = dir.file_name().unwrap_or_default().to_string_lossy();
+                matches.push((hash.to_string(), cfg));
+            }
+        }
+    }
+    match matches.len() {
+        0 => anyhow::bail!("no known repo named {name_or_hash:?}"),
+        1 => Ok(matches.remove(0)),
+        _ => anyhow::bail!(
+            "repo name {name_or_hash:?} is ambiguous; use one of these config hashes: {}",
+            matches
+                .iter()
+                .map(|(h, _)| h.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Names of other registered repos whose stored `worktrees_dir` is `dir` itself (not nested
+/// below it), so their worktrees would land next to this repo's.
+fn repos_sharing_worktrees_dir(cfg_root: &Path, self_hash: &str, dir: &Path) -> Vec<String> {
+    let Ok(ents) = std::fs::read_dir(cfg_root.join("repos")) else {
+        return Vec::new();
+    };
+    let dir = normalize_path(dir);
+    let mut names = Vec::new();
+    for ent in ents.flatten() {
+        if ent.file_name().to_string_lossy() == self_hash {
+            continue;
+        }
+        let Ok(s) = std::fs::read_to_string(ent.path().join("config.toml")) else {
+            continue;
+        };
+        let Ok(cfg) = toml::from_str::<RepoConfig>(&s) else {
+            continue;
+        };
+        let Some(Ok(theirs)) = cfg.platform_worktrees_dir().map(expand_path) else {
+            continue;
+        };
+        if normalize_path(&theirs) == dir {
+            names.push(cfg.repo_name);
+        }
+    }
+    names.sort();
+    names
+}
+
+/// Hooks inherited from `parent` (and, transitively, whatever it inherits from), outermost
+/// ancestor first. A chain that leads back to an already visited repo is cut with a warning.
+fn inherited_hooks(cfg_root: &Path, self_hash: &str, parent: &str) -> anyhow::Result<Vec<Hook>> {
+    let mut visited = vec![self_hash.to_string()];
+    let mut chain = Vec::new();
+    let mut next = Some(parent.to_string());
+    while let Some(name) = next.take() {
+        let (hash, cfg) = find_repo_config(cfg_root, &name)?;
+        if visited.contains(&hash) {
+            eprintln!("gw: inherit_hooks_from cycle at {name:?}; ignoring the rest of the chain");
+            break;
+        }
+        visited.push(hash);
+        next = cfg.inherit_hooks_from.clone();
+        chain.push(cfg.hooks);
+    }
+    Ok(chain.into_iter().rev().flatten().collect())
+}
+
+/// Canonicalize `p`, or just its parent if `p` itself no longer exists.
+fn normalize_path(p: &Path) -> PathBuf {
+    std::fs::canonicalize(p)
+        .ok()
+        .or_else(|| {
+            let parent = std::fs::canonicalize(p.parent()?).ok()?;
+            Some(parent.join(p.file_name()?))
+        })
+        .unwrap_or_else(|| p.to_path_buf())
+}
+
+/// One group of `repos/<hash>` registrations collapsed by [`prune_duplicate_repos`].
+struct RepoMerge {
+    name: String,
+    git_common_dir: String,
+    /// The surviving config dir name (hash of the canonical git common dir).
+    hash: String,
+    /// Number of registrations merged.
+    count: usize,
+}
+
+/// Collapse registrations whose `git_common_dir` canonicalizes to the same path into a single
+/// config under that path's hash. The most recently written config wins for scalar settings;
+/// hooks (by command) and per-worktree metadata are unioned.
+fn prune_duplicate_repos(cfg_root: &Path) -> anyhow::Result<Vec<RepoMerge>> {
+    let repos_dir = cfg_root.join("repos");
+    if !repos_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut groups: BTreeMap<PathBuf, Vec<(String, std::time::SystemTime, RepoConfig)>> =
+        BTreeMap::new();
+    for ent in std::fs::read_dir(&repos_dir)? {
+        let ent = ent?;
+        let cfg_path = ent.path().join("config.toml");
+        let Ok(s) = std::fs::read_to_string(&cfg_path) else {
+            continue;
+        };
+        let Ok(cfg) = toml::from_str::<RepoConfig>(&s) else {
+            continue;
+        };
+        let modified = std::fs::metadata(&cfg_path)?.modified()?;
+        let hash = ent.file_name().to_string_lossy().to_string();
+        groups
+            .entry(normalize_path(Path::new(&cfg.git_common_dir)))
+            .or_default()
+            .push((hash, modified, cfg));
+    }
+
+    let mut merges = Vec::new();
+    for (common, mut group) in groups {
+        if group.len() < 2 {
+            continue;
+        }
+        // Newest first.
+        group.sort_by_key(|g| std::cmp::Reverse(g.1));
+        let mut merged = group[0].2.clone();
+        merged.git_common_dir = common.to_string_lossy().to_string();
+        for (_, _, cfg) in &group[1..] {
+            for hook in &cfg.hooks {
+                if !merged.hooks.iter().any(|h| h.command == hook.command) {
+                    merged.hooks.push(hook.clone());
+                }
+            }
+            for (path, meta) in &cfg.worktrees {
+                merged
+                    .worktrees
+                    .entry(path.clone())
+                    .or_insert_with(|| meta.clone());
+            }
+            merged.last_opened = merged.last_opened.max(cfg.last_opened);
+        }
+
+        let hash = blake3::hash(merged.git_common_dir.as_bytes())
+            .to_hex()
+            .to_string();
+        let keep = repos_dir.join(&hash);
+        std::fs::create_dir_all(&keep)?;
+        std::fs::write(keep.join("config.toml"), toml::to_string_pretty(&merged)?)?;
+        let count = group.len();
+        for (h, _, _) in group {
+            if h != hash {
+                std::fs::remove_dir_all(repos_dir.join(&h))?;
+            }
+        }
+        merges.push(RepoMerge {
+            name: merged.repo_name,
+            git_common_dir: merged.git_common_dir,
+            hash,
+            count,
+        });
+    }
+    Ok(merges)
+}
+
+/// Move the `repos/<hash>` config of a repo that used to live at `old` to `repo`'s current hash,
+/// rewriting stored paths under the old location. Returns the new config path.
+fn migrate_repo_hash(
+    cfg_root: &Path,
+    old: &Path,
+    repo: &RepoContext,
+    force: bool,
+) -> anyhow::Result<PathBuf> {
+    let old = &normalize_path(old);
+    let repos_dir = cfg_root.join("repos");
+    let old_git_dir = old.join(".git");
+    let mut found: Option<(PathBuf, RepoConfig)> = None;
+    if repos_dir.exists() {
+        for ent in std::fs::read_dir(&repos_dir)? {
+            let dir = ent?.path();
+            let Ok(s) = std::fs::read_to_string(dir.join("config.toml")) else {
+                continue;
+            };
+            let Ok(cfg) = toml::from_str::<RepoConfig>(&s) else {
+                continue;
+            };
+            let common = Path::new(&cfg.git_common_dir);
+            if common == old || common == old_git_dir {
+                found = Some((dir, cfg));
+                break;
+            }
+        }
+    }
+    let Some((old_dir, mut cfg)) = found else {
+        anyhow::bail!("no repo config found for old location {}", old.display());
+    };
+
+    let new_dir = repos_dir.join(&repo.repo_hash);
+    if new_dir == old_dir {
+        anyhow::bail!("repo config is already stored under the current hash");
+    }
+    if new_dir.exists() {
+        if !force {
+            anyhow::bail!(
+                "config already exists for the new location ({}); re-run with --force to replace it",
+                new_dir.display()
+            );
+        }
+        std::fs::remove_dir_all(&new_dir)?;
+    }
+
+    let rewrite = |p: &str| -> String {
+        match Path::new(p).strip_prefix(old) {
+            Ok(rest) => repo.toplevel.join(rest).to_string_lossy().to_string(),
+            Err(_) => p.to_string(),
+        }
+    };
+    cfg.repo_name = repo.repo_name.clone();
+    cfg.git_common_dir = repo.git_common_dir.to_string_lossy().to_string();
+    cfg.anchor_path = rewrite(&cfg.anchor_path);
+    cfg.worktrees_dir = cfg.worktrees_dir.as_deref().map(rewrite);
+    cfg.worktrees_dir_windows = cfg.worktrees_dir_windows.as_deref().map(rewrite);
+    cfg.worktrees_dir_unix = cfg.worktrees_dir_unix.as_deref().map(rewrite);
+    cfg.worktrees = std::mem::take(&mut cfg.worktrees)
+        .into_iter()
+        .map(|(k, v)| (rewrite(&k), v))
+        .collect();
+
+    std::fs::rename(&old_dir, &new_dir)?;
+    save_repo_config(cfg_root, repo, &cfg)?;
+    Ok(repo_config_path(cfg_root, repo))
+}
+
 pub(crate) fn sanitize_branch_for_path(branch: &str) -> PathBuf {
     let mut out = PathBuf::new();
     for seg in branch.split('/') {
@@ -909,76 +4725,188 @@ pub(crate) fn sanitize_branch_for_path(branch: &str) -> PathBuf {
     out
 }
 
-pub(crate) fn run_hooks(
-    hooks: &[Hook],
-    repo: &RepoContext,
-    branch: &str,
-    wt_path: &Path,
-) -> anyhow::Result<()> {
-    if hooks.is_empty() {
-        return Ok(());
+/// Prepend `prefix` to the first component of a sanitized branch path (`feat/x` -> `wt-feat/x`).
+fn prefix_first_segment(path: &Path, prefix: &str) -> PathBuf {
+    if prefix.is_empty() {
+        return path.to_path_buf();
     }
+    let mut comps = path.components();
+    let mut out = match comps.next() {
+        Some(first) => {
+            let mut name = std::ffi::OsString::from(prefix);
+            name.push(first.as_os_str());
+            PathBuf::from(name)
+        }
+        None => PathBuf::from(prefix),
+    };
+    out.extend(comps);
+    out
+}
 
-    for hook in hooks {
-        #[cfg(unix)]
-        let mut cmd = {
-            let mut c = std::process::Command::new("sh");
-            c.args(["-lc", &hook.command]);
-            c
+/// Parse a dotenv-style `KEY=value` file. Blank lines and `#` comments are skipped, an
+/// optional `export ` prefix is accepted, and matching surrounding quotes are stripped.
+pub(crate) fn parse_env_file(s: &str) -> anyhow::Result<Vec<(String, String)>> {
+    let mut out = Vec::new();
+    for (i, line) in s.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            anyhow::bail!("line {}: expected KEY=value", i + 1);
         };
+        let key = key.trim();
+        if key.is_empty() {
+            anyhow::bail!("line {}: empty key", i + 1);
+        }
+        let value = value.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+            .unwrap_or(value);
+        out.push((key.to_string(), value.to_string()));
+    }
+    Ok(out)
+}
 
-        #[cfg(windows)]
-        let mut cmd = {
-            let mut c = std::process::Command::new("cmd");
-            c.args(["/C", &hook.command]);
-            c
-        };
+/// Load an env file for hooks. Unless `override_existing`, variables already present in the
+/// process environment win over the file.
+fn hook_env_from_file(
+    path: &Path,
+    override_existing: bool,
+) -> anyhow::Result<Vec<(String, String)>> {
+    let s = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read env file {}", path.display()))?;
+    let vars =
+        parse_env_file(&s).with_context(|| format!("invalid env file {}", path.display()))?;
+    Ok(vars
+        .into_iter()
+        .filter(|(k, _)| override_existing || std::env::var_os(k).is_none())
+        .collect())
+}
 
-        let status = cmd
-            .current_dir(wt_path)
-            .env("GW_WORKTREE_PATH", wt_path.to_string_lossy().to_string())
-            .env("GW_BRANCH", branch)
-            .env("GW_REPO_ROOT", repo.toplevel.to_string_lossy().to_string())
-            .status()?;
-        if !status.success() {
-            anyhow::bail!("hook failed: {}", hook.command);
-        }
+/// Deepest directory containing every path in `paths` (`None` if they only share `/`).
+fn common_parent(paths: &[PathBuf]) -> Option<PathBuf> {
+    let (first, rest) = paths.split_first()?;
+    let mut common: Vec<_> = first.components().collect();
+    for p in rest {
+        let n = common
+            .iter()
+            .zip(p.components())
+            .take_while(|(a, b)| **a == *b)
+            .count();
+        common.truncate(n);
     }
-    Ok(())
+    let out: PathBuf = common.into_iter().collect();
+    // Only sharing the filesystem root (or nothing) isn't a meaningful base.
+    out.parent()?;
+    Some(out)
+}
+
+/// Environment variable naming a parent directory for the worktrees of repos without a
+/// configured `worktrees_dir`.
+pub(crate) const WORKTREES_DIR_ENV: &str = "GW_WORKTREES_DIR";
+
+/// The worktrees base `gw new` uses without prompting, and where it comes from: the repo's
+/// `worktrees_dir`, else `<$GW_WORKTREES_DIR>/<repo name>`, else
+/// `<default_worktrees_parent>/<repo name>` from the global config.
+pub(crate) fn configured_worktrees_dir(
+    global: &GlobalConfig,
+    repo: &RepoContext,
+    repo_cfg: &RepoConfig,
+) -> Option<(String, &'static str)> {
+    if let Some(w) = repo_cfg.platform_worktrees_dir() {
+        return Some((w.to_string(), "repo"));
+    }
+    let nested = |parent: &str| {
+        Path::new(parent)
+            .join(&repo.repo_name)
+            .to_string_lossy()
+            .to_string()
+    };
+    if let Some(parent) = std::env::var(WORKTREES_DIR_ENV)
+        .ok()
+        .filter(|p| !p.is_empty())
+    {
+        return Some((nested(&parent), WORKTREES_DIR_ENV));
+    }
+    global
+        .default_worktrees_parent
+        .as_deref()
+        .map(|parent| (nested(parent), "default_worktrees_parent"))
 }
 
-pub(crate) fn prompt_worktrees_dir(repo: &RepoContext) -> anyhow::Result<PathBuf> {
-    use dialoguer::{Input, Select, theme::ColorfulTheme};
+/// The worktrees base `gw new` would use right now and where it comes from: as
+/// [`configured_worktrees_dir`], else `inferred` from existing worktrees. `None` when `gw new`
+/// would prompt without a guess.
+pub(crate) fn effective_worktrees_dir(
+    global: &GlobalConfig,
+    repo: &RepoContext,
+    repo_cfg: &RepoConfig,
+) -> anyhow::Result<Option<(PathBuf, &'static str)>> {
+    Ok(match configured_worktrees_dir(global, repo, repo_cfg) {
+        Some((dir, source)) => Some((expand_path(&dir)?, source)),
+        None => infer_worktrees_dir(repo).map(|dir| (dir, "inferred")),
+    })
+}
+
+/// Guess a worktrees base for repos that already have worktrees (e.g. created outside gw):
+/// the common parent directory of all non-main worktrees.
+pub(crate) fn infer_worktrees_dir(repo: &RepoContext) -> Option<PathBuf> {
+    let parents: Vec<PathBuf> = list_worktrees(&repo.toplevel)
+        .ok()?
+        .iter()
+        .skip(1)
+        .filter_map(|e| e.path.parent().map(Path::to_path_buf))
+        .collect();
+    common_parent(&parents)
+}
+
+pub(crate) fn prompt_worktrees_dir(
+    repo: &RepoContext,
+    inferred: Option<&Path>,
+) -> anyhow::Result<PathBuf> {
+    use dialoguer::{Input, Select};
 
-    let theme = ColorfulTheme::default();
+    let theme = prompt_theme();
 
     let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("could not determine home dir"))?;
-    let opt1 = home.join("worktrees").join(&repo.repo_name);
-    let opt2 = repo
-        .toplevel
-        .parent()
-        .unwrap_or(&repo.toplevel)
-        .join(format!("{}-worktrees", repo.repo_name));
-
-    let idx = Select::with_theme(&theme)
+    let mut options: Vec<PathBuf> = Vec::new();
+    let mut labels: Vec<String> = Vec::new();
+    if let Some(dir) = inferred {
+        options.push(dir.to_path_buf());
+        labels.push(format!(
+            "{} (where existing worktrees live)",
+            dir.to_string_lossy()
+        ));
+    }
+    for opt in [
+        home.join("worktrees").join(&repo.repo_name),
+        repo.toplevel
+            .parent()
+            .unwrap_or(&repo.toplevel)
+            .join(format!("{}-worktrees", repo.repo_name)),
+    ] {
+        labels.push(opt.to_string_lossy().to_string());
+        options.push(opt);
+    }
+    labels.push("Somewhere else".to_string());
+
+    let idx = Select::with_theme(&*theme)
         .with_prompt("Where should I put all worktrees for this repo?")
-        .items(&[
-            format!("{}", opt1.to_string_lossy()),
-            format!("{}", opt2.to_string_lossy()),
-            "Somewhere else".to_string(),
-        ])
+        .items(&labels)
         .default(0)
         .interact()?;
 
-    match idx {
-        0 => Ok(opt1),
-        1 => Ok(opt2),
-        _ => {
-            let raw: String = Input::with_theme(&theme)
+    match options.get(idx) {
+        Some(opt) => Ok(opt.clone()),
+        None => {
+            let raw: String = Input::with_theme(&*theme)
                 .with_prompt("Worktrees directory path")
                 .interact_text()?;
-            let expanded = shellexpand::tilde(&raw).to_string();
-            Ok(PathBuf::from(expanded))
+            expand_path(raw.trim())
         }
     }
 }
@@ -1007,6 +4935,218 @@ pub(crate) fn assign_hotkeys(n: usize, pool: &[char]) -> Vec<String> {
 mod tests {
     use super::*;
 
+    #[cfg(unix)]
+    #[test]
+    fn open_in_editor_launches_detached_editor_with_worktree_path() {
+        // spec: GW-PICK-010
+        let td = tempfile::TempDir::new().unwrap();
+        let wt = td.path().join("my wt");
+        std::fs::create_dir_all(&wt).unwrap();
+        let marker = td.path().join("editor-args");
+        let editor = td.path().join("stub-editor");
+        std::fs::write(
+            &editor,
+            format!(
+                "#!/bin/sh\necho editor-noise\nprintf '%s' \"$1\" > '{}'\n",
+                marker.display()
+            ),
+        )
+        .unwrap();
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&editor, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        open_in_editor(&editor.to_string_lossy(), &wt).unwrap();
+
+        // The editor runs detached; wait until it has written its argument, not just created
+        // the file.
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let mut written = String::new();
+        while written.is_empty() && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            written = std::fs::read_to_string(&marker).unwrap_or_default();
+        }
+        assert_eq!(written, wt.to_string_lossy());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn tmux_new_window_gets_worktree_dir_and_branch_name() {
+        // spec: GW-PICK-011
+        use std::os::unix::fs::PermissionsExt;
+
+        let td = tempfile::TempDir::new().unwrap();
+        let log = td.path().join("args.log");
+        let stub = td.path().join("tmux");
+        std::fs::write(
+            &stub,
+            format!("#!/bin/sh\nprintf '%s\\n' \"$@\" > '{}'\n", log.display()),
+        )
+        .unwrap();
+        std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let wt = td.path().join("wt");
+        std::fs::create_dir_all(&wt).unwrap();
+        open_in_multiplexer(Multiplexer::Tmux, stub.to_str().unwrap(), &wt, "feat").unwrap();
+
+        let args = std::fs::read_to_string(&log).unwrap();
+        assert_eq!(
+            args,
+            format!("new-window\n-c\n{}\n-n\nfeat\n", wt.display())
+        );
+
+        let wez = Multiplexer::Wezterm.args(&wt, "feat");
+        assert_eq!(wez[..3], ["cli", "spawn", "--cwd"]);
+        assert_eq!(wez[3], wt.as_os_str());
+    }
+
+    #[test]
+    fn editor_prefers_config_over_env() {
+        let global = GlobalConfig {
+            editor: Some("code -n".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(resolve_editor(&global).as_deref(), Some("code -n"));
+    }
+
+    #[test]
+    fn format_table_pads_columns_and_shortens_paths() {
+        // spec: GW-LIST-006
+        let row = |cells: &[&str]| cells.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        let rows = vec![
+            row(&["/src/repo", "main", "0"]),
+            row(&["/wt/repo/feature-login", "feature/login", "12"]),
+        ];
+        assert_eq!(
+            format_table(&rows, None),
+            [
+                "/src/repo               main           0",
+                "/wt/repo/feature-login  feature/login  12",
+            ]
+        );
+        // 22 + 13 + 2 + 2*2 = 41 columns; at 35 the path column loses 6.
+        assert_eq!(
+            format_table(&rows, Some(35)),
+            [
+                "/src/repo         main           0",
+                "…o/feature-login  feature/login  12",
+            ]
+        );
+        assert_eq!(format_table(&rows, Some(100)), format_table(&rows, None));
+        assert!(format_table(&[], Some(10)).is_empty());
+    }
+
+    #[test]
+    fn github_compare_url_for_branch() {
+        // spec: GW-NEW-039
+        assert_eq!(
+            github_compare_url("git@github.com:acme/app.git", "feat/login").as_deref(),
+            Some("https://github.com/acme/app/compare/feat/login?expand=1")
+        );
+        assert_eq!(
+            github_compare_url("https://github.com/acme/app", "fix").as_deref(),
+            Some("https://github.com/acme/app/compare/fix?expand=1")
+        );
+        assert_eq!(
+            github_compare_url("git@gitlab.com:acme/app.git", "fix"),
+            None
+        );
+        assert_eq!(github_compare_url("/srv/git/app.git", "fix"), None);
+        assert_eq!(github_compare_url("https://github.com/acme/app", ""), None);
+    }
+
+    #[test]
+    fn parse_git_version_reads_major_minor() {
+        assert_eq!(parse_git_version("git version 2.39.5\n"), Some((2, 39)));
+        assert_eq!(
+            parse_git_version("git version 2.15.1 (Apple Git-101)"),
+            Some((2, 15))
+        );
+        assert_eq!(
+            parse_git_version("git version 2.45.windows.1"),
+            Some((2, 45))
+        );
+        assert_eq!(parse_git_version("hub version 2.14.2"), None);
+    }
+
+    #[test]
+    fn shell_quote_only_quotes_when_needed() {
+        assert_eq!(shell_quote("/tmp/wt/feat-1"), "/tmp/wt/feat-1");
+        assert_eq!(shell_quote("/tmp/my wt"), "'/tmp/my wt'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
+    #[test]
+    fn cd_command_quotes_paths_with_spaces() {
+        // spec: GW-PICK-018
+        assert_eq!(cd_command(Path::new("/tmp/wt/feat")), "cd /tmp/wt/feat");
+        assert_eq!(
+            cd_command(Path::new("/tmp/my wt/feat")),
+            "cd '/tmp/my wt/feat'"
+        );
+        assert_eq!(cd_command(Path::new("/tmp/it's")), "cd '/tmp/it'\\''s'");
+        assert_eq!(base64_encode(b"cd /a"), "Y2QgL2E=");
+        assert_eq!(base64_encode(b"cd /ab"), "Y2QgL2Fi");
+        assert_eq!(base64_encode(b"cd /abc"), "Y2QgL2FiYw==");
+    }
+
+    #[test]
+    fn prefix_applies_to_first_dir_segment_only() {
+        assert_eq!(
+            prefix_first_segment(Path::new("feat"), "wt-"),
+            PathBuf::from("wt-feat")
+        );
+        assert_eq!(
+            prefix_first_segment(Path::new("feature/x"), "wt-"),
+            PathBuf::from("wt-feature/x")
+        );
+        assert_eq!(
+            prefix_first_segment(Path::new("feat"), ""),
+            PathBuf::from("feat")
+        );
+    }
+
+    #[test]
+    fn formats_unix_seconds_as_utc() {
+        assert_eq!(format_utc(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_utc(951_825_600), "2000-02-29T12:00:00Z");
+        assert_eq!(format_utc(1_706_745_599), "2024-01-31T23:59:59Z");
+    }
+
+    #[test]
+    fn common_parent_of_worktree_dirs() {
+        let p = |s: &str| PathBuf::from(s);
+        assert_eq!(
+            common_parent(&[p("/home/u/wt/repo"), p("/home/u/wt/repo/pr")]),
+            Some(p("/home/u/wt/repo"))
+        );
+        assert_eq!(
+            common_parent(&[p("/home/u/wt/repo")]),
+            Some(p("/home/u/wt/repo"))
+        );
+        assert_eq!(common_parent(&[p("/a/x"), p("/b/y")]), None);
+        assert_eq!(common_parent(&[]), None);
+    }
+
+    #[test]
+    fn env_file_parsing_handles_comments_export_and_quotes() {
+        let vars = parse_env_file(
+            "# secrets\n\nexport TOKEN=abc\nNAME=\"hello world\"\nSINGLE='x=y'\nEMPTY=\n",
+        )
+        .unwrap();
+        assert_eq!(
+            vars,
+            vec![
+                ("TOKEN".to_string(), "abc".to_string()),
+                ("NAME".to_string(), "hello world".to_string()),
+                ("SINGLE".to_string(), "x=y".to_string()),
+                ("EMPTY".to_string(), String::new()),
+            ]
+        );
+
+        assert!(parse_env_file("NOT_A_PAIR\n").is_err());
+    }
+
     #[test]
     fn hotkeys_overflow_to_two_letters_cartesian() {
         let pool: Vec<char> = vec!['a', 's', 'd'];
@@ -1019,4 +5159,25 @@ mod tests {
         let codes = assign_hotkeys(7, &pool);
         assert_eq!(codes, vec!["a", "s", "d", "aa", "as", "ad", "sa"]);
     }
+
+    #[test]
+    fn zsh_branch_completion_patch_finds_its_anchors() {
+        // spec: GW-COMP-001
+        let mut script = Vec::new();
+        clap_complete::generate(
+            clap_complete::Shell::Zsh,
+            &mut cli_command(),
+            "gw",
+            &mut script,
+        );
+        let patched = zsh_complete_branches(&String::from_utf8(script).unwrap()).unwrap();
+        assert!(patched.contains(":__gw_branches'"));
+        assert!(patched.contains("__gw_branches() {"));
+
+        let err = zsh_complete_branches("#compdef gw\n_gw() {}\n").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("unexpected zsh completion script layout")
+        );
+    }
 }
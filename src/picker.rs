@@ -10,7 +10,7 @@ use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::{self, IsTerminal, Write};
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, Receiver, TryRecvError};
@@ -18,22 +18,73 @@ use std::thread;
 use std::time::{Duration, Instant};
 
 use crate::{
-    RepoConfig, RepoContext, WorktreeEntry, assign_hotkeys, load_repo_config,
-    parse_worktree_porcelain, save_repo_config,
+    RepoConfig, RepoContext, SortRepos, WorktreeEntry, assign_hotkeys, load_repo_config,
+    main_worktree_idx, parse_worktree_porcelain, save_repo_config,
 };
 
 #[derive(Debug, Clone)]
-struct KnownRepo {
-    hash: String,
-    name: String,
-    anchor: PathBuf,
-    git_common_dir: PathBuf,
+pub(crate) struct KnownRepo {
+    pub(crate) hash: String,
+    pub(crate) name: String,
+    pub(crate) anchor: PathBuf,
+    pub(crate) git_common_dir: PathBuf,
+    /// Unix seconds of the last `gw go` selection in this repo, if any.
+    pub(crate) last_opened: Option<u64>,
+    /// Branch of the worktree last picked in `gw go` here, to start on it next time.
+    pub(crate) last_branch: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Screen {
     Repo,
     Worktree,
+    /// Depth-first: every repo's worktrees in one list, grouped under repo header rows.
+    Flat,
+}
+
+#[derive(Debug, Clone)]
+enum FlatRow {
+    /// Non-selectable group header for `flat_repos[repo]`.
+    Header {
+        repo: usize,
+    },
+    Worktree {
+        repo: usize,
+        entry: WorktreeEntry,
+    },
+}
+
+impl FlatRow {
+    fn is_selectable(&self) -> bool {
+        matches!(self, FlatRow::Worktree { .. })
+    }
+}
+
+/// Which worktrees the worktree screen shows by dirty state (cycled with `D`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum DirtyFilter {
+    #[default]
+    All,
+    DirtyOnly,
+    CleanOnly,
+}
+
+impl DirtyFilter {
+    fn next(self) -> Self {
+        match self {
+            DirtyFilter::All => DirtyFilter::DirtyOnly,
+            DirtyFilter::DirtyOnly => DirtyFilter::CleanOnly,
+            DirtyFilter::CleanOnly => DirtyFilter::All,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            DirtyFilter::All => "all",
+            DirtyFilter::DirtyOnly => "dirty only",
+            DirtyFilter::CleanOnly => "clean only",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -41,7 +92,11 @@ enum Mode {
     Normal,
     Filter,
     ConfirmDelete,
+    /// Second confirmation: the worktree being deleted has uncommitted changes.
+    ConfirmForceDelete,
     Deleting,
+    /// `q` was pressed while a filter is active (config `confirm_quit_with_selection`).
+    ConfirmQuit,
     Help,
 }
 
@@ -51,6 +106,11 @@ struct DeleteInProgress {
     receiver: Receiver<anyhow::Result<()>>,
 }
 
+struct PreviewJob {
+    path: PathBuf,
+    receiver: Receiver<Option<String>>,
+}
+
 struct AppState {
     screen: Screen,
     mode: Mode,
@@ -77,6 +137,125 @@ struct AppState {
     last_g_at: Instant,
     pending_d: bool,
     last_d_at: Instant,
+
+    /// Whether to use colors when drawing (`--color`).
+    color: bool,
+    /// Repo list order (`--sort-repos`, toggled with `S`), reapplied on refresh. `Recency` also
+    /// orders the worktree screen while its text filter is empty.
+    sort_repos: SortRepos,
+    /// Type-to-filter in normal mode; letter hotkeys are off and commands need ctrl.
+    instant_filter: bool,
+    /// Branches whose upstream is gone, per repo hash; filled when a repo's worktrees are shown.
+    gone_branches: HashMap<String, HashSet<String>>,
+    /// `(ahead, behind)` of each worktree's upstream, by path, per repo hash; filled with
+    /// `gone_branches`. Worktrees without an upstream have no entry.
+    ahead_behind: HashMap<String, HashMap<PathBuf, (usize, usize)>>,
+    /// Paths of worktrees with uncommitted changes, per repo hash; filled with `gone_branches`.
+    dirty: HashMap<String, HashSet<PathBuf>>,
+    dirty_filter: DirtyFilter,
+    /// First line of each branch's `branch.<name>.description`, per repo hash; filled with
+    /// `gone_branches`.
+    descriptions: HashMap<String, HashMap<String, String>>,
+    /// When each worktree was last picked, by path, per repo hash; filled with `gone_branches`.
+    last_opened: HashMap<String, HashMap<PathBuf, u64>>,
+    /// Config `preview_cmd`, else [`DEFAULT_PREVIEW_CMD`].
+    preview_cmd: String,
+    /// Whether the preview pane is shown beside the worktree list (toggled with `P`; initially
+    /// on when `preview_cmd` is configured).
+    show_preview: bool,
+    /// Preview output per worktree path; `None` when the command failed or timed out.
+    previews: HashMap<PathBuf, Option<String>>,
+    preview_job: Option<PreviewJob>,
+    /// Config `picker_keep_cwd`: worktrees created with `n` don't become the anchor.
+    keep_cwd: bool,
+    /// Config `confirm_quit_with_selection`: `q` asks first while a filter is active.
+    confirm_quit: bool,
+
+    flat_repos: Vec<KnownRepo>,
+    flat_rows: Vec<FlatRow>,
+    flat_selected: usize,
+    flat_list_state: ListState,
+}
+
+impl AppState {
+    /// Indices of the worktrees the worktree screen shows, after the text and dirty filters,
+    /// most recently picked first under the recency sort unless a text filter ranks them.
+    fn visible_wt_idx(&self) -> Vec<usize> {
+        let hash = self.active_repo.as_ref().map(|r| &r.hash);
+        let dirty = hash.and_then(|h| self.dirty.get(h));
+        let mut idx = visible_worktrees_idx(&self.wt_entries, &self.wt_filter);
+        if self.sort_repos == SortRepos::Recency
+            && self.wt_filter.is_empty()
+            && let Some(last_opened) = hash.and_then(|h| self.last_opened.get(h))
+        {
+            idx = order_by_recency(&self.wt_entries, idx, last_opened);
+        }
+        filter_by_dirty(&self.wt_entries, idx, dirty, self.dirty_filter)
+    }
+
+    fn new(screen: Screen) -> Self {
+        Self {
+            screen,
+            mode: Mode::Normal,
+            status: String::new(),
+            pending_delete: None,
+            delete_in_progress: None,
+            repo_filter: String::new(),
+            repo_selected: 0,
+            repo_list_state: ListState::default(),
+            active_repo: None,
+            wt_filter: String::new(),
+            wt_selected: 0,
+            wt_list_state: ListState::default(),
+            wt_entries: Vec::new(),
+            hotkey_buf: String::new(),
+            last_hotkey_at: Instant::now(),
+            pending_g: false,
+            last_g_at: Instant::now(),
+            pending_d: false,
+            last_d_at: Instant::now(),
+            color: true,
+            sort_repos: SortRepos::Name,
+            instant_filter: false,
+            gone_branches: HashMap::new(),
+            ahead_behind: HashMap::new(),
+            dirty: HashMap::new(),
+            dirty_filter: DirtyFilter::All,
+            descriptions: HashMap::new(),
+            last_opened: HashMap::new(),
+            preview_cmd: DEFAULT_PREVIEW_CMD.to_string(),
+            show_preview: false,
+            previews: HashMap::new(),
+            preview_job: None,
+            keep_cwd: false,
+            confirm_quit: false,
+            flat_repos: Vec::new(),
+            flat_rows: Vec::new(),
+            flat_selected: 0,
+            flat_list_state: ListState::default(),
+        }
+    }
+}
+
+/// How the picker should open.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PickOptions {
+    /// Skip the repo screen and list all repos' worktrees in one grouped list.
+    pub(crate) depth_first: bool,
+    /// Draw with colors; when false, only modifiers (bold/reverse) are used.
+    pub(crate) color: bool,
+    /// Initial filter text for the first screen (`--filter`).
+    pub(crate) filter: Option<String>,
+    /// Initial order of the repo list (`--sort-repos`).
+    pub(crate) sort_repos: SortRepos,
+    /// Printable keys filter immediately, without `/` (config `instant_filter`).
+    pub(crate) instant_filter: bool,
+    /// Command whose output previews the highlighted worktree (config `preview_cmd`).
+    pub(crate) preview_cmd: Option<String>,
+    /// Create worktrees with `n` as `gw new --keep-cwd` does (config `picker_keep_cwd`).
+    pub(crate) keep_cwd: bool,
+    /// Ask before `q` discards an active filter (config `confirm_quit_with_selection`).
+    pub(crate) confirm_quit: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -88,6 +267,7 @@ pub(crate) struct PickerSelection {
 pub(crate) fn pick_worktree(
     cfg_root: &Path,
     current_repo: Option<RepoContext>,
+    opts: &PickOptions,
 ) -> anyhow::Result<Option<PickerSelection>> {
     let mut repos = list_known_repos(cfg_root)?;
 
@@ -99,8 +279,7 @@ pub(crate) fn pick_worktree(
             repo_name: repo.repo_name.clone(),
             git_common_dir: repo.git_common_dir.to_string_lossy().to_string(),
             anchor_path: repo.toplevel.to_string_lossy().to_string(),
-            worktrees_dir: None,
-            hooks: Vec::new(),
+            ..Default::default()
         };
         save_repo_config(cfg_root, repo, &stub)?;
         repos = list_known_repos(cfg_root)?;
@@ -109,6 +288,12 @@ pub(crate) fn pick_worktree(
     if repos.is_empty() {
         return Ok(None);
     }
+    repos.sort_by(|a, b| compare_repos(a, b, opts.sort_repos));
+
+    // Terminals that can't do raw mode / alternate screen get the plain numbered list.
+    if std::env::var("TERM").is_ok_and(|t| t == "dumb") {
+        return pick_plain(cfg_root, &repos, opts);
+    }
 
     // If there's no TTY at all, the picker would hang forever waiting for input.
     if !io::stdout().is_terminal() && !io::stderr().is_terminal() {
@@ -119,9 +304,9 @@ pub(crate) fn pick_worktree(
     // Draw the UI to stderr in that case.
     let use_stderr = !io::stdout().is_terminal() && io::stderr().is_terminal();
     if use_stderr {
-        pick_with_terminal(io::stderr(), cfg_root, &repos, current_repo.as_ref())
+        pick_with_terminal(io::stderr(), cfg_root, &repos, current_repo.as_ref(), opts)
     } else {
-        pick_with_terminal(io::stdout(), cfg_root, &repos, current_repo.as_ref())
+        pick_with_terminal(io::stdout(), cfg_root, &repos, current_repo.as_ref(), opts)
     }
 }
 
@@ -130,14 +315,19 @@ fn pick_with_terminal<W: Write>(
     cfg_root: &Path,
     repos: &[KnownRepo],
     current_repo: Option<&RepoContext>,
+    opts: &PickOptions,
 ) -> anyhow::Result<Option<PickerSelection>> {
-    enable_raw_mode()?;
-    w.execute(EnterAlternateScreen)?;
+    let started = enable_raw_mode().and_then(|()| w.execute(EnterAlternateScreen).map(|_| ()));
+    if let Err(e) = started {
+        disable_raw_mode().ok();
+        eprintln!("gw: can't start the interactive picker ({e}); falling back to a numbered list");
+        return pick_plain(cfg_root, repos, opts);
+    }
 
     let backend = CrosstermBackend::new(w);
     let mut terminal = Terminal::new(backend)?;
 
-    let res = picker_loop(&mut terminal, cfg_root, repos, current_repo);
+    let res = picker_loop(&mut terminal, cfg_root, repos, current_repo, opts);
 
     disable_raw_mode().ok();
     terminal.backend_mut().execute(LeaveAlternateScreen).ok();
@@ -146,7 +336,77 @@ fn pick_with_terminal<W: Write>(
     res
 }
 
-fn list_known_repos(cfg_root: &Path) -> anyhow::Result<Vec<KnownRepo>> {
+/// Fallback picker without a TUI: every repo's worktrees as a numbered list on stderr, with the
+/// choice read as a number from stdin. Empty input or `q` cancels.
+fn pick_plain(
+    cfg_root: &Path,
+    repos: &[KnownRepo],
+    opts: &PickOptions,
+) -> anyhow::Result<Option<PickerSelection>> {
+    let loaded = load_all_worktrees(cfg_root, repos);
+    let choices = plain_choices(repos, loaded, opts.filter.as_deref().unwrap_or(""));
+    if choices.is_empty() {
+        eprintln!("gw: no worktrees to choose from");
+        return Ok(None);
+    }
+    for (i, (label, _)) in choices.iter().enumerate() {
+        eprintln!("{:>3}) {label}", i + 1);
+    }
+    eprint!("Select worktree [1-{}, q to quit]: ", choices.len());
+    io::stderr().flush().ok();
+
+    let mut line = String::new();
+    io::stdin().read_line(&mut line)?;
+    let line = line.trim();
+    if line.is_empty() || line == "q" {
+        return Ok(None);
+    }
+    let n: usize = line
+        .parse()
+        .ok()
+        .filter(|n| (1..=choices.len()).contains(n))
+        .with_context(|| format!("invalid selection: {line:?}"))?;
+    Ok(choices.into_iter().nth(n - 1).map(|(_, sel)| sel))
+}
+
+/// Labelled selections for [`pick_plain`], in repo order, narrowed by `filter` like the TUI's
+/// filter. Repos whose worktrees fail to load are reported and skipped.
+fn plain_choices(
+    repos: &[KnownRepo],
+    loaded: Vec<anyhow::Result<(Vec<WorktreeEntry>, PathBuf)>>,
+    filter: &str,
+) -> Vec<(String, PickerSelection)> {
+    let mut choices = Vec::new();
+    for (repo, res) in repos.iter().zip(loaded) {
+        let (entries, anchor) = match res {
+            Ok(v) => v,
+            Err(e) => {
+                eprintln!("gw: skipping {}: {e:#}", repo.name);
+                continue;
+            }
+        };
+        for e in entries.iter().filter(|e| !e.bare) {
+            let branch = e.branch.as_deref().unwrap_or("(detached)");
+            let label = format!("{}  {branch}  {}", repo.name, e.display_path());
+            choices.push((
+                label,
+                PickerSelection {
+                    repo_anchor: anchor.clone(),
+                    worktree_path: e.path.clone(),
+                },
+            ));
+        }
+    }
+    fuzzy_rank(
+        choices.into_iter().map(|c| {
+            let text = c.0.clone();
+            (c, text)
+        }),
+        filter,
+    )
+}
+
+pub(crate) fn list_known_repos(cfg_root: &Path) -> anyhow::Result<Vec<KnownRepo>> {
     let repos_dir = cfg_root.join("repos");
     if !repos_dir.exists() {
         return Ok(Vec::new());
@@ -173,6 +433,8 @@ fn list_known_repos(cfg_root: &Path) -> anyhow::Result<Vec<KnownRepo>> {
             name: cfg.repo_name,
             anchor: PathBuf::from(cfg.anchor_path),
             git_common_dir: PathBuf::from(cfg.git_common_dir),
+            last_opened: cfg.last_opened,
+            last_branch: cfg.last_branch,
         });
     }
 
@@ -180,41 +442,88 @@ fn list_known_repos(cfg_root: &Path) -> anyhow::Result<Vec<KnownRepo>> {
     Ok(repos)
 }
 
+/// Where the repo cursor starts: on the current repo, else on the most recently opened one.
+fn initial_repo_idx(repos: &[KnownRepo], current_repo: Option<&RepoContext>) -> Option<usize> {
+    match current_repo {
+        Some(cur) => repos.iter().position(|r| r.hash == cur.repo_hash),
+        None => repos
+            .iter()
+            .enumerate()
+            .filter_map(|(i, r)| Some((r.last_opened?, std::cmp::Reverse(i))))
+            .max()
+            .map(|(_, std::cmp::Reverse(i))| i),
+    }
+}
+
+/// Repo list order for `--sort-repos`; ties fall back to name so the order is stable.
+pub(crate) fn compare_repos(a: &KnownRepo, b: &KnownRepo, mode: SortRepos) -> std::cmp::Ordering {
+    let by_name = a.name.cmp(&b.name);
+    match mode {
+        SortRepos::Name => by_name,
+        SortRepos::Recency => match (a.last_opened, b.last_opened) {
+            (Some(x), Some(y)) => y.cmp(&x),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }
+        .then(by_name),
+        SortRepos::Path => a.anchor.cmp(&b.anchor).then(by_name),
+    }
+}
+
 fn picker_loop<W: Write>(
     terminal: &mut Terminal<CrosstermBackend<W>>,
     cfg_root: &Path,
     repos: &[KnownRepo],
     current_repo: Option<&RepoContext>,
+    opts: &PickOptions,
 ) -> anyhow::Result<Option<PickerSelection>> {
-    let mut state = AppState {
-        screen: Screen::Repo,
-        mode: Mode::Normal,
-        status: "j/k move, gg/G top/bottom, / filter, enter select, n new, ? help, q quit"
-            .to_string(),
-        pending_delete: None,
-        delete_in_progress: None,
-        repo_filter: String::new(),
-        repo_selected: 0,
-        repo_list_state: ListState::default(),
-        active_repo: None,
-        wt_filter: String::new(),
-        wt_selected: 0,
-        wt_list_state: ListState::default(),
-        wt_entries: Vec::new(),
-        hotkey_buf: String::new(),
-        last_hotkey_at: Instant::now(),
-        pending_g: false,
-        last_g_at: Instant::now(),
-        pending_d: false,
-        last_d_at: Instant::now(),
-    };
+    let mut repos = repos.to_vec();
+    let mut state = AppState::new(Screen::Repo);
+    state.color = opts.color;
+    state.sort_repos = opts.sort_repos;
+    state.instant_filter = opts.instant_filter;
+    if let Some(cmd) = opts.preview_cmd.as_deref().filter(|c| !c.trim().is_empty()) {
+        state.preview_cmd = cmd.to_string();
+        state.show_preview = true;
+    }
+    state.keep_cwd = opts.keep_cwd;
+    state.confirm_quit = opts.confirm_quit;
+    state.status = if opts.instant_filter {
+        "type to filter, up/down move, enter select, ctrl+key for commands (ctrl-n new, ctrl-r refresh), esc clear/quit"
+    } else {
+        "j/k move, gg/G top/bottom, / filter, enter select, n new, r refresh, ? help, q quit"
+    }
+    .to_string();
 
-    if let Some(cur) = current_repo
-        && let Some(idx) = repos.iter().position(|r| r.hash == cur.repo_hash)
-    {
+    if let Some(idx) = initial_repo_idx(&repos, current_repo) {
         state.repo_selected = idx;
     }
 
+    if let Some(f) = opts.filter.as_deref().filter(|f| !f.is_empty()) {
+        if opts.depth_first {
+            state.wt_filter = f.to_string();
+        } else {
+            seed_repo_filter(&mut state, &repos, f, current_repo);
+        }
+    }
+
+    if opts.depth_first {
+        state.screen = Screen::Flat;
+        state.status =
+            "j/k move, gg/G top/bottom, / filter, enter select, r refresh, ? help, q quit"
+                .to_string();
+        let (flat_repos, flat_rows) = load_flat_rows(cfg_root, &repos);
+        state.flat_repos = flat_repos;
+        state.flat_rows = flat_rows;
+        // Start on the current repo's last-picked worktree when possible.
+        let vis = visible_flat_idx(&state.flat_rows, &state.wt_filter);
+        let selectable = flat_selectable(&state.flat_rows, &vis);
+        let start = current_repo
+            .and_then(|cur| flat_start(&state.flat_rows, &vis, &state.flat_repos, &cur.repo_hash));
+        state.flat_selected = start.unwrap_or_else(|| first_selectable(&selectable));
+    }
+
     loop {
         if !state.hotkey_buf.is_empty()
             && state.last_hotkey_at.elapsed() > Duration::from_millis(1500)
@@ -228,15 +537,41 @@ fn picker_loop<W: Write>(
             state.pending_d = false;
         }
         poll_delete_progress(cfg_root, &mut state);
+        if state.screen == Screen::Worktree
+            && let Some(repo) = &state.active_repo
+            && !state.gone_branches.contains_key(&repo.hash)
+        {
+            let gone = load_gone_branches(&repo.anchor);
+            state.gone_branches.insert(repo.hash.clone(), gone);
+            let counts = load_ahead_behind(&state.wt_entries);
+            state.ahead_behind.insert(repo.hash.clone(), counts);
+            let dirty = load_dirty_worktrees(&state.wt_entries);
+            state.dirty.insert(repo.hash.clone(), dirty);
+            let descriptions = load_branch_descriptions(&repo.anchor);
+            state.descriptions.insert(repo.hash.clone(), descriptions);
+            let last_opened = load_worktree_last_opened(cfg_root, &repo.hash);
+            state.last_opened.insert(repo.hash.clone(), last_opened);
+        }
 
-        let (vis_repos, repo_codes, repo_code_map) = visible_repos(repos, &state.repo_filter);
+        let (vis_repos, repo_codes, repo_code_map) = visible_repos(&repos, &state.repo_filter);
         state.repo_selected = state.repo_selected.min(vis_repos.len().saturating_sub(1));
         state.repo_list_state.select(Some(state.repo_selected));
 
-        let vis_wt_idx = visible_worktrees_idx(&state.wt_entries, &state.wt_filter);
+        let vis_wt_idx = state.visible_wt_idx();
         state.wt_selected = state.wt_selected.min(vis_wt_idx.len().saturating_sub(1));
         state.wt_list_state.select(Some(state.wt_selected));
+        if state.screen == Screen::Worktree {
+            update_preview(&mut state, &vis_wt_idx);
+        }
 
+        let vis_flat_idx = visible_flat_idx(&state.flat_rows, &state.wt_filter);
+        let flat_sel = flat_selectable(&state.flat_rows, &vis_flat_idx);
+        if !flat_sel.get(state.flat_selected).copied().unwrap_or(false) {
+            state.flat_selected = first_selectable(&flat_sel);
+        }
+        state.flat_list_state.select(Some(state.flat_selected));
+
+        let color = state.color;
         terminal.draw(|f| {
             let size = f.area();
             let chunks = Layout::default()
@@ -251,23 +586,22 @@ fn picker_loop<W: Write>(
             let title = match state.screen {
                 Screen::Repo => "gw: repos",
                 Screen::Worktree => "gw: worktrees",
+                Screen::Flat => "gw: all worktrees",
             };
 
             let filter_txt = match state.screen {
                 Screen::Repo => format!("/{}", state.repo_filter),
-                Screen::Worktree => format!("/{}", state.wt_filter),
+                Screen::Worktree | Screen::Flat => format!("/{}", state.wt_filter),
             };
 
             let header = Paragraph::new(Line::from(vec![
                 Span::styled(title, Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw("    "),
-                Span::styled(filter_txt, Style::default().fg(Color::DarkGray)),
+                Span::styled(filter_txt, fg(color, Color::DarkGray)),
                 Span::raw("    "),
                 Span::styled(
                     state.hotkey_buf.clone(),
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD),
+                    fg(color, Color::Yellow).add_modifier(Modifier::BOLD),
                 ),
             ]))
             .block(Block::default().borders(Borders::ALL));
@@ -275,68 +609,201 @@ fn picker_loop<W: Write>(
 
             match state.screen {
                 Screen::Repo => {
+                    let repo_title = match state.sort_repos {
+                        SortRepos::Name => "Repos".to_string(),
+                        sort => format!("Repos ({})", sort_label(sort)),
+                    };
                     let items: Vec<ListItem> = vis_repos
                         .iter()
                         .enumerate()
                         .map(|(i, r)| {
-                            let code = repo_codes.get(i).cloned().unwrap_or_default();
+                            let code = hotkey_label(&repo_codes, i, state.instant_filter);
                             let line = Line::from(vec![
-                                Span::styled(
-                                    format!("[{code}] "),
-                                    Style::default().fg(Color::Cyan),
-                                ),
+                                Span::styled(code, fg(color, Color::Cyan)),
                                 Span::raw(&r.name),
                                 Span::raw("  "),
                                 Span::styled(
                                     r.anchor.to_string_lossy(),
-                                    Style::default().fg(Color::DarkGray),
+                                    fg(color, Color::DarkGray),
                                 ),
                             ]);
                             ListItem::new(line)
                         })
                         .collect();
                     let list = List::new(items)
-                        .block(Block::default().borders(Borders::ALL).title("Repos"))
-                        .highlight_style(
-                            Style::default()
-                                .bg(Color::Blue)
-                                .fg(Color::White)
-                                .add_modifier(Modifier::BOLD),
-                        );
+                        .block(Block::default().borders(Borders::ALL).title(repo_title))
+                        .highlight_style(highlight_style(color));
                     f.render_stateful_widget(list, chunks[1], &mut state.repo_list_state);
                 }
                 Screen::Worktree => {
                     let pool = hotkey_pool_worktrees();
                     let codes = assign_hotkeys(vis_wt_idx.len(), &pool);
+                    let gone = state
+                        .active_repo
+                        .as_ref()
+                        .and_then(|r| state.gone_branches.get(&r.hash));
+                    let ahead_behind = state
+                        .active_repo
+                        .as_ref()
+                        .and_then(|r| state.ahead_behind.get(&r.hash));
+                    let dirty = state
+                        .active_repo
+                        .as_ref()
+                        .and_then(|r| state.dirty.get(&r.hash));
+                    let descriptions = state
+                        .active_repo
+                        .as_ref()
+                        .and_then(|r| state.descriptions.get(&r.hash));
+                    let main_idx = main_worktree_idx(&state.wt_entries);
                     let items: Vec<ListItem> = vis_wt_idx
                         .iter()
                         .enumerate()
                         .map(|(i, idx)| {
-                            let code = codes.get(i).cloned().unwrap_or_default();
+                            let code = hotkey_label(&codes, i, state.instant_filter);
                             let e = &state.wt_entries[*idx];
                             let branch =
                                 e.branch.clone().unwrap_or_else(|| "(detached)".to_string());
-                            let line = Line::from(vec![
-                                Span::styled(
-                                    format!("[{code}] "),
-                                    Style::default().fg(Color::Cyan),
-                                ),
-                                Span::raw(&e.path),
+                            let is_gone = e
+                                .branch
+                                .as_ref()
+                                .is_some_and(|b| gone.is_some_and(|g| g.contains(b)));
+                            let branch_span = if is_gone {
+                                Span::styled(format!("{branch} (gone)"), fg(color, Color::Yellow))
+                            } else {
+                                Span::styled(branch, fg(color, Color::Green))
+                            };
+                            let mut spans = vec![
+                                Span::styled(code, fg(color, Color::Cyan)),
+                                Span::raw(e.display_path()),
                                 Span::raw("  "),
-                                Span::styled(branch, Style::default().fg(Color::Green)),
-                            ]);
+                                branch_span,
+                            ];
+                            if dirty.is_some_and(|d| d.contains(&e.path)) {
+                                spans.push(Span::styled(
+                                    " *",
+                                    fg(color, Color::Red).add_modifier(Modifier::BOLD),
+                                ));
+                            }
+                            if let Some(label) = ahead_behind
+                                .and_then(|m| m.get(&e.path))
+                                .and_then(|&(ahead, behind)| ahead_behind_label(ahead, behind))
+                            {
+                                spans.push(Span::styled(
+                                    format!("  {label}"),
+                                    fg(color, Color::Cyan),
+                                ));
+                            }
+                            if let Some(sha) = e.short_head() {
+                                spans.push(Span::styled(
+                                    format!("  {sha}"),
+                                    fg(color, Color::DarkGray),
+                                ));
+                            }
+                            if main_idx == Some(*idx) {
+                                spans.push(Span::styled(
+                                    "  (main)",
+                                    fg(color, Color::Magenta).add_modifier(Modifier::BOLD),
+                                ));
+                            }
+                            if let Some(desc) = e
+                                .branch
+                                .as_ref()
+                                .and_then(|b| descriptions.and_then(|d| d.get(b)))
+                            {
+                                spans.push(Span::styled(
+                                    format!("  {desc}"),
+                                    Style::default().add_modifier(Modifier::DIM),
+                                ));
+                            }
+                            let line = Line::from(spans);
                             ListItem::new(line)
                         })
                         .collect();
+                    let mut labels = Vec::new();
+                    if state.dirty_filter != DirtyFilter::All {
+                        labels.push(state.dirty_filter.label());
+                    }
+                    if state.sort_repos == SortRepos::Recency {
+                        labels.push(sort_label(state.sort_repos));
+                    }
+                    let title = if labels.is_empty() {
+                        "Worktrees".to_string()
+                    } else {
+                        format!("Worktrees ({})", labels.join(", "))
+                    };
                     let list = List::new(items)
-                        .block(Block::default().borders(Borders::ALL).title("Worktrees"))
-                        .highlight_style(
-                            Style::default()
-                                .bg(Color::Blue)
-                                .fg(Color::White)
-                                .add_modifier(Modifier::BOLD),
+                        .block(Block::default().borders(Borders::ALL).title(title))
+                        .highlight_style(highlight_style(color));
+                    let list_area = if state.show_preview {
+                        let cols = Layout::default()
+                            .direction(Direction::Horizontal)
+                            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                            .split(chunks[1]);
+                        let text = match vis_wt_idx
+                            .get(state.wt_selected)
+                            .map(|&i| state.previews.get(&state.wt_entries[i].path))
+                        {
+                            Some(Some(Some(out))) => out.as_str(),
+                            Some(None) => "…",
+                            _ => "",
+                        };
+                        f.render_widget(
+                            Paragraph::new(text)
+                                .block(Block::default().borders(Borders::ALL).title("Preview")),
+                            cols[1],
                         );
-                    f.render_stateful_widget(list, chunks[1], &mut state.wt_list_state);
+                        cols[0]
+                    } else {
+                        chunks[1]
+                    };
+                    f.render_stateful_widget(list, list_area, &mut state.wt_list_state);
+                }
+                Screen::Flat => {
+                    let items: Vec<ListItem> = vis_flat_idx
+                        .iter()
+                        .map(|&i| match &state.flat_rows[i] {
+                            FlatRow::Header { repo } => {
+                                let r = &state.flat_repos[*repo];
+                                ListItem::new(Line::from(vec![
+                                    Span::styled(
+                                        format!("── {} ", r.name),
+                                        fg(color, Color::Yellow).add_modifier(Modifier::BOLD),
+                                    ),
+                                    Span::styled(
+                                        r.anchor.to_string_lossy(),
+                                        fg(color, Color::DarkGray),
+                                    ),
+                                ]))
+                            }
+                            FlatRow::Worktree { entry, .. } => {
+                                let branch = entry
+                                    .branch
+                                    .clone()
+                                    .unwrap_or_else(|| "(detached)".to_string());
+                                let mut spans = vec![
+                                    Span::raw("   "),
+                                    Span::raw(entry.display_path()),
+                                    Span::raw("  "),
+                                    Span::styled(branch, fg(color, Color::Green)),
+                                ];
+                                if let Some(sha) = entry.short_head() {
+                                    spans.push(Span::styled(
+                                        format!("  {sha}"),
+                                        fg(color, Color::DarkGray),
+                                    ));
+                                }
+                                ListItem::new(Line::from(spans))
+                            }
+                        })
+                        .collect();
+                    let list = List::new(items)
+                        .block(
+                            Block::default()
+                                .borders(Borders::ALL)
+                                .title("All worktrees"),
+                        )
+                        .highlight_style(highlight_style(color));
+                    f.render_stateful_widget(list, chunks[1], &mut state.flat_list_state);
                 }
             }
 
@@ -388,6 +855,44 @@ fn picker_loop<W: Write>(
                 continue;
             }
 
+            if state.mode == Mode::Normal
+                && state.instant_filter
+                && handle_instant_filter(&mut state, key)
+            {
+                continue;
+            }
+
+            if state.mode == Mode::ConfirmQuit {
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => return Ok(None),
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        state.mode = Mode::Normal;
+                        state.status = "quit cancelled".to_string();
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            if state.mode == Mode::Normal
+                && key.code == KeyCode::Char('q')
+                && quit_needs_confirmation(&state)
+            {
+                state.mode = Mode::ConfirmQuit;
+                state.status = "discard filter and quit? (y/n)".to_string();
+                continue;
+            }
+
+            if state.mode == Mode::Normal && key.code == KeyCode::Char('r') {
+                refresh(cfg_root, &mut state, &mut repos);
+                continue;
+            }
+
+            if state.mode == Mode::Normal && key.code == KeyCode::Char('S') {
+                toggle_sort(cfg_root, &mut state, &mut repos);
+                continue;
+            }
+
             match state.screen {
                 Screen::Repo => {
                     if let Some(sel) = handle_repo_key(
@@ -409,28 +914,130 @@ fn picker_loop<W: Write>(
                         return Ok(sel);
                     }
                 }
+                Screen::Flat => {
+                    if let Some(sel) = handle_flat_key(cfg_root, &mut state, key, &vis_flat_idx)? {
+                        return Ok(sel);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Whether `q` should ask before quitting: with `confirm_quit`, when the current screen's list
+/// is narrowed by a text filter (or, on the worktree screen, the dirty filter).
+fn quit_needs_confirmation(state: &AppState) -> bool {
+    state.confirm_quit
+        && match state.screen {
+            Screen::Repo => !state.repo_filter.is_empty(),
+            Screen::Worktree => {
+                !state.wt_filter.is_empty() || state.dirty_filter != DirtyFilter::All
+            }
+            Screen::Flat => !state.wt_filter.is_empty(),
+        }
+}
+
+/// Apply an initial repo filter, keeping the current repo selected if it is still visible.
+fn seed_repo_filter(
+    state: &mut AppState,
+    repos: &[KnownRepo],
+    filter: &str,
+    current_repo: Option<&RepoContext>,
+) {
+    state.repo_filter = filter.to_string();
+    let (vis, _, _) = visible_repos(repos, &state.repo_filter);
+    state.repo_selected = current_repo
+        .and_then(|cur| vis.iter().position(|r| r.hash == cur.repo_hash))
+        .unwrap_or(0);
+}
+
+/// Switch between name and recency order (`S`), keeping the highlighted row selected.
+fn toggle_sort(cfg_root: &Path, state: &mut AppState, repos: &mut Vec<KnownRepo>) {
+    state.sort_repos = match state.sort_repos {
+        SortRepos::Recency => SortRepos::Name,
+        SortRepos::Name | SortRepos::Path => SortRepos::Recency,
+    };
+    refresh(cfg_root, state, repos);
+    state.status = format!("sort: {}", sort_label(state.sort_repos));
+}
+
+fn sort_label(sort: SortRepos) -> &'static str {
+    match sort {
+        SortRepos::Name => "name",
+        SortRepos::Recency => "recent",
+        SortRepos::Path => "path",
+    }
+}
+
+/// Reload the current screen's list from disk/git (`r`), keeping the highlighted repo or
+/// worktree selected when it still exists.
+fn refresh(cfg_root: &Path, state: &mut AppState, repos: &mut Vec<KnownRepo>) {
+    reset_chords(state);
+    match state.screen {
+        Screen::Repo => {
+            let (vis, _, _) = visible_repos(repos, &state.repo_filter);
+            let prev = vis.get(state.repo_selected).map(|r| r.hash.clone());
+            match list_known_repos(cfg_root) {
+                Ok(mut fresh) => {
+                    fresh.sort_by(|a, b| compare_repos(a, b, state.sort_repos));
+                    *repos = fresh;
+                }
+                Err(e) => {
+                    state.status = format!("refresh failed: {e:#}");
+                    return;
+                }
+            }
+            let (vis, _, _) = visible_repos(repos, &state.repo_filter);
+            let keys: Vec<&str> = vis.iter().map(|r| r.hash.as_str()).collect();
+            state.repo_selected = reselect(&keys, prev.as_deref(), state.repo_selected);
+        }
+        Screen::Worktree => refresh_worktrees(cfg_root, state, None),
+        Screen::Flat => {
+            let vis = visible_flat_idx(&state.flat_rows, &state.wt_filter);
+            let prev = vis
+                .get(state.flat_selected)
+                .and_then(|&i| match &state.flat_rows[i] {
+                    FlatRow::Worktree { entry, .. } => Some(entry.path.clone()),
+                    FlatRow::Header { .. } => None,
+                });
+            if let Ok(mut fresh) = list_known_repos(cfg_root) {
+                fresh.sort_by(|a, b| compare_repos(a, b, state.sort_repos));
+                *repos = fresh;
             }
+            let (flat_repos, flat_rows) = load_flat_rows(cfg_root, repos);
+            state.flat_repos = flat_repos;
+            state.flat_rows = flat_rows;
+            let vis = visible_flat_idx(&state.flat_rows, &state.wt_filter);
+            let keys: Vec<&Path> = vis
+                .iter()
+                .map(|&i| match &state.flat_rows[i] {
+                    FlatRow::Worktree { entry, .. } => entry.path.as_path(),
+                    FlatRow::Header { .. } => Path::new(""),
+                })
+                .collect();
+            state.flat_selected = reselect(&keys, prev.as_deref(), state.flat_selected);
         }
     }
+    state.status = "refreshed".to_string();
+}
+
+/// Index of `prev` among the refreshed `keys` (repo hashes or worktree paths); if it's gone,
+/// keep the old index clamped into range.
+fn reselect<K: PartialEq + ?Sized>(keys: &[&K], prev: Option<&K>, old_idx: usize) -> usize {
+    prev.and_then(|p| keys.iter().position(|k| *k == p))
+        .unwrap_or_else(|| old_idx.min(keys.len().saturating_sub(1)))
 }
 
-fn visible_repos<'a>(
+pub(crate) fn visible_repos<'a>(
     repos: &'a [KnownRepo],
     filter: &str,
 ) -> (Vec<&'a KnownRepo>, Vec<String>, HashMap<String, usize>) {
-    let f = filter.to_lowercase();
-    let vis: Vec<&KnownRepo> = repos
-        .iter()
-        .filter(|r| {
-            if f.is_empty() {
-                true
-            } else {
-                format!("{} {}", r.name, r.anchor.to_string_lossy())
-                    .to_lowercase()
-                    .contains(&f)
-            }
-        })
-        .collect();
+    let vis = fuzzy_rank(
+        repos
+            .iter()
+            .map(|r| (r, format!("{} {}", r.name, r.anchor.to_string_lossy()))),
+        filter,
+    );
 
     let pool = hotkey_pool_repos();
     let codes = assign_hotkeys(vis.len(), &pool);
@@ -442,24 +1049,289 @@ fn visible_repos<'a>(
     (vis, codes, map)
 }
 
-fn visible_worktrees_idx(entries: &[WorktreeEntry], filter: &str) -> Vec<usize> {
-    let f = filter.to_lowercase();
-    entries
-        .iter()
-        .enumerate()
-        .filter(|(_, e)| {
-            if f.is_empty() {
-                true
-            } else {
-                format!("{} {}", e.path, e.branch.clone().unwrap_or_default())
-                    .to_lowercase()
-                    .contains(&f)
+/// Text the worktree filter matches against: the branch and directory name rank first, the full
+/// path (which every worktree shares most of) only breaks ties.
+fn worktree_filter_text(e: &WorktreeEntry) -> FilterText {
+    let branch = e.branch.clone().unwrap_or_default();
+    let name = e
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy())
+        .unwrap_or_default();
+    FilterText {
+        primary: format!("{branch} {name}"),
+        full: format!("{} {branch}", e.display_path()),
+    }
+}
+
+/// Worktrees matching `filter`, best match first. The main worktree always stays visible (and
+/// first) so you can jump back to the main checkout.
+pub(crate) fn visible_worktrees_idx(entries: &[WorktreeEntry], filter: &str) -> Vec<usize> {
+    if filter.is_empty() {
+        return (0..entries.len()).collect();
+    }
+    let main = main_worktree_idx(entries);
+    let ranked = fuzzy_rank(
+        entries
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| main != Some(*i))
+            .map(|(i, e)| (i, worktree_filter_text(e))),
+        filter,
+    );
+    main.into_iter().chain(ranked).collect()
+}
+
+/// `idx` with the main worktree first and the rest most recently picked first; never-picked
+/// worktrees keep their order at the end.
+fn order_by_recency(
+    entries: &[WorktreeEntry],
+    mut idx: Vec<usize>,
+    last_opened: &HashMap<PathBuf, u64>,
+) -> Vec<usize> {
+    let main = main_worktree_idx(entries);
+    idx.sort_by_key(|&i| {
+        (
+            main != Some(i),
+            std::cmp::Reverse(last_opened.get(&entries[i].path).copied()),
+        )
+    });
+    idx
+}
+
+/// When each worktree of the repo was last picked, from its config's `[worktrees]` entries.
+fn load_worktree_last_opened(cfg_root: &Path, repo_hash: &str) -> HashMap<PathBuf, u64> {
+    let cfg_path = cfg_root.join("repos").join(repo_hash).join("config.toml");
+    std::fs::read_to_string(cfg_path)
+        .ok()
+        .and_then(|s| toml::from_str::<RepoConfig>(&s).ok())
+        .map(|cfg| {
+            cfg.worktrees
+                .into_iter()
+                .filter_map(|(path, meta)| Some((PathBuf::from(path), meta.last_opened?)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Score of `needle` as a case-insensitive subsequence of `haystack` (`fbz` matches
+/// `feature-baz`), or `None` if it isn't one. Higher is better: each matched character scores,
+/// with bonuses for runs of consecutive matches and for matches at the start of a word (after a
+/// separator or a lower-to-upper case change). The best-scoring alignment wins.
+fn fuzzy_score(haystack: &str, needle: &str) -> Option<i64> {
+    const MATCH: i64 = 1;
+    const CONSECUTIVE: i64 = 5;
+    const WORD_START: i64 = 3;
+
+    let lower = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let needle: Vec<char> = needle.chars().map(lower).collect();
+    let Some((&first, rest)) = needle.split_first() else {
+        return Some(0);
+    };
+    let orig: Vec<char> = haystack.chars().collect();
+    let hay: Vec<char> = orig.iter().map(|&c| lower(c)).collect();
+    let score_at = |j: usize| {
+        let word_start = match j.checked_sub(1).map(|p| orig[p]) {
+            None => true,
+            Some(p) => !p.is_alphanumeric() || (p.is_lowercase() && orig[j].is_uppercase()),
+        };
+        MATCH + if word_start { WORD_START } else { 0 }
+    };
+
+    // best[j]: best score of the needle so far with its last character matched at `j`.
+    let mut best: Vec<Option<i64>> = (0..hay.len())
+        .map(|j| (hay[j] == first).then(|| score_at(j)))
+        .collect();
+    for &c in rest {
+        let mut next = vec![None; hay.len()];
+        // Best score ending before `j - 1`, i.e. leaving a gap before `j`.
+        let mut gapped: Option<i64> = None;
+        for j in 0..hay.len() {
+            if j >= 2 {
+                gapped = gapped.max(best[j - 2]);
+            }
+            if hay[j] != c {
+                continue;
             }
+            let consecutive = j
+                .checked_sub(1)
+                .and_then(|p| best[p])
+                .map(|s| s + CONSECUTIVE);
+            next[j] = consecutive.max(gapped).map(|s| s + score_at(j));
+        }
+        best = next;
+    }
+    best.into_iter().flatten().max()
+}
+
+/// What a filter matches an item against. An item is shown if `full` matches; matches in
+/// `primary` rank ahead of matches only in `full`, and `full`'s score breaks ties.
+struct FilterText {
+    primary: String,
+    full: String,
+}
+
+impl From<String> for FilterText {
+    fn from(text: String) -> Self {
+        FilterText {
+            primary: text.clone(),
+            full: text,
+        }
+    }
+}
+
+/// The `items` whose text fuzzily matches `filter` ([`fuzzy_score`]), best first; ties (and an
+/// empty filter) keep their original order.
+fn fuzzy_rank<T, S: Into<FilterText>>(
+    items: impl IntoIterator<Item = (T, S)>,
+    filter: &str,
+) -> Vec<T> {
+    let mut scored: Vec<((Option<i64>, i64), T)> = items
+        .into_iter()
+        .filter_map(|(item, text)| {
+            let text = text.into();
+            let full = fuzzy_score(&text.full, filter)?;
+            Some(((fuzzy_score(&text.primary, filter), full), item))
+        })
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, item)| item).collect()
+}
+
+/// Narrow visible worktrees `vis` to the dirty (or clean) ones per `dirty`; the main worktree
+/// stays, as with the text filter. Until dirty state is known, nothing is filtered out.
+fn filter_by_dirty(
+    entries: &[WorktreeEntry],
+    vis: Vec<usize>,
+    dirty: Option<&HashSet<PathBuf>>,
+    filter: DirtyFilter,
+) -> Vec<usize> {
+    let Some(dirty) = dirty else {
+        return vis;
+    };
+    let main = main_worktree_idx(entries);
+    vis.into_iter()
+        .filter(|&i| {
+            let is_dirty = dirty.contains(&entries[i].path);
+            main == Some(i)
+                || match filter {
+                    DirtyFilter::All => true,
+                    DirtyFilter::DirtyOnly => is_dirty,
+                    DirtyFilter::CleanOnly => !is_dirty,
+                }
         })
-        .map(|(i, _)| i)
         .collect()
 }
 
+/// Load every repo's worktrees into grouped rows (a header per repo, then its worktrees).
+/// Repos whose worktrees fail to load still get a header so they don't silently vanish.
+fn load_flat_rows(cfg_root: &Path, repos: &[KnownRepo]) -> (Vec<KnownRepo>, Vec<FlatRow>) {
+    let mut flat_repos = Vec::with_capacity(repos.len());
+    let mut rows = Vec::new();
+    let loaded = load_all_worktrees(cfg_root, repos);
+    for (idx, (repo, res)) in repos.iter().zip(loaded).enumerate() {
+        let mut r = repo.clone();
+        rows.push(FlatRow::Header { repo: idx });
+        if let Ok((entries, anchor)) = res {
+            r.anchor = anchor;
+            rows.extend(
+                entries
+                    .into_iter()
+                    .map(|entry| FlatRow::Worktree { repo: idx, entry }),
+            );
+        }
+        flat_repos.push(r);
+    }
+    (flat_repos, rows)
+}
+
+/// Visible flat rows for `filter`: matching worktrees, best match first within each repo, plus
+/// the headers of repos that still have at least one visible worktree. Without a filter every
+/// header stays, so a repo whose worktrees failed to load still shows up.
+fn visible_flat_idx(rows: &[FlatRow], filter: &str) -> Vec<usize> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < rows.len() {
+        let header = matches!(rows[i], FlatRow::Header { .. }).then_some(i);
+        let start = i + usize::from(header.is_some());
+        let end = rows[start..]
+            .iter()
+            .position(|r| matches!(r, FlatRow::Header { .. }))
+            .map_or(rows.len(), |n| start + n);
+        let ranked = fuzzy_rank(
+            (start..end).filter_map(|j| match &rows[j] {
+                FlatRow::Worktree { entry, .. } => Some((j, worktree_filter_text(entry))),
+                FlatRow::Header { .. } => None,
+            }),
+            filter,
+        );
+        if !ranked.is_empty() || filter.is_empty() {
+            out.extend(header);
+            out.extend(ranked);
+        }
+        i = end;
+    }
+    out
+}
+
+/// Visible position of the worktree checked out on `branch`. The picker remembers its last
+/// selection as a (repo hash, branch) pair rather than an index or path, so it survives
+/// worktrees being added, removed or moved.
+fn branch_position(
+    entries: &[WorktreeEntry],
+    vis: &[usize],
+    branch: Option<&str>,
+) -> Option<usize> {
+    let branch = branch?;
+    vis.iter()
+        .position(|&i| entries[i].branch.as_deref() == Some(branch))
+}
+
+/// Visible flat row to start on for repo `hash`: its last-picked branch, else its first visible
+/// worktree.
+fn flat_start(rows: &[FlatRow], vis: &[usize], repos: &[KnownRepo], hash: &str) -> Option<usize> {
+    let repo_idx = repos.iter().position(|r| r.hash == hash)?;
+    let entry = |i: usize| match &rows[i] {
+        FlatRow::Worktree { repo, entry } if *repo == repo_idx => Some(entry),
+        _ => None,
+    };
+    let last = repos[repo_idx].last_branch.as_deref();
+    vis.iter()
+        .position(|&i| last.is_some() && entry(i).is_some_and(|e| e.branch.as_deref() == last))
+        .or_else(|| vis.iter().position(|&i| entry(i).is_some()))
+}
+
+fn flat_selectable(rows: &[FlatRow], vis: &[usize]) -> Vec<bool> {
+    vis.iter().map(|&i| rows[i].is_selectable()).collect()
+}
+
+fn first_selectable(selectable: &[bool]) -> usize {
+    selectable.iter().position(|&s| s).unwrap_or(0)
+}
+
+fn last_selectable(selectable: &[bool]) -> usize {
+    selectable.iter().rposition(|&s| s).unwrap_or(0)
+}
+
+/// Move from `from` to the next selectable row in the given direction, skipping headers.
+/// Stays put when there is nothing selectable that way.
+fn step_selectable(selectable: &[bool], from: usize, forward: bool) -> usize {
+    if forward {
+        selectable
+            .iter()
+            .enumerate()
+            .skip(from + 1)
+            .find(|(_, s)| **s)
+            .map(|(i, _)| i)
+            .unwrap_or(from)
+    } else {
+        selectable[..from.min(selectable.len())]
+            .iter()
+            .rposition(|&s| s)
+            .unwrap_or(from)
+    }
+}
+
 fn handle_filter_mode(state: &mut AppState, key: KeyEvent) -> bool {
     match key.code {
         KeyCode::Esc => {
@@ -477,7 +1349,7 @@ fn handle_filter_mode(state: &mut AppState, key: KeyEvent) -> bool {
                 Screen::Repo => {
                     state.repo_filter.pop();
                 }
-                Screen::Worktree => {
+                Screen::Worktree | Screen::Flat => {
                     state.wt_filter.pop();
                 }
             }
@@ -489,7 +1361,7 @@ fn handle_filter_mode(state: &mut AppState, key: KeyEvent) -> bool {
             }
             match state.screen {
                 Screen::Repo => state.repo_filter.push(c),
-                Screen::Worktree => state.wt_filter.push(c),
+                Screen::Worktree | Screen::Flat => state.wt_filter.push(c),
             }
             true
         }
@@ -497,18 +1369,133 @@ fn handle_filter_mode(state: &mut AppState, key: KeyEvent) -> bool {
     }
 }
 
-fn suspend_tui<W: Write>(terminal: &mut Terminal<CrosstermBackend<W>>) {
-    disable_raw_mode().ok();
-    terminal.backend_mut().execute(LeaveAlternateScreen).ok();
-    terminal.show_cursor().ok();
+/// Normal-mode keys with `instant_filter`: printable keys (except `?`) extend the filter,
+/// backspace trims it and esc clears it. Ctrl/alt chords and everything else fall through to the
+/// screen's key handler, so e.g. ctrl-n still creates a worktree.
+fn handle_instant_filter(state: &mut AppState, key: KeyEvent) -> bool {
+    let filter = match state.screen {
+        Screen::Repo => &mut state.repo_filter,
+        Screen::Worktree | Screen::Flat => &mut state.wt_filter,
+    };
+    match key.code {
+        KeyCode::Char(c)
+            if c != '?'
+                && !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+        {
+            filter.push(c);
+        }
+        KeyCode::Backspace => {
+            filter.pop();
+        }
+        KeyCode::Esc if !filter.is_empty() => {
+            filter.clear();
+            state.status = "cleared filter".to_string();
+        }
+        _ => return false,
+    }
+    match state.screen {
+        Screen::Repo => state.repo_selected = 0,
+        Screen::Worktree => state.wt_selected = 0,
+        // The loop moves the selection onto the first visible worktree.
+        Screen::Flat => {}
+    }
+    reset_chords(state);
+    true
 }
 
-fn resume_tui<W: Write>(terminal: &mut Terminal<CrosstermBackend<W>>) -> anyhow::Result<()> {
-    enable_raw_mode()?;
-    terminal.backend_mut().execute(EnterAlternateScreen)?;
-    terminal.clear().ok();
-    Ok(())
-}
+/// Reload the active repo's worktrees, keeping the highlighted one selected (or `select`, if it
+/// was just moved there).
+fn refresh_worktrees(cfg_root: &Path, state: &mut AppState, select: Option<&Path>) {
+    let Some(repo) = state.active_repo.clone() else {
+        return;
+    };
+    state.gone_branches.remove(&repo.hash);
+    state.ahead_behind.remove(&repo.hash);
+    state.dirty.remove(&repo.hash);
+    state.descriptions.remove(&repo.hash);
+    state.previews.clear();
+    let vis = state.visible_wt_idx();
+    let prev = match select {
+        Some(p) => Some(p.to_path_buf()),
+        None => vis
+            .get(state.wt_selected)
+            .map(|&i| state.wt_entries[i].path.clone()),
+    };
+    state.last_opened.remove(&repo.hash);
+    match load_worktrees(cfg_root, &repo) {
+        Ok((wts, anchor)) => {
+            state.active_repo = Some(KnownRepo { anchor, ..repo });
+            state.wt_entries = wts;
+        }
+        Err(e) => {
+            state.status = format!("refresh failed: {e:#}");
+            return;
+        }
+    }
+    let vis = state.visible_wt_idx();
+    let keys: Vec<&Path> = vis
+        .iter()
+        .map(|&i| state.wt_entries[i].path.as_path())
+        .collect();
+    state.wt_selected = reselect(&keys, prev.as_deref(), state.wt_selected);
+}
+
+/// Prompt for a new name for the highlighted worktree's branch and apply it. Returns the
+/// worktree's (possibly moved) path, or `None` if cancelled.
+fn rename_worktree_branch<W: Write>(
+    terminal: &mut Terminal<CrosstermBackend<W>>,
+    cfg_root: &Path,
+    repo: &KnownRepo,
+    entry: &WorktreeEntry,
+    is_main: bool,
+) -> anyhow::Result<Option<PathBuf>> {
+    let Some(old) = entry.branch.clone() else {
+        anyhow::bail!("detached HEAD; no branch to rename");
+    };
+    suspend_tui(terminal);
+
+    let res: anyhow::Result<Option<PathBuf>> = (|| {
+        use dialoguer::{Confirm, Input};
+
+        let theme = crate::prompt_theme();
+        let new: String = Input::with_theme(&*theme)
+            .with_prompt(format!("Rename branch {old} to"))
+            .with_initial_text(old.clone())
+            .interact_text()?;
+        let new = new.trim().to_string();
+        if new.is_empty() || new == old {
+            return Ok(None);
+        }
+        // The main worktree can't be moved with `git worktree move`.
+        let move_dir = !is_main
+            && Confirm::with_theme(&*theme)
+                .with_prompt("Also move the worktree directory to match?")
+                .default(false)
+                .interact()?;
+
+        let ctx = RepoContext::detect_from_path(&repo.anchor)?;
+        let moved = crate::rename_branch(cfg_root, &ctx, &old, &new, move_dir)?;
+        Ok(Some(moved.map_or_else(|| entry.path.clone(), |m| m.path)))
+    })();
+
+    resume_tui(terminal)?;
+    res
+}
+
+fn suspend_tui<W: Write>(terminal: &mut Terminal<CrosstermBackend<W>>) {
+    disable_raw_mode().ok();
+    terminal.backend_mut().execute(LeaveAlternateScreen).ok();
+    terminal.show_cursor().ok();
+}
+
+fn resume_tui<W: Write>(terminal: &mut Terminal<CrosstermBackend<W>>) -> anyhow::Result<()> {
+    enable_raw_mode()?;
+    terminal.backend_mut().execute(EnterAlternateScreen)?;
+    terminal.clear().ok();
+    Ok(())
+}
 
 fn handle_repo_key<W: Write>(
     terminal: &mut Terminal<CrosstermBackend<W>>,
@@ -545,11 +1532,12 @@ fn handle_repo_key<W: Write>(
 
             suspend_tui(terminal);
             let res: anyhow::Result<Option<PathBuf>> = (|| {
-                use dialoguer::{Input, theme::ColorfulTheme};
+                use dialoguer::Input;
 
-                let theme = ColorfulTheme::default();
-                let spec: String = Input::with_theme(&theme)
+                let theme = crate::prompt_theme();
+                let spec: String = Input::with_theme(&*theme)
                     .with_prompt("Branch name or GitHub PR URL")
+                    .validate_with(|s: &String| crate::check_spec_not_option(s.trim()))
                     .interact_text()?;
                 let spec = spec.trim().to_string();
                 if spec.is_empty() {
@@ -557,7 +1545,11 @@ fn handle_repo_key<W: Write>(
                 }
 
                 let wt_path = crate::create_worktree_from_spec(
-                    &anchor, cfg_root, &spec, None, None, None, false, true,
+                    &anchor,
+                    cfg_root,
+                    &spec,
+                    &new_options(state.keep_cwd),
+                    true,
                 )?;
                 Ok(Some(wt_path))
             })();
@@ -566,7 +1558,7 @@ fn handle_repo_key<W: Write>(
 
             match res? {
                 Some(wt_path) => {
-                    persist_repo_anchor(cfg_root, &repo.hash, &wt_path);
+                    persist_created_worktree(cfg_root, &repo.hash, &wt_path, state.keep_cwd);
                     return Ok(Some(Some(PickerSelection {
                         repo_anchor: anchor,
                         worktree_path: wt_path,
@@ -575,11 +1567,11 @@ fn handle_repo_key<W: Write>(
                 None => state.status = "new cancelled".to_string(),
             }
         }
-        KeyCode::Char('j') => {
+        KeyCode::Char('j') | KeyCode::Down => {
             state.repo_selected = (state.repo_selected + 1).min(vis_repos.len().saturating_sub(1));
             reset_chords(state);
         }
-        KeyCode::Char('k') => {
+        KeyCode::Char('k') | KeyCode::Up => {
             state.repo_selected = state.repo_selected.saturating_sub(1);
             reset_chords(state);
         }
@@ -604,17 +1596,21 @@ fn handle_repo_key<W: Write>(
             state.screen = Screen::Worktree;
             state.mode = Mode::Normal;
             state.wt_filter.clear();
+            state.dirty_filter = DirtyFilter::All;
             state.wt_selected = 0;
             state.hotkey_buf.clear();
             state.pending_g = false;
             state.pending_d = false;
             state.status =
-                "j/k move, / filter, enter select, n new, dd delete, esc back, ? help, q quit"
+                "j/k move, / filter, enter select, n new, dd delete, r refresh, esc back, ? help, q quit"
                     .to_string();
             match load_worktrees(cfg_root, repo) {
                 Ok((wts, anchor)) => {
                     let mut r = (*repo).clone();
                     r.anchor = anchor;
+                    let vis = visible_worktrees_idx(&wts, "");
+                    state.wt_selected =
+                        branch_position(&wts, &vis, r.last_branch.as_deref()).unwrap_or(0);
                     state.active_repo = Some(r);
                     state.wt_entries = wts;
                 }
@@ -626,19 +1622,16 @@ fn handle_repo_key<W: Write>(
 
             terminal.clear().ok();
         }
-        KeyCode::Char(c) => {
-            if is_repo_hotkey(c) {
-                push_hotkey(state, c);
+        KeyCode::Char(c) if is_repo_hotkey(c) && !state.instant_filter => {
+            push_hotkey(state, c);
 
-                if let Some(sel) = repo_code_map.get(&state.hotkey_buf).copied() {
-                    state.repo_selected = sel;
-                    if state.hotkey_buf.len() >= 2 {
-                        state.hotkey_buf.clear();
-                    }
-                } else if !has_prefix(&state.hotkey_buf, repo_codes) || state.hotkey_buf.len() >= 2
-                {
+            if let Some(sel) = repo_code_map.get(&state.hotkey_buf).copied() {
+                state.repo_selected = sel;
+                if state.hotkey_buf.len() >= 2 {
                     state.hotkey_buf.clear();
                 }
+            } else if !has_prefix(&state.hotkey_buf, repo_codes) || state.hotkey_buf.len() >= 2 {
+                state.hotkey_buf.clear();
             }
         }
         _ => {}
@@ -666,16 +1659,25 @@ fn handle_worktree_key<W: Write>(
     }
 
     // Confirmation mode for delete.
-    if state.mode == Mode::ConfirmDelete {
+    if matches!(state.mode, Mode::ConfirmDelete | Mode::ConfirmForceDelete) {
         match key.code {
             KeyCode::Char('y') | KeyCode::Char('Y') => {
+                let force = state.mode == Mode::ConfirmForceDelete;
                 let Some(target) = state.pending_delete.take() else {
                     state.mode = Mode::Normal;
                     state.status = "no delete target".to_string();
                     return Ok(None);
                 };
+                if !force && ask_force_if_dirty(state, &target) {
+                    return Ok(None);
+                }
 
-                state.delete_in_progress = Some(spawn_delete_worktree(&repo.anchor, &target)?);
+                state.delete_in_progress = Some(spawn_delete_worktree(
+                    cfg_root,
+                    &repo.anchor,
+                    &target,
+                    force,
+                )?);
                 state.mode = Mode::Deleting;
                 state.status = format!("deleting {}", target.to_string_lossy());
                 return Ok(None);
@@ -714,7 +1716,7 @@ fn handle_worktree_key<W: Write>(
             state.pending_g = false;
             state.pending_d = false;
             state.status =
-                "j/k move, gg/G top/bottom, / filter, enter select, n new, ? help, q quit"
+                "j/k move, gg/G top/bottom, / filter, enter select, n new, r refresh, ? help, q quit"
                     .to_string();
         }
         KeyCode::Char('n') => {
@@ -722,11 +1724,12 @@ fn handle_worktree_key<W: Write>(
 
             // Prompt for a new worktree/branch name and create it, then immediately select it.
             let res: anyhow::Result<Option<PathBuf>> = (|| {
-                use dialoguer::{Input, theme::ColorfulTheme};
+                use dialoguer::Input;
 
-                let theme = ColorfulTheme::default();
-                let spec: String = Input::with_theme(&theme)
+                let theme = crate::prompt_theme();
+                let spec: String = Input::with_theme(&*theme)
                     .with_prompt("Branch name or GitHub PR URL")
+                    .validate_with(|s: &String| crate::check_spec_not_option(s.trim()))
                     .interact_text()?;
                 let spec = spec.trim().to_string();
                 if spec.is_empty() {
@@ -737,10 +1740,7 @@ fn handle_worktree_key<W: Write>(
                     &repo.anchor,
                     cfg_root,
                     &spec,
-                    None,
-                    None,
-                    None,
-                    false,
+                    &new_options(state.keep_cwd),
                     true,
                 )?;
                 Ok(Some(wt_path))
@@ -760,16 +1760,42 @@ fn handle_worktree_key<W: Write>(
                 }
             }
         }
+        KeyCode::Char('D') => {
+            state.dirty_filter = state.dirty_filter.next();
+            state.wt_selected = 0;
+            state.status = format!("dirty filter: {}", state.dirty_filter.label());
+            reset_chords(state);
+        }
+        KeyCode::Char('P') => {
+            state.show_preview = !state.show_preview;
+            state.status = format!("preview {}", if state.show_preview { "on" } else { "off" });
+            reset_chords(state);
+        }
+        KeyCode::Char('R') => {
+            let Some(&i) = vis_wt_idx.get(state.wt_selected) else {
+                return Ok(None);
+            };
+            let entry = state.wt_entries[i].clone();
+            let is_main = main_worktree_idx(&state.wt_entries) == Some(i);
+            match rename_worktree_branch(terminal, cfg_root, &repo, &entry, is_main) {
+                Ok(Some(path)) => {
+                    refresh_worktrees(cfg_root, state, Some(&path));
+                    state.status = "branch renamed".to_string();
+                }
+                Ok(None) => state.status = "rename cancelled".to_string(),
+                Err(e) => state.status = format!("rename failed: {e:#}"),
+            }
+        }
         KeyCode::Char('/') => {
             state.mode = Mode::Filter;
             state.wt_filter.clear();
             state.status = "filter: type, enter to apply".to_string();
         }
-        KeyCode::Char('j') => {
+        KeyCode::Char('j') | KeyCode::Down => {
             state.wt_selected = (state.wt_selected + 1).min(vis_wt_idx.len().saturating_sub(1));
             reset_chords(state);
         }
-        KeyCode::Char('k') => {
+        KeyCode::Char('k') | KeyCode::Up => {
             state.wt_selected = state.wt_selected.saturating_sub(1);
             reset_chords(state);
         }
@@ -792,32 +1818,30 @@ fn handle_worktree_key<W: Write>(
                 .get(state.wt_selected)
                 .context("no worktree selected")?;
             let e = state.wt_entries.get(i).context("no worktree selected")?;
-            let wt_path = PathBuf::from(&e.path);
-            persist_repo_anchor(cfg_root, &repo.hash, &wt_path);
+            let wt_path = e.path.clone();
+            persist_repo_opened(cfg_root, &repo.hash, &wt_path, e.branch.as_deref());
             return Ok(Some(Some(PickerSelection {
                 repo_anchor: repo.anchor,
                 worktree_path: wt_path,
             })));
         }
-        KeyCode::Char(c) => {
-            if is_worktree_hotkey(c) {
-                let pool = hotkey_pool_worktrees();
-                let codes = assign_hotkeys(vis_wt_idx.len(), &pool);
-                let mut map = HashMap::new();
-                for (i, code) in codes.iter().enumerate() {
-                    map.insert(code.clone(), i);
-                }
+        KeyCode::Char(c) if is_worktree_hotkey(c) && !state.instant_filter => {
+            let pool = hotkey_pool_worktrees();
+            let codes = assign_hotkeys(vis_wt_idx.len(), &pool);
+            let mut map = HashMap::new();
+            for (i, code) in codes.iter().enumerate() {
+                map.insert(code.clone(), i);
+            }
 
-                push_hotkey(state, c);
+            push_hotkey(state, c);
 
-                if let Some(sel) = map.get(&state.hotkey_buf).copied() {
-                    state.wt_selected = sel;
-                    if state.hotkey_buf.len() >= 2 {
-                        state.hotkey_buf.clear();
-                    }
-                } else if !has_prefix(&state.hotkey_buf, &codes) || state.hotkey_buf.len() >= 2 {
+            if let Some(sel) = map.get(&state.hotkey_buf).copied() {
+                state.wt_selected = sel;
+                if state.hotkey_buf.len() >= 2 {
                     state.hotkey_buf.clear();
                 }
+            } else if !has_prefix(&state.hotkey_buf, &codes) || state.hotkey_buf.len() >= 2 {
+                state.hotkey_buf.clear();
             }
         }
         _ => {}
@@ -826,7 +1850,322 @@ fn handle_worktree_key<W: Write>(
     Ok(None)
 }
 
-fn load_worktrees(
+fn handle_flat_key(
+    cfg_root: &Path,
+    state: &mut AppState,
+    key: KeyEvent,
+    vis_flat_idx: &[usize],
+) -> anyhow::Result<Option<Option<PickerSelection>>> {
+    let selectable = flat_selectable(&state.flat_rows, vis_flat_idx);
+    match key.code {
+        KeyCode::Char('q') | KeyCode::Esc => return Ok(Some(None)),
+        KeyCode::Char('?') => {
+            state.mode = Mode::Help;
+            state.status = "press ?/esc/q to close help".to_string();
+        }
+        KeyCode::Char('/') => {
+            state.mode = Mode::Filter;
+            state.wt_filter.clear();
+            state.status = "filter: type, enter to apply".to_string();
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            state.flat_selected = step_selectable(&selectable, state.flat_selected, true);
+            reset_chords(state);
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            state.flat_selected = step_selectable(&selectable, state.flat_selected, false);
+            reset_chords(state);
+        }
+        KeyCode::Char('G') => {
+            state.flat_selected = last_selectable(&selectable);
+            reset_chords(state);
+        }
+        KeyCode::Char('g') => {
+            if state.pending_g {
+                state.flat_selected = first_selectable(&selectable);
+                state.pending_g = false;
+            } else {
+                state.pending_g = true;
+                state.last_g_at = Instant::now();
+            }
+        }
+        KeyCode::Enter => {
+            let i = *vis_flat_idx
+                .get(state.flat_selected)
+                .context("no worktree selected")?;
+            let FlatRow::Worktree { repo, entry } = &state.flat_rows[i] else {
+                return Ok(None);
+            };
+            let repo = &state.flat_repos[*repo];
+            let wt_path = entry.path.clone();
+            persist_repo_opened(cfg_root, &repo.hash, &wt_path, entry.branch.as_deref());
+            return Ok(Some(Some(PickerSelection {
+                repo_anchor: repo.anchor.clone(),
+                worktree_path: wt_path,
+            })));
+        }
+        _ => {}
+    }
+    Ok(None)
+}
+
+/// Load worktrees for many repos at once, one thread per repo. Results keep `repos` order.
+pub(crate) fn load_all_worktrees(
+    cfg_root: &Path,
+    repos: &[KnownRepo],
+) -> Vec<anyhow::Result<(Vec<WorktreeEntry>, PathBuf)>> {
+    thread::scope(|s| {
+        let handles: Vec<_> = repos
+            .iter()
+            .map(|repo| s.spawn(move || load_worktrees(cfg_root, repo)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| {
+                h.join()
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("worktree loader panicked")))
+            })
+            .collect()
+    })
+}
+
+/// Preview shown when `preview_cmd` isn't configured: the worktree's recent commits.
+const DEFAULT_PREVIEW_CMD: &str = "git -C {path} log --oneline -n 10";
+
+/// How long `preview_cmd` may run before it is killed and the preview left empty.
+const PREVIEW_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Collect a finished preview and start one for the highlighted worktree if it isn't cached.
+/// One command runs at a time, in the background, so slow previews never block input.
+fn update_preview(state: &mut AppState, vis_wt_idx: &[usize]) {
+    if !state.show_preview {
+        return;
+    }
+    let cmd = state.preview_cmd.as_str();
+    if let Some(job) = &state.preview_job {
+        match job.receiver.try_recv() {
+            Ok(out) => {
+                state.previews.insert(job.path.clone(), out);
+            }
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                state.previews.insert(job.path.clone(), None);
+            }
+        }
+        state.preview_job = None;
+    }
+    let Some(e) = vis_wt_idx
+        .get(state.wt_selected)
+        .map(|&i| &state.wt_entries[i])
+    else {
+        return;
+    };
+    if state.previews.contains_key(&e.path) {
+        return;
+    }
+    let command = preview_command(cmd, &e.display_path(), e.branch.as_deref());
+    let (tx, rx) = mpsc::channel();
+    let dir = e.path.clone();
+    thread::spawn(move || {
+        let _ = tx.send(run_preview(&command, &dir));
+    });
+    state.preview_job = Some(PreviewJob {
+        path: e.path.clone(),
+        receiver: rx,
+    });
+}
+
+/// `template` with `{path}` and `{branch}` replaced by the shell-quoted values (a detached
+/// worktree's branch is empty). Substituted text is never rescanned for placeholders.
+fn preview_command(template: &str, path: &str, branch: Option<&str>) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        if let Some(after) = tail.strip_prefix("{path}") {
+            out.push_str(&crate::shell_quote(path));
+            rest = after;
+        } else if let Some(after) = tail.strip_prefix("{branch}") {
+            out.push_str(&crate::shell_quote(branch.unwrap_or_default()));
+            rest = after;
+        } else {
+            out.push('{');
+            rest = &tail[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Run `command` with `sh -c` in `dir`; its stdout, or `None` on failure or after
+/// [`PREVIEW_TIMEOUT`].
+fn run_preview(command: &str, dir: &Path) -> Option<String> {
+    let mut child = std::process::Command::new("sh")
+        .args(["-c", command])
+        .current_dir(dir)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .ok()?;
+    // Drain stdout on its own thread so a chatty command can't block on a full pipe.
+    let mut stdout = child.stdout.take()?;
+    let reader = thread::spawn(move || {
+        let mut buf = Vec::new();
+        io::Read::read_to_end(&mut stdout, &mut buf).map(|_| buf)
+    });
+    let deadline = Instant::now() + PREVIEW_TIMEOUT;
+    let status = loop {
+        match child.try_wait().ok()? {
+            Some(status) => break status,
+            None if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+            None => thread::sleep(Duration::from_millis(20)),
+        }
+    };
+    let buf = reader.join().ok()?.ok()?;
+    status
+        .success()
+        .then(|| String::from_utf8_lossy(&buf).into_owned())
+}
+
+/// `(ahead, behind)` counts against `@{u}` for each worktree on a branch, one `git rev-list` per
+/// worktree in parallel (best-effort; worktrees without an upstream are left out).
+fn load_ahead_behind(entries: &[WorktreeEntry]) -> HashMap<PathBuf, (usize, usize)> {
+    thread::scope(|s| {
+        let handles: Vec<_> = entries
+            .iter()
+            .filter(|e| e.branch.is_some() && !e.bare)
+            .map(|e| {
+                s.spawn(move || {
+                    let out = std::process::Command::new("git")
+                        .current_dir(&e.path)
+                        .args(["rev-list", "--left-right", "--count", "@{u}...HEAD"])
+                        .stderr(std::process::Stdio::null())
+                        .output()
+                        .ok()
+                        .filter(|o| o.status.success())?;
+                    let counts = parse_left_right_count(&String::from_utf8_lossy(&out.stdout))?;
+                    Some((e.path.clone(), counts))
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .filter_map(|h| h.join().ok().flatten())
+            .collect()
+    })
+}
+
+/// Paths of worktrees with uncommitted changes (any `git status --porcelain` entry), checked in
+/// parallel; worktrees whose status can't be read count as clean.
+fn load_dirty_worktrees(entries: &[WorktreeEntry]) -> HashSet<PathBuf> {
+    thread::scope(|s| {
+        let handles: Vec<_> = entries
+            .iter()
+            .filter(|e| !e.bare)
+            .map(|e| {
+                s.spawn(move || {
+                    crate::count_uncommitted(&e.path)
+                        .is_ok_and(|n| n > 0)
+                        .then(|| e.path.clone())
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .filter_map(|h| h.join().ok().flatten())
+            .collect()
+    })
+}
+
+/// Parse `git rev-list --left-right --count @{u}...HEAD` (`<behind>\t<ahead>`) as
+/// `(ahead, behind)`.
+fn parse_left_right_count(out: &str) -> Option<(usize, usize)> {
+    let mut parts = out.split_whitespace();
+    let behind = parts.next()?.parse().ok()?;
+    let ahead = parts.next()?.parse().ok()?;
+    Some((ahead, behind))
+}
+
+/// `↑2 ↓1`-style label, leaving out zero counts; `None` when in sync with the upstream.
+fn ahead_behind_label(ahead: usize, behind: usize) -> Option<String> {
+    let parts: Vec<String> = [(ahead, '↑'), (behind, '↓')]
+        .into_iter()
+        .filter(|&(n, _)| n > 0)
+        .map(|(n, arrow)| format!("{arrow}{n}"))
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+/// Local branches whose upstream no longer exists (best-effort; empty on git errors).
+fn load_gone_branches(anchor: &Path) -> HashSet<String> {
+    std::process::Command::new("git")
+        .current_dir(anchor)
+        .args(["branch", "-vv"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| parse_gone_branches(&String::from_utf8_lossy(&o.stdout)))
+        .unwrap_or_default()
+}
+
+/// Branch descriptions (`git branch --edit-description`, `gw new --set-description`) by branch
+/// name, first line only.
+fn load_branch_descriptions(anchor: &Path) -> HashMap<String, String> {
+    std::process::Command::new("git")
+        .current_dir(anchor)
+        .args([
+            "config",
+            "--null",
+            "--get-regexp",
+            r"^branch\..*\.description$",
+        ])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| parse_branch_descriptions(&String::from_utf8_lossy(&o.stdout)))
+        .unwrap_or_default()
+}
+
+/// Parse `git config --null --get-regexp` output: `<key>\n<value>\0` per entry.
+fn parse_branch_descriptions(out: &str) -> HashMap<String, String> {
+    out.split('\0')
+        .filter_map(|entry| {
+            let (key, value) = entry.split_once('\n')?;
+            let branch = key.strip_prefix("branch.")?.strip_suffix(".description")?;
+            let first = value.lines().next()?.trim();
+            (!first.is_empty()).then(|| (branch.to_string(), first.to_string()))
+        })
+        .collect()
+}
+
+/// Parse `git branch -vv` output for branches whose tracking info reads `[<upstream>: gone]`.
+fn parse_gone_branches(branch_vv: &str) -> HashSet<String> {
+    branch_vv
+        .lines()
+        .filter_map(|line| {
+            // Two-column marker: `* ` current, `+ ` checked out in another worktree.
+            let rest = line.get(2..)?;
+            let mut parts = rest.split_whitespace();
+            let name = parts.next()?;
+            if name.starts_with('(') {
+                return None; // `(HEAD detached at ...)`
+            }
+            let sha = parts.next()?;
+            let after_sha = rest.split_once(sha)?.1.trim_start();
+            // Only the bracketed upstream right after the sha counts, not the commit subject.
+            let tracking = after_sha.strip_prefix('[')?.split(']').next()?;
+            tracking.ends_with(": gone").then(|| name.to_string())
+        })
+        .collect()
+}
+
+pub(crate) fn load_worktrees(
     cfg_root: &Path,
     repo: &KnownRepo,
 ) -> anyhow::Result<(Vec<WorktreeEntry>, PathBuf)> {
@@ -840,8 +2179,7 @@ fn load_worktrees(
         if let Ok(out) = out
             && out.status.success()
         {
-            let txt = String::from_utf8(out.stdout)?;
-            return Ok((parse_worktree_porcelain(&txt), repo.anchor.clone()));
+            return Ok((parse_worktree_porcelain(&out.stdout), repo.anchor.clone()));
         }
     }
 
@@ -860,12 +2198,11 @@ fn load_worktrees(
         );
     }
 
-    let txt = String::from_utf8(out.stdout)?;
-    let entries = parse_worktree_porcelain(&txt);
+    let entries = parse_worktree_porcelain(&out.stdout);
 
     // Repair the stored anchor to something valid so future opens work without fallback.
     let anchor = if let Some(first) = entries.first() {
-        PathBuf::from(&first.path)
+        first.path.clone()
     } else {
         // Shouldn't happen, but avoid returning a bogus path.
         repo.anchor.clone()
@@ -939,14 +2276,21 @@ fn command_hint(screen: Screen, mode: Mode) -> &'static str {
     match mode {
         Mode::Filter => "commands: type to filter, enter apply, esc cancel",
         Mode::ConfirmDelete => "commands: y confirm delete, n/esc cancel",
+        Mode::ConfirmForceDelete => {
+            "commands: y force delete (discards uncommitted changes), n/esc cancel"
+        }
         Mode::Deleting => "commands: wait for delete to finish",
+        Mode::ConfirmQuit => "commands: y quit, n/esc keep the filter",
         Mode::Help => "commands: ?/esc/q close help",
         Mode::Normal => match screen {
             Screen::Repo => {
-                "commands: j/k move, gg/G top/bottom, / filter, enter open, n new, ? help, q/esc quit"
+                "commands: j/k move, gg/G top/bottom, / filter, enter open, n new, S sort, r refresh, ? help, q/esc quit"
             }
             Screen::Worktree => {
-                "commands: j/k move, gg/G top/bottom, / filter, enter select, n new, dd delete, esc back, ? help, q quit"
+                "commands: j/k move, gg/G top/bottom, / filter, D dirty filter, P preview, S sort, enter select, n new, dd delete, r refresh, esc back, ? help, q quit"
+            }
+            Screen::Flat => {
+                "commands: j/k move, gg/G top/bottom, / filter, enter select, S sort, r refresh, ? help, q/esc quit"
             }
         },
     }
@@ -973,13 +2317,52 @@ fn footer_text(status: &str, screen: Screen, mode: Mode, spinner: Option<char>)
     }
 }
 
-fn spawn_delete_worktree(repo_anchor: &Path, target: &Path) -> anyhow::Result<DeleteInProgress> {
+/// If `target` has uncommitted changes, list them in the status and switch to the force-delete
+/// confirmation (keeping `target` pending). Returns whether it did.
+fn ask_force_if_dirty(state: &mut AppState, target: &Path) -> bool {
+    let dirty = crate::uncommitted_paths(target).unwrap_or_default();
+    if dirty.is_empty() {
+        return false;
+    }
+    const SHOWN: usize = 3;
+    let mut listed = dirty
+        .iter()
+        .take(SHOWN)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if dirty.len() > SHOWN {
+        listed.push_str(&format!(", +{} more", dirty.len() - SHOWN));
+    }
+    state.pending_delete = Some(target.to_path_buf());
+    state.mode = Mode::ConfirmForceDelete;
+    state.status = format!(
+        "{} has uncommitted changes ({listed}); force delete? (y/n)",
+        target.to_string_lossy()
+    );
+    true
+}
+
+fn spawn_delete_worktree(
+    cfg_root: &Path,
+    repo_anchor: &Path,
+    target: &Path,
+    force: bool,
+) -> anyhow::Result<DeleteInProgress> {
     let job = prepare_delete_worktree(repo_anchor, target)?;
+    let cfg_root = cfg_root.to_path_buf();
     let started_at = Instant::now();
     let (tx, rx) = mpsc::channel();
     let target = job.target.clone();
     thread::spawn(move || {
-        let res = run_delete_worktree(job.main, job.target);
+        let (main, target) = (job.main.clone(), job.target.clone());
+        // The anchor may have been the removed worktree; detect the repo from main instead.
+        let res = crate::RepoContext::detect_from_path(&main).and_then(|repo| {
+            // Hook output is captured so it can't draw over the picker.
+            crate::run_pre_remove_hooks(&cfg_root, &repo, &target, Some(&mut Vec::new()))?;
+            run_delete_worktree(job.main, job.target, force)?;
+            crate::prune_worktree_config(&cfg_root, &repo, &target, &main).map(|_| ())
+        });
         let _ = tx.send(res);
     });
 
@@ -997,21 +2380,10 @@ struct DeleteWorktreeJob {
 
 fn prepare_delete_worktree(repo_anchor: &Path, target: &Path) -> anyhow::Result<DeleteWorktreeJob> {
     let repo = crate::RepoContext::detect_from_path(repo_anchor)?;
-    let out = std::process::Command::new("git")
-        .current_dir(&repo.toplevel)
-        .args(["worktree", "list", "--porcelain"])
-        .output()?;
-    if !out.status.success() {
-        anyhow::bail!(
-            "git worktree list failed: {}",
-            String::from_utf8_lossy(&out.stderr).trim()
-        );
-    }
-
-    let entries = crate::parse_worktree_porcelain(&String::from_utf8(out.stdout)?);
+    let entries = crate::list_worktrees(&repo.toplevel)?;
     let main = entries
         .first()
-        .map(|entry| PathBuf::from(&entry.path))
+        .map(|entry| entry.path.clone())
         .unwrap_or(repo.toplevel);
     let main = std::fs::canonicalize(&main).unwrap_or(main);
     let target = std::fs::canonicalize(target).unwrap_or_else(|_| target.to_path_buf());
@@ -1036,17 +2408,15 @@ fn prepare_delete_worktree(repo_anchor: &Path, target: &Path) -> anyhow::Result<
     Ok(DeleteWorktreeJob { main, target })
 }
 
-fn run_delete_worktree(main: PathBuf, target: PathBuf) -> anyhow::Result<()> {
-    let out = std::process::Command::new("git")
-        .current_dir(&main)
-        .args(["worktree", "remove"])
-        .arg(&target)
-        .output()?;
+fn run_delete_worktree(main: PathBuf, target: PathBuf, force: bool) -> anyhow::Result<()> {
+    let mut cmd = std::process::Command::new("git");
+    cmd.current_dir(&main).args(["worktree", "remove"]);
+    if force {
+        cmd.arg("--force");
+    }
+    let out = cmd.arg(&target).output()?;
     if !out.status.success() {
-        anyhow::bail!(
-            "{}",
-            String::from_utf8_lossy(&out.stderr).trim().to_string()
-        );
+        anyhow::bail!("{}", String::from_utf8_lossy(&out.stderr).trim());
     }
     Ok(())
 }
@@ -1067,7 +2437,7 @@ fn handle_worktree_delete_chord(
             .get(state.wt_selected)
             .context("no worktree selected")?;
         let e = state.wt_entries.get(i).context("no worktree selected")?;
-        let target = PathBuf::from(&e.path);
+        let target = e.path.clone();
         state.pending_delete = Some(target.clone());
         state.mode = Mode::ConfirmDelete;
         state.status = format!("delete {} ? (y/n)", target.to_string_lossy());
@@ -1080,17 +2450,89 @@ fn handle_worktree_delete_chord(
 }
 
 fn persist_repo_anchor(cfg_root: &Path, repo_hash: &str, anchor: &Path) {
+    update_repo_config(cfg_root, repo_hash, |cfg| {
+        cfg.anchor_path = anchor.to_string_lossy().to_string();
+    });
+}
+
+/// Options for worktrees created with `n`.
+fn new_options(keep_cwd: bool) -> crate::NewOptions {
+    crate::NewOptions {
+        keep_cwd,
+        ..Default::default()
+    }
+}
+
+/// Select a worktree just created with `n` for next time, unless `keep_cwd` asks to leave the
+/// anchor and recency alone.
+fn persist_created_worktree(cfg_root: &Path, repo_hash: &str, wt_path: &Path, keep_cwd: bool) {
+    if !keep_cwd {
+        let branch = head_branch(wt_path);
+        persist_repo_opened(cfg_root, repo_hash, wt_path, branch.as_deref());
+    }
+}
+
+/// The branch checked out in `wt_path`, if HEAD isn't detached.
+fn head_branch(wt_path: &Path) -> Option<String> {
+    let out = std::process::Command::new("git")
+        .current_dir(wt_path)
+        .args(["symbolic-ref", "--quiet", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    Some(String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+/// Like [`persist_repo_anchor`], and also stamps `last_opened` on the repo and the worktree at
+/// `anchor` for the recency sort, and remembers `branch` as the selection for next time.
+fn persist_repo_opened(cfg_root: &Path, repo_hash: &str, anchor: &Path, branch: Option<&str>) {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    update_repo_config(cfg_root, repo_hash, |cfg| {
+        cfg.anchor_path = anchor.to_string_lossy().to_string();
+        cfg.last_opened = Some(now);
+        cfg.worktrees
+            .entry(cfg.anchor_path.clone())
+            .or_default()
+            .last_opened = Some(now);
+        cfg.last_branch = branch.map(str::to_string);
+    });
+}
+
+fn update_repo_config(cfg_root: &Path, repo_hash: &str, f: impl FnOnce(&mut RepoConfig)) {
     let cfg_path = cfg_root.join("repos").join(repo_hash).join("config.toml");
     if let Ok(s) = std::fs::read_to_string(&cfg_path)
         && let Ok(mut cfg) = toml::from_str::<RepoConfig>(&s)
     {
-        cfg.anchor_path = anchor.to_string_lossy().to_string();
+        f(&mut cfg);
         if let Ok(s2) = toml::to_string_pretty(&cfg) {
             let _ = std::fs::write(cfg_path, s2);
         }
     }
 }
 
+/// Foreground color, or plain when colors are disabled.
+fn fg(color: bool, c: Color) -> Style {
+    if color {
+        Style::default().fg(c)
+    } else {
+        Style::default()
+    }
+}
+
+fn highlight_style(color: bool) -> Style {
+    if color {
+        Style::default()
+            .bg(Color::Blue)
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+    }
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let w = r.width.saturating_mul(percent_x) / 100;
     let h = r.height.saturating_mul(percent_y) / 100;
@@ -1124,6 +2566,8 @@ Keys:
 - /: filter
 - enter: open repo's worktrees
 - n: create a new worktree for the highlighted repo (then select it)
+- S: switch between name and most-recently-opened order
+- r: refresh the repo list
 - ?: help
 - q/esc: quit
 
@@ -1142,6 +2586,11 @@ Keys:
 - enter: select highlighted worktree
 - n: create a new worktree for this repo (then select it)
 - dd: delete highlighted worktree (confirmation; branch preserved)
+- R: rename the highlighted worktree's branch (optionally moving its directory)
+- D: cycle showing all / only dirty / only clean worktrees
+- P: toggle the preview pane (recent commits, or config `preview_cmd`)
+- S: switch between name and most-recently-picked order (main worktree stays first)
+- r: refresh the worktree list (e.g. after changes in another terminal)
 - esc: back to repos
 - ?: help
 - q: quit
@@ -1149,7 +2598,68 @@ Keys:
 {common}
 "#
         ),
+        Screen::Flat => r#"All Worktrees (depth-first)
+
+Every known repo's worktrees in one list, grouped under repo headers.
+Headers are not selectable; j/k skip over them.
+
+Keys:
+- j/k: move
+- gg/G: top/bottom
+- /: filter worktrees (headers stay with their matching worktrees)
+- enter: select highlighted worktree
+- S: switch repo groups between name and most-recently-opened order
+- r: refresh all repos and worktrees
+- ?: help
+- q/esc: quit
+"#
+        .to_string(),
+    }
+}
+
+fn push_hotkey(state: &mut AppState, c: char) {
+    if state.hotkey_buf.len() >= 2 {
+        state.hotkey_buf.clear();
+    }
+    state.hotkey_buf.push(c);
+    state.last_hotkey_at = Instant::now();
+}
+
+fn has_prefix(buf: &str, codes: &[String]) -> bool {
+    codes.iter().any(|c| c.starts_with(buf))
+}
+
+/// `[code] ` prefix for row `i`; empty when hotkeys are off (`instant_filter`).
+fn hotkey_label(codes: &[String], i: usize, instant_filter: bool) -> String {
+    if instant_filter {
+        return String::new();
     }
+    format!(
+        "[{}] ",
+        codes.get(i).map(String::as_str).unwrap_or_default()
+    )
+}
+
+fn is_repo_hotkey(c: char) -> bool {
+    hotkey_pool_repos().contains(&c)
+}
+
+fn is_worktree_hotkey(c: char) -> bool {
+    hotkey_pool_worktrees().contains(&c)
+}
+
+fn hotkey_pool_repos() -> Vec<char> {
+    vec![
+        'a', 's', 'd', 'f', 'h', 'l', 'w', 'e', 't', 'y', 'u', 'i', 'o', 'p', 'z', 'x', 'c', 'v',
+        'b', 'n', 'm',
+    ]
+}
+
+fn hotkey_pool_worktrees() -> Vec<char> {
+    vec![
+        'a', 's', 'f', 'h', 'l', 'w', 'e', 't', 'y', 'u', 'i', 'o', 'p', 'z', 'x', 'c', 'v', 'b',
+        'm',
+    ]
 }
 
 #[cfg(test)]
@@ -1197,8 +2707,7 @@ mod tests {
                 .join("does-not-exist")
                 .to_string_lossy()
                 .to_string(),
-            worktrees_dir: None,
-            hooks: Vec::new(),
+            ..Default::default()
         };
         crate::save_repo_config(&cfg_root, &ctx, &cfg).unwrap();
 
@@ -1232,6 +2741,39 @@ mod tests {
         assert!(wt_help.contains("Worktree Picker"));
     }
 
+    #[test]
+    fn help_text_lists_every_key_per_screen() {
+        // spec: GW-PICK-017
+        let expected: [(Screen, &[&str]); 3] = [
+            (
+                Screen::Repo,
+                &[
+                    "- j/k:", "- gg/G:", "- /:", "- enter:", "- n:", "- S:", "- r:", "- ?:",
+                    "- q/esc:",
+                ],
+            ),
+            (
+                Screen::Worktree,
+                &[
+                    "- j/k:", "- gg/G:", "- /:", "- enter:", "- n:", "- dd:", "- R:", "- D:",
+                    "- P:", "- S:", "- r:", "- esc:", "- ?:", "- q:",
+                ],
+            ),
+            (
+                Screen::Flat,
+                &[
+                    "- j/k:", "- gg/G:", "- /:", "- enter:", "- S:", "- r:", "- ?:", "- q/esc:",
+                ],
+            ),
+        ];
+        for (screen, keys) in expected {
+            let help = help_text(screen);
+            for key in keys {
+                assert!(help.contains(key), "{screen:?} help is missing {key:?}");
+            }
+        }
+    }
+
     #[test]
     fn persist_repo_anchor_updates_anchor_path() {
         // spec: GW-PICK-006
@@ -1252,8 +2794,7 @@ mod tests {
             repo_name: ctx.repo_name.clone(),
             git_common_dir: ctx.git_common_dir.to_string_lossy().to_string(),
             anchor_path: ctx.toplevel.to_string_lossy().to_string(),
-            worktrees_dir: None,
-            hooks: Vec::new(),
+            ..Default::default()
         };
         crate::save_repo_config(&cfg_root, &ctx, &cfg).unwrap();
 
@@ -1264,6 +2805,108 @@ mod tests {
         assert_eq!(repaired.anchor_path, new_anchor.to_string_lossy().as_ref());
     }
 
+    #[test]
+    fn picker_rename_moves_the_worktree_only_when_asked() {
+        // spec: GW-PICK-015
+        let td = TempDir::new().unwrap();
+        let root = std::fs::canonicalize(td.path()).unwrap();
+        let repo = root.join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        run_git(&repo, &["init"]);
+        run_git(&repo, &["config", "user.email", "gw@example.com"]);
+        run_git(&repo, &["config", "user.name", "gw"]);
+        std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+        run_git(&repo, &["add", "."]);
+        run_git(&repo, &["commit", "-m", "init"]);
+
+        let cfg_root = root.join("cfg");
+        let ctx = crate::RepoContext::detect_from_path(&repo).unwrap();
+        let wts = root.join("wts");
+        crate::save_repo_config(
+            &cfg_root,
+            &ctx,
+            &RepoConfig {
+                repo_name: ctx.repo_name.clone(),
+                git_common_dir: ctx.git_common_dir.to_string_lossy().to_string(),
+                anchor_path: ctx.toplevel.to_string_lossy().to_string(),
+                worktrees_dir: Some(wts.to_string_lossy().to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let opts = crate::NewOptions {
+            no_hooks: true,
+            ..new_options(false)
+        };
+        let wt =
+            crate::create_worktree_from_spec(&repo, &cfg_root, "feat/a", &opts, false).unwrap();
+        persist_created_worktree(&cfg_root, &ctx.repo_hash, &wt, false);
+
+        // Without moving, the directory stays put.
+        let moved = crate::rename_branch(&cfg_root, &ctx, "feat/a", "feat/b", false).unwrap();
+        assert!(moved.is_none());
+        assert!(wt.exists());
+
+        // Moving goes where `gw new feat/c` would put it and keeps the config pointing at it.
+        let moved = crate::rename_branch(&cfg_root, &ctx, "feat/b", "feat/c", true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(moved.path, wts.join("feat").join("c"));
+        assert!(moved.path.join("README.md").exists());
+        let cfg = crate::load_repo_config(&cfg_root, &ctx).unwrap();
+        assert_eq!(cfg.anchor_path, moved.path.to_string_lossy());
+        assert!(cfg.worktrees.contains_key(cfg.anchor_path.as_str()));
+    }
+
+    #[test]
+    fn picker_new_with_keep_cwd_leaves_anchor_unchanged() {
+        // spec: GW-PICK-024
+        let td = TempDir::new().unwrap();
+        let repo = td.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        run_git(&repo, &["init"]);
+        run_git(&repo, &["config", "user.email", "gw@example.com"]);
+        run_git(&repo, &["config", "user.name", "gw"]);
+        std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+        run_git(&repo, &["add", "."]);
+        run_git(&repo, &["commit", "-m", "init"]);
+
+        let cfg_root = td.path().join("cfg");
+        let ctx = crate::RepoContext::detect_from_path(&repo).unwrap();
+        let cfg = RepoConfig {
+            repo_name: ctx.repo_name.clone(),
+            git_common_dir: ctx.git_common_dir.to_string_lossy().to_string(),
+            anchor_path: ctx.toplevel.to_string_lossy().to_string(),
+            worktrees_dir: Some(td.path().join("wts").to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        crate::save_repo_config(&cfg_root, &ctx, &cfg).unwrap();
+
+        let create = |branch: &str, keep_cwd: bool| {
+            let opts = crate::NewOptions {
+                no_hooks: true,
+                ..new_options(keep_cwd)
+            };
+            let wt =
+                crate::create_worktree_from_spec(&repo, &cfg_root, branch, &opts, false).unwrap();
+            persist_created_worktree(&cfg_root, &ctx.repo_hash, &wt, keep_cwd);
+            (wt, crate::load_repo_config(&cfg_root, &ctx).unwrap())
+        };
+
+        let (wt, after) = create("scratch", true);
+        assert!(wt.exists());
+        assert_eq!(after.anchor_path, cfg.anchor_path);
+        assert_eq!(after.last_opened, None);
+
+        let (wt, after) = create("feat", false);
+        assert_eq!(after.anchor_path, wt.to_string_lossy().as_ref());
+        assert!(after.last_opened.is_some());
+        assert_eq!(
+            after.worktrees[after.anchor_path.as_str()].last_opened,
+            after.last_opened
+        );
+    }
+
     #[test]
     fn footer_text_always_includes_command_hint() {
         // spec: GW-PICK-104
@@ -1310,7 +2953,7 @@ mod tests {
         std::fs::write(wt.join("README.md"), "changed\n").unwrap();
 
         let job = prepare_delete_worktree(&repo, &wt).unwrap();
-        let err = run_delete_worktree(job.main, job.target).unwrap_err();
+        let err = run_delete_worktree(job.main, job.target, false).unwrap_err();
         assert!(
             err.to_string()
                 .contains("contains modified or untracked files"),
@@ -1318,34 +2961,504 @@ mod tests {
         );
     }
 
+    #[test]
+    fn delete_job_aborts_when_pre_remove_hook_fails() {
+        // spec: GW-RM-013
+        let td = TempDir::new().unwrap();
+        let repo = td.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+
+        run_git(&repo, &["init"]);
+        run_git(&repo, &["config", "user.email", "gw@example.com"]);
+        run_git(&repo, &["config", "user.name", "gw"]);
+        std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+        run_git(&repo, &["add", "."]);
+        run_git(&repo, &["commit", "-m", "init"]);
+
+        let wt = td.path().join("wt");
+        run_git(
+            &repo,
+            &["worktree", "add", "-b", "feat", wt.to_str().unwrap()],
+        );
+        let cfg_root = td.path().join("cfg");
+        std::fs::create_dir_all(&cfg_root).unwrap();
+        std::fs::write(
+            cfg_root.join("config.toml"),
+            "[[hooks]]\ncommand = \"echo noisy; exit 7\"\nwhen = \"pre_remove\"\n",
+        )
+        .unwrap();
+
+        let job = spawn_delete_worktree(&cfg_root, &repo, &wt, false).unwrap();
+        let err = job.receiver.recv().unwrap().unwrap_err();
+        assert!(err.to_string().contains("hook failed"), "got: {err:#}");
+        assert!(wt.join("README.md").exists());
+    }
+
+    #[test]
+    fn dirty_worktrees_are_those_with_uncommitted_changes() {
+        // spec: GW-PICK-023
+        let td = TempDir::new().unwrap();
+        let repo = td.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        run_git(&repo, &["init"]);
+        run_git(&repo, &["config", "user.email", "gw@example.com"]);
+        run_git(&repo, &["config", "user.name", "gw"]);
+        std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+        run_git(&repo, &["add", "."]);
+        run_git(&repo, &["commit", "-m", "init"]);
+        let wt = td.path().join("wt");
+        run_git(
+            &repo,
+            &["worktree", "add", "-b", "feat", wt.to_str().unwrap()],
+        );
+        std::fs::write(wt.join("scratch.txt"), "new\n").unwrap();
+
+        let entry = |p: &Path| WorktreeEntry {
+            path: p.to_path_buf(),
+            ..Default::default()
+        };
+        let dirty = load_dirty_worktrees(&[entry(&repo), entry(&wt)]);
+        assert_eq!(dirty, HashSet::from([wt.clone()]));
+    }
+
+    #[test]
+    fn dirty_filter_cycles_and_narrows_worktrees() {
+        // spec: GW-PICK-026
+        let f = DirtyFilter::default();
+        assert_eq!(f, DirtyFilter::All);
+        assert_eq!(f.next(), DirtyFilter::DirtyOnly);
+        assert_eq!(f.next().next(), DirtyFilter::CleanOnly);
+        assert_eq!(f.next().next().next(), DirtyFilter::All);
+
+        let entry = |p: &str| WorktreeEntry {
+            path: p.into(),
+            ..Default::default()
+        };
+        let entries = vec![
+            entry("/r/main"),
+            entry("/r/a"),
+            entry("/r/b"),
+            entry("/r/c"),
+        ];
+        let dirty = HashSet::from([PathBuf::from("/r/main"), PathBuf::from("/r/b")]);
+        let vis = || vec![0, 1, 2, 3];
+        let filtered = |f| filter_by_dirty(&entries, vis(), Some(&dirty), f);
+        assert_eq!(filtered(DirtyFilter::All), [0, 1, 2, 3]);
+        // The main worktree stays either way.
+        assert_eq!(filtered(DirtyFilter::DirtyOnly), [0, 2]);
+        assert_eq!(filtered(DirtyFilter::CleanOnly), [0, 1, 3]);
+        // Applies on top of the text filter.
+        assert_eq!(
+            filter_by_dirty(&entries, vec![2, 3], Some(&dirty), DirtyFilter::CleanOnly),
+            [3]
+        );
+        // Dirty state not computed yet: nothing is hidden.
+        assert_eq!(
+            filter_by_dirty(&entries, vis(), None, DirtyFilter::DirtyOnly),
+            [0, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn branch_descriptions_are_read_from_git_config() {
+        // spec: GW-NEW-066
+        let td = TempDir::new().unwrap();
+        let repo = td.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        run_git(&repo, &["init"]);
+        run_git(
+            &repo,
+            &[
+                "config",
+                "branch.feat/x.y.description",
+                "Fix login\nmore detail",
+            ],
+        );
+        run_git(&repo, &["config", "branch.empty.description", ""]);
+
+        let descriptions = load_branch_descriptions(&repo);
+        assert_eq!(
+            descriptions,
+            HashMap::from([("feat/x.y".to_string(), "Fix login".to_string())])
+        );
+        assert!(load_branch_descriptions(&td.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn flat_navigation_skips_repo_headers() {
+        // spec: GW-PICK-008
+        let wt = |repo: usize, path: &str| FlatRow::Worktree {
+            repo,
+            entry: WorktreeEntry {
+                path: path.into(),
+                ..Default::default()
+            },
+        };
+        let rows = vec![
+            FlatRow::Header { repo: 0 },
+            wt(0, "/a/main"),
+            wt(0, "/a/feat"),
+            FlatRow::Header { repo: 1 },
+            FlatRow::Header { repo: 2 },
+            wt(2, "/c/main"),
+        ];
+        let vis = visible_flat_idx(&rows, "");
+        // Repo 1 has no worktrees (e.g. they failed to load); its header still shows.
+        assert_eq!(vis, vec![0, 1, 2, 3, 4, 5]);
+
+        let sel = flat_selectable(&rows, &vis);
+        assert_eq!(first_selectable(&sel), 1);
+        assert_eq!(last_selectable(&sel), 5);
+        assert_eq!(step_selectable(&sel, 1, true), 2);
+        assert_eq!(step_selectable(&sel, 2, true), 5, "skips both headers");
+        assert_eq!(step_selectable(&sel, 5, true), 5, "stays on the last row");
+        assert_eq!(step_selectable(&sel, 5, false), 2, "skips headers upwards");
+        assert_eq!(
+            step_selectable(&sel, 1, false),
+            1,
+            "never lands on a header"
+        );
+
+        // With a filter, headers of repos without a match are hidden.
+        let vis = visible_flat_idx(&rows, "/c/");
+        assert_eq!(vis, vec![4, 5]);
+    }
+
+    #[test]
+    fn remembered_selection_resolves_after_reorder() {
+        // spec: GW-PICK-025
+        let entry = |path: &str, branch: &str| WorktreeEntry {
+            path: path.into(),
+            branch: Some(branch.to_string()),
+            ..Default::default()
+        };
+        let repo = |hash: &str, last_branch: Option<&str>| KnownRepo {
+            hash: hash.to_string(),
+            name: hash.to_string(),
+            anchor: PathBuf::from("/x"),
+            git_common_dir: PathBuf::from("/x/.git"),
+            last_opened: None,
+            last_branch: last_branch.map(str::to_string),
+        };
+        let repos = vec![repo("a", None), repo("b", Some("feat"))];
+        let rows = |entries: Vec<WorktreeEntry>| {
+            let mut rows = vec![FlatRow::Header { repo: 0 }];
+            rows.push(FlatRow::Worktree {
+                repo: 0,
+                entry: entry("/a/main", "feat"),
+            });
+            rows.push(FlatRow::Header { repo: 1 });
+            rows.extend(
+                entries
+                    .into_iter()
+                    .map(|entry| FlatRow::Worktree { repo: 1, entry }),
+            );
+            rows
+        };
+
+        let before = rows(vec![entry("/b/main", "main"), entry("/b/feat", "feat")]);
+        let vis = visible_flat_idx(&before, "");
+        assert_eq!(flat_start(&before, &vis, &repos, "b"), Some(4));
+
+        // A worktree was added ahead of it and the remembered one moved directories.
+        let after = rows(vec![
+            entry("/b/new", "new"),
+            entry("/b/main", "main"),
+            entry("/elsewhere/feat", "feat"),
+        ]);
+        let vis = visible_flat_idx(&after, "");
+        assert_eq!(flat_start(&after, &vis, &repos, "b"), Some(5));
+        // Without a remembered branch (or once it's gone), the repo's first worktree.
+        assert_eq!(flat_start(&after, &vis, &repos, "a"), Some(1));
+        let gone = rows(vec![entry("/b/main", "main")]);
+        let vis = visible_flat_idx(&gone, "");
+        assert_eq!(flat_start(&gone, &vis, &repos, "b"), Some(3));
+        assert_eq!(flat_start(&gone, &vis, &repos, "unknown"), None);
+
+        let wts = vec![
+            entry("/b/new", "new"),
+            entry("/b/main", "main"),
+            entry("/b/feat", "feat"),
+        ];
+        let vis = visible_worktrees_idx(&wts, "");
+        assert_eq!(branch_position(&wts, &vis, Some("feat")), Some(2));
+        assert_eq!(branch_position(&wts, &vis, Some("gone")), None);
+        assert_eq!(branch_position(&wts, &vis, None), None);
+    }
+
+    #[test]
+    fn no_color_styles_have_no_colors() {
+        // spec: GW-COLOR-002
+        assert_eq!(fg(false, Color::Green).fg, None);
+        assert_eq!(fg(true, Color::Green).fg, Some(Color::Green));
+        let hl = highlight_style(false);
+        assert_eq!((hl.fg, hl.bg), (None, None));
+        assert!(hl.add_modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn refresh_keeps_selection_by_path() {
+        // spec: GW-PICK-009
+        // Before: [/a, /b, /c] with /b highlighted. Another terminal adds /0 and removes /a.
+        let after = ["/0", "/b", "/c"];
+        assert_eq!(reselect(&after, Some("/b"), 1), 1);
+        let after = ["/0", "/x", "/y", "/b"];
+        assert_eq!(reselect(&after, Some("/b"), 1), 3);
+
+        // Highlighted worktree was removed: keep the old index, clamped into range.
+        let after = ["/a", "/c"];
+        assert_eq!(reselect(&after, Some("/b"), 1), 1);
+        assert_eq!(reselect(&after, Some("/b"), 5), 1);
+        assert_eq!(reselect(&[], Some("/b"), 3), 0);
+    }
+
+    #[test]
+    fn seeded_filter_narrows_visible_repos() {
+        // spec: GW-PICK-012
+        let repo = |name: &str| KnownRepo {
+            hash: format!("h-{name}"),
+            name: name.to_string(),
+            anchor: PathBuf::from(format!("/src/{name}")),
+            git_common_dir: PathBuf::from(format!("/src/{name}/.git")),
+            last_opened: None,
+            last_branch: None,
+        };
+        let repos = vec![repo("api"), repo("docs"), repo("web-api")];
+        let mut state = AppState::new(Screen::Repo);
+        state.repo_selected = 1;
+
+        seed_repo_filter(&mut state, &repos, "api", None);
+
+        let (vis, _, _) = visible_repos(&repos, &state.repo_filter);
+        let names: Vec<&str> = vis.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["api", "web-api"]);
+        assert_eq!(state.repo_selected, 0);
+    }
+
+    #[test]
+    fn instant_filter_types_into_filter_without_slash() {
+        // spec: GW-PICK-020
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let mut state = AppState::new(Screen::Repo);
+        state.instant_filter = true;
+        state.repo_selected = 3;
+
+        assert!(handle_instant_filter(&mut state, key(KeyCode::Char('a'))));
+        assert!(handle_instant_filter(&mut state, key(KeyCode::Char('p'))));
+        assert_eq!(state.repo_filter, "ap");
+        assert_eq!(state.mode, Mode::Normal);
+        assert_eq!(state.repo_selected, 0);
+
+        assert!(handle_instant_filter(&mut state, key(KeyCode::Backspace)));
+        assert_eq!(state.repo_filter, "a");
+        // Help and ctrl chords are left to the normal key handler.
+        assert!(!handle_instant_filter(&mut state, key(KeyCode::Char('?'))));
+        assert!(!handle_instant_filter(
+            &mut state,
+            KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL)
+        ));
+        assert!(!handle_instant_filter(&mut state, key(KeyCode::Enter)));
+
+        // esc clears a non-empty filter, then falls through (quit/back).
+        assert!(handle_instant_filter(&mut state, key(KeyCode::Esc)));
+        assert_eq!(state.repo_filter, "");
+        assert!(!handle_instant_filter(&mut state, key(KeyCode::Esc)));
+
+        state.screen = Screen::Worktree;
+        assert!(handle_instant_filter(&mut state, key(KeyCode::Char('/'))));
+        assert_eq!(state.wt_filter, "/");
+    }
+
+    #[test]
+    fn compare_repos_orders_by_each_mode() {
+        // spec: GW-PICK-019
+        let repo = |name: &str, anchor: &str, last_opened: Option<u64>| KnownRepo {
+            hash: format!("h-{name}"),
+            name: name.to_string(),
+            anchor: PathBuf::from(anchor),
+            git_common_dir: PathBuf::from(format!("{anchor}/.git")),
+            last_opened,
+            last_branch: None,
+        };
+        let repos = vec![
+            repo("web", "/a/web", Some(100)),
+            repo("api", "/z/api", None),
+            repo("docs", "/m/docs", Some(300)),
+            repo("cli", "/b/cli", None),
+        ];
+        let sorted = |mode| {
+            let mut v = repos.clone();
+            v.sort_by(|a, b| compare_repos(a, b, mode));
+            v.into_iter().map(|r| r.name).collect::<Vec<_>>()
+        };
+
+        assert_eq!(sorted(SortRepos::Name), ["api", "cli", "docs", "web"]);
+        assert_eq!(sorted(SortRepos::Recency), ["docs", "web", "api", "cli"]);
+        assert_eq!(sorted(SortRepos::Path), ["web", "cli", "docs", "api"]);
+    }
+
+    #[test]
+    fn recency_sort_orders_worktrees_and_starts_on_the_latest_repo() {
+        // spec: GW-PICK-030
+        let entry = |p: &str| WorktreeEntry {
+            path: p.into(),
+            ..Default::default()
+        };
+        let entries = vec![
+            entry("/r/main"),
+            entry("/r/a"),
+            entry("/r/b"),
+            entry("/r/c"),
+        ];
+        let last_opened = HashMap::from([
+            (PathBuf::from("/r/main"), 10),
+            (PathBuf::from("/r/b"), 300),
+            (PathBuf::from("/r/c"), 200),
+        ]);
+        assert_eq!(
+            order_by_recency(&entries, vec![0, 1, 2, 3], &last_opened),
+            [0, 2, 3, 1]
+        );
+
+        let repo = |name: &str, last_opened: Option<u64>| KnownRepo {
+            hash: format!("h-{name}"),
+            name: name.to_string(),
+            anchor: PathBuf::from(format!("/{name}")),
+            git_common_dir: PathBuf::from(format!("/{name}/.git")),
+            last_opened,
+            last_branch: None,
+        };
+        let mut state = AppState::new(Screen::Worktree);
+        state.active_repo = Some(repo("r", None));
+        state.wt_entries = entries;
+        state.last_opened.insert("h-r".to_string(), last_opened);
+        assert_eq!(state.visible_wt_idx(), [0, 1, 2, 3]);
+        state.sort_repos = SortRepos::Recency;
+        assert_eq!(state.visible_wt_idx(), [0, 2, 3, 1]);
+        // A text filter ranks by match instead.
+        state.wt_filter = "r".to_string();
+        assert_eq!(state.visible_wt_idx(), [0, 1, 2, 3]);
+
+        let repos = vec![
+            repo("api", None),
+            repo("cli", Some(200)),
+            repo("web", Some(100)),
+        ];
+        assert_eq!(initial_repo_idx(&repos, None), Some(1));
+        assert_eq!(initial_repo_idx(&repos[..1], None), None);
+    }
+
+    #[test]
+    fn parses_gone_upstreams_from_branch_vv() {
+        // spec: GW-PICK-013
+        let out = "\
+* main       1a2b3c4 [origin/main] init
++ feat       5d6e7f8 [origin/feat: gone] wip
+  old-thing  9a8b7c6 [origin/old-thing: gone] done
+  ahead      0f1e2d3 [origin/ahead: ahead 2] more
+  local      4c5d6e7 subject mentions [x: gone] but has no upstream
+  (HEAD detached at 1a2b3c4) 1a2b3c4 detached
+";
+        let gone = parse_gone_branches(out);
+        let mut names: Vec<&str> = gone.iter().map(String::as_str).collect();
+        names.sort();
+        assert_eq!(names, ["feat", "old-thing"]);
+    }
+
+    #[test]
+    fn main_worktree_is_tagged_and_survives_filtering() {
+        // spec: GW-PICK-014
+        let porcelain = "\
+worktree /src/repo
+HEAD 1111111111111111111111111111111111111111
+branch refs/heads/main
+
+worktree /wt/repo/feat
+HEAD 2222222222222222222222222222222222222222
+branch refs/heads/feat
+
+worktree /wt/repo/fix
+HEAD 3333333333333333333333333333333333333333
+detached
+";
+        let entries = parse_worktree_porcelain(porcelain.as_bytes());
+        assert_eq!(entries[2].branch, None);
+        assert_eq!(
+            entries[2].head.as_deref(),
+            Some("3333333333333333333333333333333333333333")
+        );
+        assert_eq!(entries[1].short_head(), Some("2222222"));
+        assert_eq!(main_worktree_idx(&entries), Some(0));
+        assert_eq!(visible_worktrees_idx(&entries, "feat"), [0, 1]);
+
+        let bare = parse_worktree_porcelain(
+            b"worktree /src/repo.git\nbare\n\nworktree /wt/repo/feat\nHEAD 2222\nbranch refs/heads/feat\n",
+        );
+        assert!(bare[0].bare);
+        assert_eq!(bare[0].head, None);
+        assert_eq!(bare[1].short_head(), Some("2222"));
+
+        let unborn = parse_worktree_porcelain(
+            format!(
+                "worktree /src/repo\nHEAD {}\nbranch refs/heads/main\n",
+                "0".repeat(40)
+            )
+            .as_bytes(),
+        );
+        assert_eq!(unborn[0].head, None);
+        assert_eq!(main_worktree_idx(&bare), None);
+        assert_eq!(visible_worktrees_idx(&bare, "feat"), [1]);
+    }
+
+    #[test]
+    fn preview_command_substitutes_quoted_path_and_branch() {
+        // spec: GW-PICK-021
+        assert_eq!(
+            preview_command(
+                "git -C {path} log -3 {branch}",
+                "/wt/repo/feat",
+                Some("feat/x")
+            ),
+            "git -C /wt/repo/feat log -3 feat/x"
+        );
+        assert_eq!(
+            preview_command("ls {path} # {branch}", "/wt/my repo/{branch}", None),
+            "ls '/wt/my repo/{branch}' # ''"
+        );
+        assert_eq!(
+            preview_command("echo {} {pathx", "/p", None),
+            "echo {} {pathx"
+        );
+    }
+
+    #[test]
+    fn ahead_behind_parses_rev_list_and_labels_nonzero_counts() {
+        // spec: GW-PICK-022
+        assert_eq!(parse_left_right_count("1\t2\n"), Some((2, 1)));
+        assert_eq!(parse_left_right_count("fatal: no upstream\n"), None);
+        assert_eq!(ahead_behind_label(2, 1).as_deref(), Some("↑2 ↓1"));
+        assert_eq!(ahead_behind_label(0, 3).as_deref(), Some("↓3"));
+        assert_eq!(ahead_behind_label(0, 0), None);
+    }
+
+    #[test]
+    fn hotkey_pools_do_not_shadow_command_keys() {
+        assert!(!is_repo_hotkey('r'));
+        assert!(!is_worktree_hotkey('r'));
+        assert!(!is_worktree_hotkey('R'));
+        assert!(!is_worktree_hotkey('D'));
+    }
+
     #[test]
     fn dd_begins_delete_confirmation_for_highlighted_worktree() {
         // spec: GW-PICK-106
         let target = PathBuf::from("/tmp/worktree-a");
-        let mut state = AppState {
-            screen: Screen::Worktree,
-            mode: Mode::Normal,
-            status: String::new(),
-            pending_delete: None,
-            delete_in_progress: None,
-            repo_filter: String::new(),
-            repo_selected: 0,
-            repo_list_state: ListState::default(),
-            active_repo: None,
-            wt_filter: String::new(),
-            wt_selected: 0,
-            wt_list_state: ListState::default(),
-            wt_entries: vec![WorktreeEntry {
-                path: target.to_string_lossy().to_string(),
-                branch: Some("feat".to_string()),
-            }],
-            hotkey_buf: String::new(),
-            last_hotkey_at: Instant::now(),
-            pending_g: false,
-            last_g_at: Instant::now(),
-            pending_d: false,
-            last_d_at: Instant::now(),
-        };
+        let mut state = AppState::new(Screen::Worktree);
+        state.wt_entries = vec![WorktreeEntry {
+            path: target.clone(),
+            branch: Some("feat".to_string()),
+            ..Default::default()
+        }];
 
         let vis_wt_idx = vec![0];
         handle_worktree_delete_chord(&mut state, 'd', &vis_wt_idx).unwrap();
@@ -1358,38 +3471,154 @@ mod tests {
         assert_eq!(state.pending_delete.as_deref(), Some(target.as_path()));
         assert!(state.status.contains("delete"));
     }
-}
 
-fn push_hotkey(state: &mut AppState, c: char) {
-    if state.hotkey_buf.len() >= 2 {
-        state.hotkey_buf.clear();
+    #[test]
+    fn confirmed_delete_of_dirty_worktree_asks_again_to_force() {
+        // spec: GW-PICK-107
+        let td = TempDir::new().unwrap();
+        let repo = td.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        run_git(&repo, &["init"]);
+        run_git(&repo, &["config", "user.email", "gw@example.com"]);
+        run_git(&repo, &["config", "user.name", "gw"]);
+        std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+        run_git(&repo, &["add", "."]);
+        run_git(&repo, &["commit", "-m", "init"]);
+
+        let mut state = AppState::new(Screen::Worktree);
+        state.mode = Mode::ConfirmDelete;
+        assert!(!ask_force_if_dirty(&mut state, &repo));
+        assert_eq!(state.mode, Mode::ConfirmDelete);
+
+        std::fs::write(repo.join("README.md"), "changed\n").unwrap();
+        std::fs::write(repo.join("notes.txt"), "scratch\n").unwrap();
+        assert!(ask_force_if_dirty(&mut state, &repo));
+        assert_eq!(state.mode, Mode::ConfirmForceDelete);
+        assert_eq!(state.pending_delete.as_deref(), Some(repo.as_path()));
+        assert!(
+            state
+                .status
+                .contains("uncommitted changes (README.md, notes.txt)")
+        );
+        assert!(state.status.contains("force delete?"));
+        assert!(command_hint(Screen::Worktree, state.mode).contains("force delete"));
     }
-    state.hotkey_buf.push(c);
-    state.last_hotkey_at = Instant::now();
-}
 
-fn has_prefix(buf: &str, codes: &[String]) -> bool {
-    codes.iter().any(|c| c.starts_with(buf))
-}
+    #[test]
+    fn fuzzy_filter_matches_subsequences_and_ranks_them() {
+        // spec: GW-PICK-027
+        assert!(fuzzy_score("feature-baz", "fbz").is_some());
+        assert!(fuzzy_score("feature-baz", "FBZ").is_some());
+        assert!(fuzzy_score("Feature-Baz", "fbz").is_some());
+        assert!(fuzzy_score("feature-baz", "zbf").is_none(), "order matters");
+        assert!(fuzzy_score("feature-baz", "fbzz").is_none());
+        assert_eq!(fuzzy_score("anything", ""), Some(0));
+
+        // Consecutive letters and word starts beat scattered ones.
+        let score = |h: &str| fuzzy_score(h, "baz").unwrap();
+        assert!(score("feature-baz") > score("b-a-z"));
+        assert!(score("feature-baz") > score("foobaz"));
+        assert!(score("fooBaz") > score("foobaz"));
+        // The best alignment is found even when an earlier partial match exists.
+        assert_eq!(score("b-a-z baz"), score("x baz"));
+
+        let ranked = fuzzy_rank(
+            ["b-a-z", "nope", "baz", "xbaz", "b_a_z"]
+                .into_iter()
+                .map(|s| (s, s.to_string())),
+            "baz",
+        );
+        assert_eq!(ranked, ["baz", "xbaz", "b-a-z", "b_a_z"]);
 
-fn is_repo_hotkey(c: char) -> bool {
-    hotkey_pool_repos().contains(&c)
-}
+        let entry = |path: &str| WorktreeEntry {
+            path: path.into(),
+            branch: Some(path.rsplit('/').next().unwrap().to_string()),
+            ..Default::default()
+        };
+        let entries = [
+            entry("/wt/main"),
+            entry("/wt/fix-bz"),
+            entry("/wt/feature-baz"),
+            entry("/wt/other"),
+        ];
+        assert_eq!(visible_worktrees_idx(&entries, ""), [0, 1, 2, 3]);
+        assert_eq!(visible_worktrees_idx(&entries, "baz"), [0, 2]);
+        // `fix-bz` matches at word starts and ends in a run, so it outranks `feature-baz`.
+        assert_eq!(visible_worktrees_idx(&entries, "fbz"), [0, 1, 2]);
+        assert_eq!(visible_worktrees_idx(&entries, "feabaz"), [0, 2]);
+
+        // Real paths share a long prefix; a match in the branch or directory name outranks one
+        // that only hits that prefix, which still keeps the worktree visible.
+        let entries = [
+            entry("/home/dev/wt/shop/main"),
+            entry("/home/dev/wt/shop/feature"),
+            entry("/home/dev/wt/shop/wt-cleanup"),
+            entry("/home/dev/wt/shop/fix-shopping-cart"),
+        ];
+        assert_eq!(visible_worktrees_idx(&entries, "wt"), [0, 2, 1, 3]);
+        assert_eq!(visible_worktrees_idx(&entries, "shop"), [0, 3, 1, 2]);
+        assert_eq!(visible_worktrees_idx(&entries, "devfeat"), [0, 1]);
+    }
 
-fn is_worktree_hotkey(c: char) -> bool {
-    hotkey_pool_worktrees().contains(&c)
-}
+    #[test]
+    fn quit_asks_first_only_while_a_filter_is_active() {
+        // spec: GW-PICK-028
+        let mut state = AppState::new(Screen::Repo);
+        state.repo_filter = "api".to_string();
+        assert!(!quit_needs_confirmation(&state), "off by default");
+
+        state.confirm_quit = true;
+        assert!(quit_needs_confirmation(&state));
+        state.repo_filter.clear();
+        assert!(!quit_needs_confirmation(&state));
+
+        state.screen = Screen::Worktree;
+        assert!(!quit_needs_confirmation(&state));
+        state.dirty_filter = DirtyFilter::DirtyOnly;
+        assert!(quit_needs_confirmation(&state));
+        state.dirty_filter = DirtyFilter::All;
+        state.wt_filter = "feat".to_string();
+        assert!(quit_needs_confirmation(&state));
+
+        state.screen = Screen::Flat;
+        assert!(quit_needs_confirmation(&state));
+        state.wt_filter.clear();
+        assert!(!quit_needs_confirmation(&state));
+    }
 
-fn hotkey_pool_repos() -> Vec<char> {
-    vec![
-        'a', 's', 'd', 'f', 'h', 'l', 'w', 'e', 'r', 't', 'y', 'u', 'i', 'o', 'p', 'z', 'x', 'c',
-        'v', 'b', 'n', 'm',
-    ]
-}
+    #[test]
+    fn preview_pane_defaults_to_recent_commits_when_toggled_on() {
+        // spec: GW-PICK-029
+        let td = TempDir::new().unwrap();
+        let repo = td.path().join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        run_git(&repo, &["init"]);
+        run_git(&repo, &["config", "user.email", "gw@example.com"]);
+        run_git(&repo, &["config", "user.name", "gw"]);
+        std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+        run_git(&repo, &["add", "."]);
+        run_git(&repo, &["commit", "-m", "first commit"]);
+
+        let mut state = AppState::new(Screen::Worktree);
+        state.wt_entries = vec![WorktreeEntry {
+            path: repo.clone(),
+            branch: Some("main".to_string()),
+            ..Default::default()
+        }];
+        update_preview(&mut state, &[0]);
+        assert!(state.preview_job.is_none(), "pane is off by default");
+
+        state.show_preview = true;
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !state.previews.contains_key(&repo) && Instant::now() < deadline {
+            update_preview(&mut state, &[0]);
+            thread::sleep(Duration::from_millis(20));
+        }
+        let preview = state.previews[&repo].clone().unwrap_or_default();
+        assert!(preview.contains("first commit"), "{preview:?}");
 
-fn hotkey_pool_worktrees() -> Vec<char> {
-    vec![
-        'a', 's', 'f', 'h', 'l', 'w', 'e', 'r', 't', 'y', 'u', 'i', 'o', 'p', 'z', 'x', 'c', 'v',
-        'b', 'm',
-    ]
+        // Cached: the same selection doesn't run the command again.
+        update_preview(&mut state, &[0]);
+        assert!(state.preview_job.is_none());
+    }
 }
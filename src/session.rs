@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A named set of worktree paths, stored at `<config_root>/sessions/<name>.toml`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct Session {
+    #[serde(default)]
+    pub(crate) paths: Vec<String>,
+}
+
+fn sessions_dir(cfg_root: &Path) -> PathBuf {
+    cfg_root.join("sessions")
+}
+
+fn session_path(cfg_root: &Path, name: &str) -> anyhow::Result<PathBuf> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        anyhow::bail!("invalid session name: {name:?} (use letters, digits, '-', '_' or '.')");
+    }
+    Ok(sessions_dir(cfg_root).join(format!("{name}.toml")))
+}
+
+/// Save `paths` (made absolute) as session `name`, overwriting any existing one.
+pub(crate) fn save(cfg_root: &Path, name: &str, paths: &[PathBuf]) -> anyhow::Result<PathBuf> {
+    let file = session_path(cfg_root, name)?;
+    let mut session = Session::default();
+    for p in paths {
+        let abs = std::fs::canonicalize(p).map_err(|e| anyhow::anyhow!("{}: {e}", p.display()))?;
+        if !abs.is_dir() {
+            anyhow::bail!("not a directory: {}", p.display());
+        }
+        session.paths.push(abs.to_string_lossy().to_string());
+    }
+    std::fs::create_dir_all(sessions_dir(cfg_root))?;
+    std::fs::write(&file, toml::to_string_pretty(&session)?)?;
+    Ok(file)
+}
+
+pub(crate) fn load(cfg_root: &Path, name: &str) -> anyhow::Result<Session> {
+    let file = session_path(cfg_root, name)?;
+    if !file.exists() {
+        anyhow::bail!("no session named {name:?}");
+    }
+    Ok(toml::from_str(&std::fs::read_to_string(file)?)?)
+}
+
+/// Names of all saved sessions, sorted.
+pub(crate) fn list(cfg_root: &Path) -> anyhow::Result<Vec<String>> {
+    let dir = sessions_dir(cfg_root);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut names = Vec::new();
+    for ent in std::fs::read_dir(dir)? {
+        let path = ent?.path();
+        if path.extension().and_then(|e| e.to_str()) == Some("toml")
+            && let Some(stem) = path.file_stem()
+        {
+            names.push(stem.to_string_lossy().to_string());
+        }
+    }
+    names.sort();
+    Ok(names)
+}
@@ -0,0 +1,84 @@
+use serde::Serialize;
+use std::path::Path;
+use std::thread;
+
+use crate::picker::{list_known_repos, load_all_worktrees};
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub(crate) struct RepoStats {
+    pub(crate) name: String,
+    pub(crate) anchor: String,
+    pub(crate) worktrees: usize,
+    pub(crate) dirty: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub(crate) struct TotalStats {
+    pub(crate) repos: usize,
+    pub(crate) worktrees: usize,
+    pub(crate) dirty: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct Stats {
+    pub(crate) repos: Vec<RepoStats>,
+    pub(crate) total: TotalStats,
+}
+
+/// Collect per-repo worktree/dirty counts across every registered repo.
+pub(crate) fn collect(cfg_root: &Path) -> anyhow::Result<Stats> {
+    let repos = list_known_repos(cfg_root)?;
+    let loaded = load_all_worktrees(cfg_root, &repos);
+
+    let mut stats = Stats::default();
+    for (repo, res) in repos.iter().zip(loaded) {
+        let mut rs = RepoStats {
+            name: repo.name.clone(),
+            anchor: repo.anchor.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        match res {
+            Ok((entries, anchor)) => {
+                rs.anchor = anchor.to_string_lossy().to_string();
+                rs.worktrees = entries.len();
+                rs.dirty = thread::scope(|s| {
+                    let handles: Vec<_> = entries
+                        .iter()
+                        .map(|e| s.spawn(move || crate::count_uncommitted(&e.path)))
+                        .collect();
+                    handles
+                        .into_iter()
+                        .filter_map(|h| h.join().ok())
+                        .filter(|n| matches!(n, Ok(n) if *n > 0))
+                        .count()
+                });
+            }
+            Err(e) => rs.error = Some(format!("{e:#}")),
+        }
+        stats.total.repos += 1;
+        stats.total.worktrees += rs.worktrees;
+        stats.total.dirty += rs.dirty;
+        stats.repos.push(rs);
+    }
+    Ok(stats)
+}
+
+pub(crate) fn print(stats: &Stats, json: bool) -> anyhow::Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(stats)?);
+        return Ok(());
+    }
+    for r in &stats.repos {
+        match &r.error {
+            Some(e) => println!("{}\terror={}", r.name, e.trim()),
+            None => println!("{}\tworktrees={}\tdirty={}", r.name, r.worktrees, r.dirty),
+        }
+    }
+    println!(
+        "total\trepos={}\tworktrees={}\tdirty={}",
+        stats.total.repos, stats.total.worktrees, stats.total.dirty
+    );
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::WorktreeEntry;
+use crate::picker::{list_known_repos, load_all_worktrees, load_worktrees};
+
+/// Which repos `gw sync` covers.
+pub(crate) enum Scope {
+    /// The repo the current directory is in.
+    Current,
+    /// A known repo by name or config hash (`--repo`).
+    Repo(String),
+    /// Every registered repo (`--all-repos`).
+    All,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    /// Fast-forwarded from the first to the second (short) SHA.
+    Advanced(String, String),
+    UpToDate,
+    /// Has uncommitted changes; not touched.
+    Dirty,
+    /// Fetching or fast-forwarding failed (e.g. the branch diverged); holds git's error.
+    Failed(String),
+}
+
+impl Outcome {
+    fn label(&self) -> String {
+        match self {
+            Outcome::Advanced(from, to) => format!("advanced {from}..{to}"),
+            Outcome::UpToDate => "up-to-date".to_string(),
+            Outcome::Dirty => "skipped-dirty".to_string(),
+            Outcome::Failed(_) => "failed".to_string(),
+        }
+    }
+}
+
+/// `git pull --ff-only` every worktree in `scope` whose branch has an upstream, at most `jobs` at
+/// a time, printing one `<path>\t<branch>\t<outcome>` line per worktree. Fails if any worktree
+/// couldn't be fast-forwarded.
+///
+/// Worktrees share their repo's remote-tracking refs, and concurrent fetches into them fail to
+/// lock those refs. So each repo is fetched once up front and every worktree is then
+/// fast-forwarded with `git merge --ff-only @{u}`, which is what the pull would do after its fetch.
+pub(crate) fn run(cfg_root: &Path, scope: Scope, jobs: usize) -> anyhow::Result<()> {
+    let repos: Vec<Vec<WorktreeEntry>> = scope_worktrees(cfg_root, scope)?
+        .into_iter()
+        .map(|entries| {
+            entries
+                .into_iter()
+                .filter(|e| !e.bare && e.branch.is_some() && has_upstream(&e.path))
+                .collect::<Vec<_>>()
+        })
+        .filter(|entries| !entries.is_empty())
+        .collect();
+    if repos.is_empty() {
+        eprintln!("gw: no worktrees with an upstream to sync");
+        return Ok(());
+    }
+
+    let fetched = parallel_map(&repos, jobs, |entries| fetch(&entries[0].path));
+    let mut targets = Vec::new();
+    let mut outcomes = Vec::new();
+    for (entries, fetched) in repos.iter().zip(fetched) {
+        for e in entries {
+            targets.push(e);
+            outcomes.push(fetched.clone().err().map(Outcome::Failed));
+        }
+    }
+    let pending: Vec<usize> = (0..targets.len())
+        .filter(|&i| outcomes[i].is_none())
+        .collect();
+    let merged = parallel_map(&pending, jobs, |&i| fast_forward(&targets[i].path));
+    for (i, outcome) in pending.into_iter().zip(merged) {
+        outcomes[i] = Some(outcome);
+    }
+
+    let mut failed = 0;
+    for (e, outcome) in targets.iter().zip(outcomes.iter().flatten()) {
+        let path = e.display_path();
+        match outcome {
+            Outcome::Dirty => eprintln!("gw: {path} has uncommitted changes; skipping"),
+            Outcome::Failed(err) => {
+                eprintln!("gw: {path}: {err}");
+                failed += 1;
+            }
+            _ => {}
+        }
+        println!(
+            "{path}\t{}\t{}",
+            e.branch.as_deref().unwrap_or_default(),
+            outcome.label()
+        );
+    }
+    if failed > 0 {
+        anyhow::bail!("{failed} worktree(s) couldn't be fast-forwarded");
+    }
+    Ok(())
+}
+
+/// The worktrees of each repo in `scope`.
+fn scope_worktrees(cfg_root: &Path, scope: Scope) -> anyhow::Result<Vec<Vec<WorktreeEntry>>> {
+    match scope {
+        Scope::Current => {
+            let repo = crate::RepoContext::detect_from_cwd()?;
+            Ok(vec![crate::list_worktrees(&repo.toplevel)?])
+        }
+        Scope::Repo(name) => {
+            let (hash, _) = crate::find_repo_config(cfg_root, &name)?;
+            let repo = list_known_repos(cfg_root)?
+                .into_iter()
+                .find(|r| r.hash == hash)
+                .ok_or_else(|| anyhow::anyhow!("no known repo named {name:?}"))?;
+            Ok(vec![load_worktrees(cfg_root, &repo)?.0])
+        }
+        Scope::All => {
+            let repos = list_known_repos(cfg_root)?;
+            let mut out = Vec::new();
+            for (repo, res) in repos.iter().zip(load_all_worktrees(cfg_root, &repos)) {
+                match res {
+                    Ok((wts, _)) => out.push(wts),
+                    Err(e) => eprintln!("gw: skipping {}: {e:#}", repo.name),
+                }
+            }
+            Ok(out)
+        }
+    }
+}
+
+/// `f` over `items` on up to `jobs` worker threads; results are in `items` order.
+fn parallel_map<T: Sync, R: Send + Clone>(
+    items: &[T],
+    jobs: usize,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(vec![None; items.len()]);
+    thread::scope(|s| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            s.spawn(|| {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(i) else { break };
+                    let r = f(item);
+                    results.lock().unwrap()[i] = Some(r);
+                }
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.expect("sync worker panicked"))
+        .collect()
+}
+
+/// `git fetch --all` in a worktree of the repo; git's error on failure.
+fn fetch(path: &Path) -> Result<(), String> {
+    let out = Command::new("git")
+        .current_dir(path)
+        .args(["fetch", "--all", "--quiet"])
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !out.status.success() {
+        return Err(String::from_utf8_lossy(&out.stderr).trim().to_string());
+    }
+    Ok(())
+}
+
+fn fast_forward(path: &Path) -> Outcome {
+    match crate::count_uncommitted(path) {
+        Ok(0) => {}
+        Ok(_) => return Outcome::Dirty,
+        Err(e) => return Outcome::Failed(format!("{e:#}")),
+    }
+    let Some(before) = short_head(path) else {
+        return Outcome::Failed("could not read HEAD".to_string());
+    };
+    let out = match Command::new("git")
+        .current_dir(path)
+        .args(["merge", "--ff-only", "--quiet", "@{u}"])
+        .stdin(Stdio::null())
+        .output()
+    {
+        Ok(out) => out,
+        Err(e) => return Outcome::Failed(e.to_string()),
+    };
+    if !out.status.success() {
+        return Outcome::Failed(String::from_utf8_lossy(&out.stderr).trim().to_string());
+    }
+    match short_head(path) {
+        Some(after) if after != before => Outcome::Advanced(before, after),
+        Some(_) => Outcome::UpToDate,
+        None => Outcome::Failed("could not read HEAD".to_string()),
+    }
+}
+
+fn has_upstream(path: &Path) -> bool {
+    Command::new("git")
+        .current_dir(path)
+        .args(["rev-parse", "--verify", "--quiet", "@{u}"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+fn short_head(path: &Path) -> Option<String> {
+    let out = Command::new("git")
+        .current_dir(path)
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    Some(String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
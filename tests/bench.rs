@@ -0,0 +1,25 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+#[test]
+fn bench_prints_stage_timings() {
+    // spec: GW-BENCH-001
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.args(["bench", "--repos", "10"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"(?m)^list_known_repos: \d+\.\d{3}ms$").unwrap())
+        .stdout(predicate::str::contains("visible_repos: "))
+        .stdout(predicate::str::contains("assign_hotkeys: "))
+        .stderr(predicate::str::contains("10 repos"));
+}
+
+#[test]
+fn bench_is_hidden_from_help() {
+    // spec: GW-BENCH-002
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.args(["--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("bench").not());
+}
@@ -0,0 +1,55 @@
+use assert_cmd::Command;
+use std::path::Path;
+use std::process::Command as StdCommand;
+use tempfile::TempDir;
+
+fn run_git(cwd: &Path, args: &[&str]) {
+    let status = StdCommand::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .status()
+        .expect("failed to run git");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+fn assert_no_ansi(out: &[u8]) {
+    let s = String::from_utf8_lossy(out);
+    assert!(!s.contains('\x1b'), "unexpected ANSI escape in {s:?}");
+}
+
+#[test]
+fn color_never_emits_no_ansi_escapes() {
+    // spec: GW-COLOR-001
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let out = Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .args(["--color", "never", "init", "zsh"])
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    assert_no_ansi(&out.stdout);
+    assert_no_ansi(&out.stderr);
+
+    // The flag is global, so it is also accepted after the subcommand.
+    let out = Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .args(["list", "--color", "never"])
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    assert!(!out.stdout.is_empty());
+    assert_no_ansi(&out.stdout);
+    assert_no_ansi(&out.stderr);
+}
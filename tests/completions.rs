@@ -0,0 +1,20 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+#[test]
+fn completions_zsh_covers_subcommands_and_flags() {
+    // spec: GW-COMP-001
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .args(["completions", "zsh"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("#compdef gw"))
+        .stdout(predicate::str::contains("worktree-env"))
+        .stdout(predicate::str::contains("--require-clean"))
+        .stdout(predicate::str::contains("--sort-repos"))
+        .stdout(predicate::str::contains("completions"))
+        .stdout(predicate::str::contains(
+            "'::spec -- Branch name or GitHub PR URL:__gw_branches'",
+        ))
+        .stdout(predicate::str::contains("__gw_branches() {"));
+}
@@ -18,3 +18,20 @@ fn config_root_defaults_to_home_dot_config_gw() {
             td.path().join(".config").join("gw").to_string_lossy()
         )));
 }
+
+#[test]
+fn config_dir_flag_overrides_env_var() {
+    // spec: GW-CFG-004
+    let td = TempDir::new().unwrap();
+    let flag_dir = td.path().join("from-flag");
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.env("GW_CONFIG_DIR", td.path().join("from-env"))
+        .args(["--config-dir", flag_dir.to_str().unwrap(), "config"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "config_root={}\n",
+            flag_dir.to_string_lossy()
+        )));
+}
@@ -0,0 +1,142 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command as StdCommand;
+use tempfile::TempDir;
+
+fn run_git(cwd: &Path, args: &[&str]) {
+    let status = StdCommand::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .status()
+        .expect("failed to run git");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn go_falls_back_to_numbered_list_on_dumb_terminal() {
+    // spec: GW-PICK-016
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let cfg_dir = td.path().join("cfg");
+    let worktrees_dir = td.path().join("worktrees");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args([
+            "new",
+            "feat",
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+            "--no-hooks",
+        ])
+        .assert()
+        .success();
+
+    let feat = worktrees_dir.join("repo").join("feat");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(td.path())
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .env("TERM", "dumb")
+        .args(["go"])
+        .write_stdin("2\n")
+        .assert()
+        .success()
+        .stdout(format!("{}\n", feat.to_string_lossy()))
+        .stderr(predicate::str::contains("  1) repo  "))
+        .stderr(predicate::str::contains("  2) repo  feat  "));
+
+    // Out-of-range input is rejected; empty input cancels.
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(td.path())
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .env("TERM", "dumb")
+        .args(["go"])
+        .write_stdin("7\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid selection"));
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(td.path())
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .env("TERM", "dumb")
+        .args(["go"])
+        .write_stdin("\n")
+        .assert()
+        .failure()
+        .stdout("");
+}
+
+#[cfg(unix)]
+#[test]
+fn go_open_launches_editor_and_still_prints_the_path() {
+    // spec: GW-PICK-010
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let cfg_dir = td.path().join("cfg");
+    let worktrees_dir = td.path().join("worktrees");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args([
+            "new",
+            "feat",
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+            "--no-hooks",
+        ])
+        .assert()
+        .success();
+
+    let marker = td.path().join("editor-args");
+    let editor = td.path().join("stub-editor");
+    std::fs::write(
+        &editor,
+        format!(
+            "#!/bin/sh\necho editor-noise\nprintf '%s' \"$1\" > '{}'\n",
+            marker.display()
+        ),
+    )
+    .unwrap();
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(&editor, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let feat = worktrees_dir.join("repo").join("feat");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(td.path())
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .env("TERM", "dumb")
+        .env_remove("VISUAL")
+        .env("EDITOR", &editor)
+        .args(["go", "--open"])
+        .write_stdin("2\n")
+        .assert()
+        .success()
+        .stdout(format!("{}\n", feat.to_string_lossy()));
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    let mut written = String::new();
+    while written.is_empty() && std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        written = std::fs::read_to_string(&marker).unwrap_or_default();
+    }
+    assert_eq!(written, feat.to_string_lossy());
+}
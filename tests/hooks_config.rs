@@ -46,6 +46,64 @@ command = "echo hook"
         .stdout(predicate::str::contains("echo hook"));
 }
 
+#[test]
+fn hooks_run_runs_named_hook_in_current_worktree() {
+    // spec: GW-HOOKS-004, GW-HOOKS-001
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+    let wt = td.path().join("wt");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "feat", wt.to_str().unwrap()],
+    );
+
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        r#"[[hooks]]
+command = "echo \"$GW_BRANCH $GW_REPO_ROOT\" > .gw_bootstrap"
+name = "bootstrap"
+when = "pre_create"
+
+[[hooks]]
+command = "echo unnamed"
+"#,
+    )
+    .unwrap();
+    let gw = |args: &[&str]| {
+        Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+            .current_dir(&wt)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args(args)
+            .assert()
+    };
+
+    gw(&["hooks"]).success().stdout(predicate::str::contains(
+        "global: [bootstrap] echo \"$GW_BRANCH $GW_REPO_ROOT\" > .gw_bootstrap\nglobal: echo unnamed\n",
+    ));
+
+    gw(&["hooks", "run", "bootstrap"]).success();
+    assert_eq!(
+        std::fs::read_to_string(wt.join(".gw_bootstrap")).unwrap(),
+        format!("feat {}\n", repo.canonicalize().unwrap().display())
+    );
+
+    gw(&["hooks", "run", "nope"])
+        .failure()
+        .stderr(predicate::str::contains(
+            "no hook named \"nope\"; named hooks: bootstrap",
+        ));
+}
+
 #[test]
 fn config_prints_repo_config_path() {
     // spec: GW-CONFIG-001, GW-CONFIG-002, GW-CFG-001
@@ -74,6 +132,101 @@ fn config_prints_repo_config_path() {
         .stdout(predicate::str::contains("repos"));
 }
 
+#[test]
+fn config_prints_effective_worktrees_dir() {
+    // spec: GW-CONFIG-006, GW-NEW-068
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let cfg_dir = td.path().join("cfg");
+    let env_dir = td.path().join("from-env");
+    let gw = |env: Option<&Path>, args: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .env_remove("GW_WORKTREES_DIR");
+        if let Some(dir) = env {
+            cmd.env("GW_WORKTREES_DIR", dir);
+        }
+        cmd.args(args).assert()
+    };
+
+    // Nothing configured and nothing to infer from: gw new would prompt.
+    gw(None, &["config"])
+        .success()
+        .stdout(predicate::str::contains("effective_worktrees_dir").not());
+
+    let out = gw(Some(&env_dir), &["config"]).success();
+    let stdout = String::from_utf8_lossy(&out.get_output().stdout).to_string();
+    assert!(!stdout.contains("\nworktrees_dir="), "{stdout}");
+    assert!(
+        stdout.contains(&format!(
+            "effective_worktrees_dir={}\neffective_worktrees_dir_source=GW_WORKTREES_DIR\n",
+            env_dir.join("repo").display()
+        )),
+        "{stdout}"
+    );
+
+    // `config get` resolves it the same way.
+    gw(
+        Some(&env_dir),
+        &["config", "get", "--effective", "worktrees_dir"],
+    )
+    .success()
+    .stdout(format!("{}\tenv\n", env_dir.join("repo").display()));
+
+    // gw new puts the worktree there without prompting, and doesn't store the dir.
+    gw(Some(&env_dir), &["new", "feat"]).success();
+    assert!(env_dir.join("repo").join("feat").join("README.md").exists());
+
+    // Without the env var, the global default parent applies...
+    let parent = td.path().join("parent");
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        format!(
+            "default_worktrees_parent = {:?}\n",
+            parent.to_str().unwrap()
+        ),
+    )
+    .unwrap();
+    gw(None, &["config"])
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "effective_worktrees_dir={}\neffective_worktrees_dir_source=default_worktrees_parent",
+            parent.join("repo").display()
+        )));
+    gw(None, &["config", "get", "--effective", "worktrees_dir"])
+        .success()
+        .stdout(format!("{}\tglobal\n", parent.join("repo").display()));
+
+    // ...and with neither, the base is inferred from the existing worktree.
+    std::fs::remove_file(cfg_dir.join("config.toml")).unwrap();
+    gw(None, &["config"])
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "effective_worktrees_dir={}\neffective_worktrees_dir_source=inferred",
+            std::fs::canonicalize(env_dir.join("repo"))
+                .unwrap()
+                .display()
+        )));
+    gw(None, &["config", "get", "--effective", "worktrees_dir"])
+        .success()
+        .stdout(format!(
+            "{}\tinferred\n",
+            std::fs::canonicalize(env_dir.join("repo"))
+                .unwrap()
+                .display()
+        ));
+}
+
 #[test]
 fn hooks_shows_repo_hooks_when_configured() {
     // spec: GW-HOOKS-002
@@ -140,3 +293,181 @@ command = "echo repo hook"
         .success()
         .stdout(predicate::str::contains("repo: echo repo hook"));
 }
+
+#[test]
+fn doctor_hooks_warns_about_missing_binary_and_unknown_gw_var() {
+    // spec: GW-HOOKS-003
+    let td = TempDir::new().unwrap();
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        r#"[[hooks]]
+command = "FOO=1 gw-definitely-not-a-real-binary --setup"
+
+[[hooks]]
+command = "echo $GW_BRANCH ${GW_NOPE}"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(td.path())
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["config", "doctor-hooks"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "`gw-definitely-not-a-real-binary` not found on PATH",
+        ))
+        .stdout(predicate::str::contains("$GW_NOPE is not set by gw"))
+        .stdout(predicate::str::contains("GW_BRANCH is not set").not());
+
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        "[[hooks]]\ncommand = \"git status && echo $GW_WORKTREE_PATH\"\n",
+    )
+    .unwrap();
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(td.path())
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["config", "doctor-hooks"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn config_hooks_reorder_rewrites_hook_order() {
+    // spec: GW-CONFIG-004
+    let td = TempDir::new().unwrap();
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        r#"[[hooks]]
+command = "echo zero"
+
+[[hooks]]
+command = "echo one"
+continue_on_error = true
+
+[[hooks]]
+command = "echo two"
+"#,
+    )
+    .unwrap();
+
+    let gw = |args: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(td.path())
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args(args)
+            .assert()
+    };
+
+    gw(&["config", "hooks", "reorder", "--global", "--order", "2,0"])
+        .failure()
+        .stderr(predicate::str::contains("exactly once"));
+    gw(&["config", "hooks", "reorder", "--global", "--order", "2,0,1"]).success();
+    gw(&["hooks"])
+        .success()
+        .stdout("global: echo two\nglobal: echo zero\nglobal: echo one\n");
+    let cfg = std::fs::read_to_string(cfg_dir.join("config.toml")).unwrap();
+    assert!(cfg.contains("continue_on_error = true"));
+}
+
+#[test]
+fn config_get_effective_reports_source() {
+    // spec: GW-CONFIG-005
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let cfg_dir = td.path().join("cfg");
+    let gw = |args: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args(args)
+            .assert()
+    };
+
+    // Register the repo, then configure global values and override some in the repo config.
+    let worktrees_dir = td.path().join("worktrees");
+    gw(&[
+        "new",
+        "feat",
+        "--worktrees-dir",
+        worktrees_dir.to_str().unwrap(),
+        "--no-hooks",
+    ])
+    .success();
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        r#"base = "main"
+max_worktrees = 5
+
+[[hooks]]
+command = "echo global"
+"#,
+    )
+    .unwrap();
+    let repo_cfg = std::fs::read_dir(cfg_dir.join("repos"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path()
+        .join("config.toml");
+    let s = std::fs::read_to_string(&repo_cfg).unwrap();
+    std::fs::write(
+        &repo_cfg,
+        format!("base = \"develop\"\ninherit_hooks_from = \"parent\"\n{s}").replace(
+            "hooks = []",
+            r#"hooks = [{ command = "echo repo" }, { command = "echo bye", when = "pre_remove" }]"#,
+        ),
+    )
+    .unwrap();
+    // A repo whose hooks this one inherits.
+    let parent_cfg = cfg_dir.join("repos").join("parenthash");
+    std::fs::create_dir_all(&parent_cfg).unwrap();
+    std::fs::write(
+        parent_cfg.join("config.toml"),
+        "repo_name = \"parent\"\ngit_common_dir = \"/parent/.git\"\nanchor_path = \"/parent\"\n\n\
+         [[hooks]]\ncommand = \"echo inherited\"\n",
+    )
+    .unwrap();
+
+    gw(&["config", "get", "--effective", "base"])
+        .success()
+        .stdout("develop\trepo\n");
+    gw(&["config", "get", "base"]).success().stdout("develop\n");
+    gw(&["config", "get", "--effective", "max_worktrees"])
+        .success()
+        .stdout("5\tglobal\n");
+    gw(&["config", "get", "--effective", "branch_exists_action"])
+        .success()
+        .stdout("use\tdefault\n");
+    gw(&["config", "get", "--effective", "hooks"])
+        .success()
+        .stdout(
+            "post_create: echo global\tglobal\n\
+             post_create: echo inherited\trepo\n\
+             post_create: echo repo\trepo\n\
+             pre_remove: echo bye\trepo\n",
+        );
+    gw(&["config", "get", "--effective", "editor"])
+        .failure()
+        .stderr(predicate::str::contains("editor is not set"));
+    gw(&["config", "get", "nope"])
+        .failure()
+        .stderr(predicate::str::contains("unknown config key"));
+}
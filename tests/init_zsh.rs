@@ -3,7 +3,7 @@ use predicates::prelude::*;
 
 #[test]
 fn init_zsh_prints_wrapper_function() {
-    // spec: GW-INIT-001, GW-INIT-002, GW-INIT-003, GW-INIT-004
+    // spec: GW-INIT-001, GW-INIT-002, GW-INIT-003, GW-INIT-004, GW-INIT-008
     let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
     cmd.args(["init", "zsh"])
         .assert()
@@ -12,6 +12,122 @@ fn init_zsh_prints_wrapper_function() {
         .stdout(predicate::str::contains("command gw"))
         .stdout(predicate::str::contains("gw go"))
         .stdout(predicate::str::contains("gw ls"))
-        .stdout(predicate::str::contains("gw rm"))
-        .stdout(predicate::str::contains("gw new"));
+        .stdout(predicate::str::contains(
+            r#""$1" == "rm" || "$1" == "remove""#,
+        ))
+        .stdout(predicate::str::contains("gw new"))
+        .stdout(predicate::str::contains("gw switch"));
+}
+
+#[test]
+fn init_detects_shell_from_env() {
+    // spec: GW-INIT-007
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .arg("init")
+        .env("SHELL", "/usr/local/bin/zsh")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("# gw shell integration (zsh)"));
+
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .arg("init")
+        .env("SHELL", "/bin/tcsh")
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(predicate::str::contains("unsupported shell `tcsh`"))
+        .stderr(predicate::str::contains("supported: zsh, bash"));
+
+    // Without $SHELL, the parent process (here the test binary) decides.
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .arg("init")
+        .env_remove("SHELL")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("(from the parent process)"));
+}
+
+#[test]
+fn init_detects_bash() {
+    // spec: GW-INIT-009
+    let out = Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .arg("init")
+        .env("SHELL", "/bin/bash")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "# gw shell integration (bash)\n",
+        ))
+        .get_output()
+        .stdout
+        .clone();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("gw()") && out.contains("command gw"));
+
+    // The wrapper is valid bash.
+    let status = std::process::Command::new("bash")
+        .args(["-c", &format!("{out}\ntype gw >/dev/null")])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .args(["uninit", "bash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("unset -f gw"))
+        .stdout(predicate::str::contains("~/.bashrc"));
+
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .args(["completions", "bash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("complete -F _gw"));
+}
+
+#[cfg(unix)]
+#[test]
+fn wrapper_follows_gw_remove_like_gw_rm() {
+    // spec: GW-INIT-003
+    use std::os::unix::fs::PermissionsExt;
+
+    let wrapper = Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .args(["init", "bash"])
+        .output()
+        .unwrap()
+        .stdout;
+    let td = tempfile::TempDir::new().unwrap();
+    let out_dir = std::fs::canonicalize(td.path()).unwrap().join("main");
+    std::fs::create_dir_all(&out_dir).unwrap();
+    // A stand-in `gw` that, like `gw rm .`, prints where to go after removing the worktree.
+    let bin = td.path().join("bin");
+    std::fs::create_dir_all(&bin).unwrap();
+    std::fs::write(
+        bin.join("gw"),
+        format!("#!/bin/sh\necho \"$1\" >&2\necho '{}'\n", out_dir.display()),
+    )
+    .unwrap();
+    std::fs::set_permissions(bin.join("gw"), std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    for sub in ["rm", "remove"] {
+        let out = std::process::Command::new("bash")
+            .arg("-c")
+            .arg(format!("eval \"$1\"; gw {sub} . && pwd"))
+            .arg("bash")
+            .arg(String::from_utf8_lossy(&wrapper).as_ref())
+            .current_dir(td.path())
+            .env(
+                "PATH",
+                format!("{}:{}", bin.display(), std::env::var("PATH").unwrap()),
+            )
+            .output()
+            .unwrap();
+        assert!(out.status.success(), "{out:?}");
+        assert_eq!(
+            String::from_utf8_lossy(&out.stdout).trim(),
+            out_dir.to_string_lossy(),
+            "gw {sub}"
+        );
+        assert_eq!(String::from_utf8_lossy(&out.stderr).trim(), sub);
+    }
 }
@@ -41,4 +41,376 @@ fn list_shows_worktrees_in_repo() {
         .success()
         .stdout(predicate::str::contains(wt.to_string_lossy().as_ref()))
         .stdout(predicate::str::contains("feat"));
+
+    let sha = StdCommand::new("git")
+        .current_dir(&wt)
+        .args(["rev-parse", "--short=7", "HEAD"])
+        .output()
+        .unwrap()
+        .stdout;
+    let sha = String::from_utf8(sha).unwrap();
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .args(["list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "{}\tfeat\t{}\n",
+            wt.display(),
+            sha.trim()
+        )));
+}
+
+#[test]
+fn list_untracked_count_reports_dirty_and_clean_worktrees() {
+    // spec: GW-LIST-003
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let wt = td.path().join("wt");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "feat", wt.to_str().unwrap()],
+    );
+    std::fs::write(wt.join("README.md"), "changed\n").unwrap();
+    std::fs::write(wt.join("new.txt"), "new\n").unwrap();
+
+    let out = Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .args(["list", "--untracked-count"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let out = String::from_utf8(out).unwrap();
+    let line_for = |branch: &str| {
+        out.lines()
+            .find(|l| l.split('\t').nth(1) == Some(branch))
+            .unwrap_or_else(|| panic!("no line for {branch} in {out:?}"))
+            .to_string()
+    };
+    assert!(line_for("feat").ends_with("\t2"), "{out}");
+    let main_branch = out.lines().next().unwrap().split('\t').nth(1).unwrap();
+    assert!(line_for(main_branch).ends_with("\t0"), "{out}");
+}
+
+#[test]
+fn list_relative_prints_paths_under_cwd_relative() {
+    // spec: GW-LIST-004
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let inside = repo.join(".worktrees").join("feat");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "feat", inside.to_str().unwrap()],
+    );
+    let outside = td.path().join("elsewhere");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "other", outside.to_str().unwrap()],
+    );
+
+    let out = Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .args(["list", "--relative"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let out = String::from_utf8(out).unwrap();
+    let paths: Vec<&str> = out.lines().map(|l| l.split('\t').next().unwrap()).collect();
+    assert_eq!(paths[0], ".");
+    assert!(paths.contains(&".worktrees/feat"), "{out}");
+    let outside_canon = std::fs::canonicalize(&outside).unwrap();
+    assert!(
+        paths
+            .iter()
+            .any(|p| Path::new(p).is_absolute() && Path::new(p) == outside_canon),
+        "{out}"
+    );
+}
+
+#[test]
+fn list_group_by_base_sections_worktrees_by_trunk() {
+    // spec: GW-LIST-005
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init", "-b", "main"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+    run_git(&repo, &["branch", "develop"]);
+    let commit_in = |wt: &Path, msg: &str| {
+        std::fs::write(wt.join(format!("{msg}.txt")), msg).unwrap();
+        run_git(wt, &["add", "."]);
+        run_git(wt, &["commit", "-m", msg]);
+    };
+
+    // develop moves ahead of main; one feature off each trunk, plus one with no new commits.
+    let dev = td.path().join("dev");
+    run_git(
+        &repo,
+        &["worktree", "add", dev.to_str().unwrap(), "develop"],
+    );
+    commit_in(&dev, "dev-work");
+    let on_main = td.path().join("on-main");
+    run_git(
+        &repo,
+        &[
+            "worktree",
+            "add",
+            "-b",
+            "fix",
+            on_main.to_str().unwrap(),
+            "main",
+        ],
+    );
+    commit_in(&on_main, "fix");
+    let on_dev = td.path().join("on-dev");
+    run_git(
+        &repo,
+        &[
+            "worktree",
+            "add",
+            "-b",
+            "feat",
+            on_dev.to_str().unwrap(),
+            "develop",
+        ],
+    );
+    commit_in(&on_dev, "feat");
+
+    let out = Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .args(["list", "--group-by-base"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let sections: Vec<&str> = stdout.split("\n\n").collect();
+    assert_eq!(sections.len(), 2, "{stdout}");
+    assert!(sections[0].starts_with("main:\n"), "{stdout}");
+    assert!(sections[0].contains("\tmain") && sections[0].contains("\tfix"));
+    assert!(!sections[0].contains("\tfeat"));
+    assert!(sections[1].starts_with("develop:\n"), "{stdout}");
+    assert!(sections[1].contains("\tdevelop") && sections[1].contains("\tfeat"));
+}
+
+#[test]
+fn list_json_prints_entries_with_null_branch_when_detached() {
+    // spec: GW-LIST-007
+    let td = TempDir::new().unwrap();
+    let root = std::fs::canonicalize(td.path()).unwrap();
+    let repo = root.join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+    let feat = root.join("feat");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "feat", feat.to_str().unwrap()],
+    );
+    let detached = root.join("detached");
+    run_git(
+        &repo,
+        &["worktree", "add", "--detach", detached.to_str().unwrap()],
+    );
+
+    let out = Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .args(["list", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let entries: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    let entries = entries.as_array().unwrap();
+    assert_eq!(entries.len(), 3);
+    let branch_of = |p: &Path| {
+        entries
+            .iter()
+            .find(|e| e["path"] == p.to_str().unwrap())
+            .unwrap_or_else(|| panic!("missing {p:?} in {entries:?}"))["branch"]
+            .clone()
+    };
+    assert_eq!(entries[0]["path"], repo.to_str().unwrap());
+    assert_eq!(branch_of(&feat), "feat");
+    assert!(branch_of(&detached).is_null());
+    let head = StdCommand::new("git")
+        .current_dir(&repo)
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .unwrap()
+        .stdout;
+    let head = String::from_utf8(head).unwrap();
+    assert!(
+        entries.iter().all(|e| e["head"] == head.trim()),
+        "{entries:?}"
+    );
+
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .args(["list", "--json", "--table"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn list_json_shape_is_pinned_including_the_bare_entry() {
+    // spec: GW-LIST-007
+    let td = TempDir::new().unwrap();
+    let root = std::fs::canonicalize(td.path()).unwrap();
+    let src = root.join("src");
+    std::fs::create_dir_all(&src).unwrap();
+    run_git(&src, &["init"]);
+    run_git(&src, &["config", "user.email", "gw@example.com"]);
+    run_git(&src, &["config", "user.name", "gw"]);
+    run_git(&src, &["commit", "--allow-empty", "-m", "init"]);
+    let bare = root.join("repo.git");
+    run_git(
+        &root,
+        &[
+            "clone",
+            "--bare",
+            src.to_str().unwrap(),
+            bare.to_str().unwrap(),
+        ],
+    );
+    let wt = root.join("wt");
+    run_git(
+        &bare,
+        &["worktree", "add", "-b", "feat", wt.to_str().unwrap()],
+    );
+    let head = StdCommand::new("git")
+        .current_dir(&wt)
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .unwrap()
+        .stdout;
+    let head = String::from_utf8(head).unwrap();
+
+    let out = Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&wt)
+        .args(["list", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let entries: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(
+        entries,
+        serde_json::json!([
+            {"path": bare.to_str().unwrap(), "branch": null, "head": null, "bare": true},
+            {"path": wt.to_str().unwrap(), "branch": "feat", "head": head.trim()},
+        ])
+    );
+}
+
+#[test]
+fn list_branches_only_prints_branch_names_and_skips_detached() {
+    // spec: GW-LIST-008
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    run_git(&repo, &["init", "-b", "main"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+    let feat = td.path().join("feat");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "feat/x", feat.to_str().unwrap()],
+    );
+    let detached = td.path().join("detached");
+    run_git(
+        &repo,
+        &["worktree", "add", "--detach", detached.to_str().unwrap()],
+    );
+
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .args(["list", "--branches-only"])
+        .assert()
+        .success()
+        .stdout("main\nfeat/x\n");
+
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .args(["list", "--branches-only", "--json"])
+        .assert()
+        .failure();
+}
+
+#[cfg(unix)]
+#[test]
+fn list_survives_non_utf8_worktree_paths() {
+    // spec: GW-LIST-009
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let wt = td.path().join(OsStr::from_bytes(b"wt-\xff"));
+    let status = StdCommand::new("git")
+        .current_dir(&repo)
+        .args(["worktree", "add", "-b", "feat"])
+        .arg(&wt)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .args(["list", "--untracked-count"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "{}\tfeat\t",
+            td.path().join("wt-\u{FFFD}").display()
+        )))
+        // The raw path still reaches git: its status is read, not reported as `?`.
+        .stdout(predicate::str::contains("?").not());
 }
@@ -0,0 +1,97 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command as StdCommand;
+use tempfile::TempDir;
+
+fn run_git(cwd: &Path, args: &[&str]) {
+    let status = StdCommand::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .status()
+        .expect("failed to run git");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn config_migrate_hash_carries_config_to_moved_repo() {
+    // spec: GW-CONFIG-003
+    let td = TempDir::new().unwrap();
+    let old = td.path().join("old").join("repo");
+    std::fs::create_dir_all(&old).unwrap();
+
+    run_git(&old, &["init"]);
+    run_git(&old, &["config", "user.email", "gw@example.com"]);
+    run_git(&old, &["config", "user.name", "gw"]);
+    std::fs::write(old.join("README.md"), "hi\n").unwrap();
+    run_git(&old, &["add", "."]);
+    run_git(&old, &["commit", "-m", "init"]);
+
+    let cfg_dir = td.path().join("cfg");
+    let worktrees_dir = td.path().join("worktrees");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&old)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args([
+            "new",
+            "feat",
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+            "--no-hooks",
+        ])
+        .assert()
+        .success();
+
+    let old_cfg_dir = std::fs::read_dir(cfg_dir.join("repos"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let cfg_file = old_cfg_dir.join("config.toml");
+    let cfg = std::fs::read_to_string(&cfg_file)
+        .unwrap()
+        .replace("hooks = []", "hooks = [{ command = \"echo moved-hook\" }]");
+    assert!(cfg.contains("moved-hook"));
+    std::fs::write(&cfg_file, cfg).unwrap();
+
+    let new = td.path().join("new").join("repo");
+    std::fs::create_dir_all(new.parent().unwrap()).unwrap();
+    std::fs::rename(&old, &new).unwrap();
+
+    // Before migrating, the moved repo has no hooks.
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&new)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["hooks"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("moved-hook").not());
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&new)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["config", "migrate-hash", "--old", old.to_str().unwrap()])
+        .assert()
+        .success();
+
+    assert!(!old_cfg_dir.exists());
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&new)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["hooks"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("repo: echo moved-hook"));
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&new)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["config"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "worktrees_dir={}",
+            worktrees_dir.join("repo").display()
+        )));
+}
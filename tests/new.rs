@@ -303,6 +303,364 @@ fn new_accepts_github_pr_url() {
     assert_eq!(branch.trim(), "pr/7");
 }
 
+#[test]
+fn new_base_pr_merge_checks_out_merge_ref_detached() {
+    // spec: GW-NEW-036
+    let td = TempDir::new().unwrap();
+    let remote = td.path().join("remote.git");
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    run_git(td.path(), &["init", "--bare", remote.to_str().unwrap()]);
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+    run_git(
+        &repo,
+        &["remote", "add", "upstream", remote.to_str().unwrap()],
+    );
+
+    // PR 9 has a head and a (distinct) merge ref; PR 7 only a head.
+    run_git(&repo, &["push", "upstream", "HEAD:refs/pull/9/head"]);
+    run_git(&repo, &["push", "upstream", "HEAD:refs/pull/7/head"]);
+    run_git(&repo, &["commit", "--allow-empty", "-m", "merge pr 9"]);
+    run_git(&repo, &["push", "upstream", "HEAD:refs/pull/9/merge"]);
+    let merge_sha = git_out(&repo, &["rev-parse", "HEAD"]);
+    run_git(&repo, &["reset", "--hard", "HEAD~1"]);
+
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+    let gw_new = |spec: &str| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args([
+                "new",
+                spec,
+                "--worktrees-dir",
+                worktrees_dir.to_str().unwrap(),
+                "--no-hooks",
+                "--base-pr-merge",
+            ])
+            .assert()
+    };
+
+    gw_new("https://github.com/example/repo/pull/9").success();
+    let wt = worktrees_dir.join("repo").join("pr").join("9-merge");
+    assert_eq!(git_out(&wt, &["rev-parse", "HEAD"]), merge_sha);
+    assert_eq!(
+        git_out(&wt, &["rev-parse", "--abbrev-ref", "HEAD"]).trim(),
+        "HEAD"
+    );
+    assert!(!git_out(&repo, &["branch", "--list", "pr/9*"]).contains("pr/9"));
+
+    gw_new("https://github.com/example/repo/pull/7")
+        .success()
+        .stderr(predicate::str::contains("no refs/pull/7/merge"));
+    let wt = worktrees_dir.join("repo").join("pr").join("7");
+    assert_eq!(
+        git_out(&wt, &["rev-parse", "--abbrev-ref", "HEAD"]).trim(),
+        "pr/7"
+    );
+
+    gw_new("feat").failure().stderr(predicate::str::contains(
+        "--base-pr-merge needs a GitHub PR URL",
+    ));
+}
+
+#[test]
+fn new_detach_checks_out_base_without_branch() {
+    // spec: GW-NEW-037
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "existing");
+    run_git(&repo, &["commit", "--allow-empty", "-m", "second"]);
+    let first = git_out(&repo, &["rev-parse", "HEAD~1"]);
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        r#"[[hooks]]
+command = "echo \"$GW_BRANCH\" > .gw_branch"
+"#,
+    )
+    .unwrap();
+    let worktrees_dir = td.path().join("worktrees");
+
+    let gw_new = |extra: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args([
+                "new",
+                "--worktrees-dir",
+                worktrees_dir.to_str().unwrap(),
+                "--detach",
+            ])
+            .args(extra)
+            .assert()
+    };
+
+    gw_new(&["scratch", "--base", "HEAD~1"]).success();
+    let wt = worktrees_dir.join("repo").join("scratch");
+    assert_eq!(git_out(&wt, &["rev-parse", "HEAD"]), first);
+    assert_eq!(
+        git_out(&wt, &["rev-parse", "--abbrev-ref", "HEAD"]).trim(),
+        "HEAD"
+    );
+    assert!(git_out(&repo, &["branch", "--list", "scratch"]).is_empty());
+    let short = git_out(&wt, &["rev-parse", "--short", "HEAD"]);
+    assert_eq!(
+        std::fs::read_to_string(wt.join(".gw_branch")).unwrap(),
+        short
+    );
+    let repo_cfg_dir = std::fs::read_dir(cfg_dir.join("repos"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let repo_cfg = std::fs::read_to_string(repo_cfg_dir.join("config.toml")).unwrap();
+    assert!(repo_cfg.contains(&format!("anchor_path = \"{}\"", wt.display())));
+
+    // An existing branch is checked out detached at its tip...
+    gw_new(&["existing"]).success();
+    let wt = worktrees_dir.join("repo").join("existing");
+    assert_eq!(git_out(&wt, &["rev-parse", "HEAD"]), first);
+    assert_eq!(
+        git_out(&wt, &["rev-parse", "--abbrev-ref", "HEAD"]).trim(),
+        "HEAD"
+    );
+
+    // ...so also passing --base is contradictory.
+    gw_new(&[
+        "existing",
+        "--base",
+        "HEAD",
+        "--path",
+        td.path().join("x").to_str().unwrap(),
+    ])
+    .failure()
+    .stderr(predicate::str::contains("existing is an existing branch"));
+}
+
+#[test]
+fn new_force_replaces_orphaned_directory_only() {
+    // spec: GW-NEW-038
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "unused");
+    let cfg_dir = td.path().join("cfg");
+    let worktrees_dir = td.path().join("worktrees");
+    let gw_new = |branch: &str, extra: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args([
+                "new",
+                branch,
+                "--worktrees-dir",
+                worktrees_dir.to_str().unwrap(),
+                "--no-hooks",
+            ])
+            .args(extra)
+            .assert()
+    };
+
+    // A stale directory from a crashed run.
+    let stale = worktrees_dir.join("repo").join("feat");
+    std::fs::create_dir_all(&stale).unwrap();
+    std::fs::write(stale.join("junk.txt"), "old\n").unwrap();
+
+    gw_new("feat", &[])
+        .failure()
+        .stderr(predicate::str::contains("is not a registered worktree"))
+        .stderr(predicate::str::contains("--replace-leftover"));
+    assert!(stale.join("junk.txt").exists());
+    // `--force` only overrides limits; it doesn't delete anything.
+    gw_new("feat", &["--force"])
+        .failure()
+        .stderr(predicate::str::contains("is not a registered worktree"));
+    assert!(stale.join("junk.txt").exists());
+
+    gw_new("feat", &["--replace-leftover"])
+        .success()
+        .stderr(predicate::str::contains("removing leftover directory"));
+    assert!(!stale.join("junk.txt").exists());
+    assert!(stale.join("README.md").exists());
+
+    // A registered worktree (or a dir containing one) is never removed.
+    std::fs::write(stale.join("work.txt"), "keep\n").unwrap();
+    let parent = worktrees_dir.join("repo");
+    gw_new(
+        "other",
+        &["--replace-leftover", "--path", stale.to_str().unwrap()],
+    )
+    .failure()
+    .stderr(predicate::str::contains(
+        "already holds the registered worktree",
+    ));
+    gw_new(
+        "other",
+        &["--replace-leftover", "--path", parent.to_str().unwrap()],
+    )
+    .failure()
+    .stderr(predicate::str::contains(
+        "already holds the registered worktree",
+    ));
+    assert!(stale.join("work.txt").exists());
+
+    // `--force` doesn't let git check out a branch that another worktree has.
+    let dup = td.path().join("dup");
+    gw_new("feat", &["--force", "--path", dup.to_str().unwrap()])
+        .failure()
+        .stderr(predicate::str::contains("already"));
+    assert!(!dup.join("README.md").exists());
+}
+
+#[test]
+fn new_replace_leftover_never_deletes_another_repos_worktree() {
+    // spec: GW-NEW-038
+    let td = TempDir::new().unwrap();
+    let cfg_dir = td.path().join("cfg");
+    let worktrees_dir = td.path().join("wt");
+    // Two repos with the same name share `<worktrees_dir>/app/`.
+    let org1 = td.path().join("org1").join("app");
+    let org2 = td.path().join("org2").join("app");
+    init_repo_with_branch(&org1, "unused");
+    init_repo_with_branch(&org2, "unused");
+    let gw_new = |repo: &Path, extra: &[&str]| {
+        Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+            .current_dir(repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args(["new", "feat", "--no-hooks"])
+            .args(["--worktrees-dir", worktrees_dir.to_str().unwrap()])
+            .args(extra)
+            .assert()
+    };
+
+    gw_new(&org1, &[]).success();
+    let feat = worktrees_dir.join("app").join("feat");
+    std::fs::write(feat.join("wip.txt"), "uncommitted\n").unwrap();
+
+    for extra in [
+        &["--replace-leftover"][..],
+        &["--force"],
+        &["--force", "--replace-leftover"],
+    ] {
+        gw_new(&org2, extra)
+            .failure()
+            .stderr(predicate::str::contains("contains a git checkout"));
+        assert_eq!(
+            std::fs::read_to_string(feat.join("wip.txt")).unwrap(),
+            "uncommitted\n"
+        );
+    }
+    let list = git_out(&org1, &["worktree", "list"]);
+    assert!(list.contains("[feat]"), "{list}");
+}
+
+#[test]
+fn new_open_url_opens_pr_url_in_browser() {
+    // spec: GW-NEW-039
+    let td = TempDir::new().unwrap();
+    let remote = td.path().join("remote.git");
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    run_git(td.path(), &["init", "--bare", remote.to_str().unwrap()]);
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+    run_git(
+        &repo,
+        &["remote", "add", "upstream", remote.to_str().unwrap()],
+    );
+    run_git(&repo, &["push", "upstream", "HEAD:refs/pull/7/head"]);
+
+    // Stub browser that records the URL it was asked to open.
+    let opened = td.path().join("opened");
+    let browser = td.path().join("browser.sh");
+    std::fs::write(
+        &browser,
+        format!("#!/bin/sh\nprintf '%s' \"$1\" > '{}'\n", opened.display()),
+    )
+    .unwrap();
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(&browser, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let worktrees_dir = td.path().join("worktrees");
+    let pr_url = "https://github.com/example/repo/pull/7";
+    let wt = worktrees_dir.join("repo").join("pr").join("7");
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .env("GW_CONFIG_DIR", td.path().join("cfg"))
+        .env("BROWSER", &browser)
+        .args([
+            "new",
+            pr_url,
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+            "--no-hooks",
+            "--open-url",
+        ])
+        .assert()
+        .success()
+        .stdout(format!("{}\n", wt.display()))
+        .stderr(predicate::str::contains(format!("gw: opened {pr_url}")));
+    assert_eq!(std::fs::read_to_string(&opened).unwrap(), pr_url);
+
+    // A branch on a non-GitHub remote has nothing to open.
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .env("GW_CONFIG_DIR", td.path().join("cfg"))
+        .env("BROWSER", &browser)
+        .args(["new", "feat", "--no-hooks", "--open-url"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "--open-url: no PR URL or GitHub remote",
+        ));
+
+    // Among several remotes `origin` isn't special: without --track or an upstream, skip.
+    run_git(
+        &repo,
+        &[
+            "remote",
+            "add",
+            "origin",
+            "https://github.com/example/repo.git",
+        ],
+    );
+    let gw_open = |branch: &str, extra: &[&str]| {
+        Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+            .current_dir(&repo)
+            .env("GW_CONFIG_DIR", td.path().join("cfg"))
+            .env("BROWSER", &browser)
+            .args(["new", branch, "--no-hooks", "--open-url"])
+            .args(extra)
+            .assert()
+            .success()
+    };
+    std::fs::remove_file(&opened).unwrap();
+    run_git(&repo, &["branch", "plain"]);
+    gw_open("plain", &[]).stderr(predicate::str::contains("pass --track"));
+    assert!(!opened.exists());
+
+    // The branch's configured upstream remote picks one.
+    run_git(&repo, &["branch", "tracked"]);
+    run_git(&repo, &["config", "branch.tracked.remote", "origin"]);
+    gw_open("tracked", &[]);
+    assert_eq!(
+        std::fs::read_to_string(&opened).unwrap(),
+        "https://github.com/example/repo/compare/tracked?expand=1"
+    );
+}
+
 #[test]
 fn new_requires_spec_without_tty() {
     // spec: GW-NEW-002
@@ -365,6 +723,62 @@ fn new_fails_in_nontty_when_multiple_remotes_and_remote_needed() {
         .stderr(predicates::str::contains("multiple remotes"));
 }
 
+#[test]
+fn new_track_forces_remote_without_prompt() {
+    // spec: GW-NEW-013
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    // Two remotes that both have the branch.
+    for name in ["a", "b"] {
+        let remote = td.path().join(format!("{name}.git"));
+        run_git(td.path(), &["init", "--bare", remote.to_str().unwrap()]);
+        run_git(&repo, &["remote", "add", name, remote.to_str().unwrap()]);
+        run_git(&repo, &["push", name, "HEAD:refs/heads/feat-remote"]);
+    }
+
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+    let gw_new = |branch: &str, remote: &str| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args([
+                "new",
+                branch,
+                "--worktrees-dir",
+                worktrees_dir.to_str().unwrap(),
+                "--no-hooks",
+                "--track",
+                remote,
+            ])
+            .assert()
+    };
+
+    gw_new("feat-remote", "b").success();
+    let wt = worktrees_dir.join("repo").join("feat-remote");
+    let upstream = git_out(&wt, &["rev-parse", "--abbrev-ref", "@{u}"]);
+    assert_eq!(upstream.trim(), "b/feat-remote");
+
+    gw_new("missing", "a")
+        .failure()
+        .stderr(predicate::str::contains(
+            "branch missing not found on remote a",
+        ));
+    assert!(!worktrees_dir.join("repo").join("missing").exists());
+
+    gw_new("other", "nope")
+        .failure()
+        .stderr(predicate::str::contains("no remote named nope"));
+}
+
 #[test]
 fn new_rejects_mismatched_pr_url_for_github_remote() {
     // spec: GW-NEW-031
@@ -402,3 +816,1995 @@ fn new_rejects_mismatched_pr_url_for_github_remote() {
         .failure()
         .stderr(predicates::str::contains("PR URL is for"));
 }
+
+#[test]
+fn new_writes_worktree_path_to_cd_file() {
+    // spec: GW-NEW-071
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+    let cd_file = td.path().join("cd-target");
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args([
+            "new",
+            "feat",
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+            "--cd-file",
+            cd_file.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let wt = worktrees_dir.join("repo").join("feat");
+    let written = std::fs::read_to_string(&cd_file).unwrap();
+    assert_eq!(written, wt.to_string_lossy());
+
+    // No temp files should be left behind next to the target.
+    let leftovers: Vec<_> = std::fs::read_dir(td.path())
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().ends_with(".tmp"))
+        .collect();
+    assert!(leftovers.is_empty(), "unexpected temp files: {leftovers:?}");
+}
+
+#[test]
+fn new_injects_env_file_into_hooks() {
+    // spec: GW-NEW-051, GW-NEW-052
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        r#"[[hooks]]
+command = "printf '%s|%s' \"$GW_TEST_SECRET\" \"$GW_TEST_PRESET\" > .gw_env_marker"
+"#,
+    )
+    .unwrap();
+
+    let env_file = td.path().join("hooks.env");
+    std::fs::write(
+        &env_file,
+        "# local secrets\nGW_TEST_SECRET=s3cret\nGW_TEST_PRESET=from-file\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .env("GW_TEST_PRESET", "from-process")
+        .args([
+            "new",
+            "feat-env",
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+            "--env-file",
+            env_file.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let marker = worktrees_dir
+        .join("repo")
+        .join("feat-env")
+        .join(".gw_env_marker");
+    assert_eq!(
+        std::fs::read_to_string(&marker).unwrap(),
+        "s3cret|from-process"
+    );
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .env("GW_TEST_PRESET", "from-process")
+        .args([
+            "new",
+            "feat-env-override",
+            "--env-file",
+            env_file.to_str().unwrap(),
+            "--env-override",
+        ])
+        .assert()
+        .success();
+
+    let marker = worktrees_dir
+        .join("repo")
+        .join("feat-env-override")
+        .join(".gw_env_marker");
+    assert_eq!(
+        std::fs::read_to_string(&marker).unwrap(),
+        "s3cret|from-file"
+    );
+}
+
+#[test]
+fn new_infers_worktrees_dir_from_existing_sibling_worktree() {
+    // spec: GW-NEW-045
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    // A worktree created outside gw.
+    let base = td.path().join("elsewhere");
+    let existing = base.join("existing");
+    run_git(
+        &repo,
+        &[
+            "worktree",
+            "add",
+            "-b",
+            "existing",
+            existing.to_str().unwrap(),
+        ],
+    );
+
+    let cfg_dir = td.path().join("cfg");
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["new", "feat", "--infer-dir"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("inferred"))
+        .stdout(predicate::str::contains(
+            base.join("feat").to_string_lossy().as_ref(),
+        ));
+
+    assert!(base.join("feat").exists());
+
+    let out = StdCommand::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["config"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(
+        stdout.contains(&format!("worktrees_dir={}", base.to_string_lossy())),
+        "expected inferred base to be persisted, got:\n{stdout}"
+    );
+}
+
+#[test]
+fn new_resume_reruns_hooks_after_failed_hook() {
+    // spec: GW-NEW-053
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        "[[hooks]]\ncommand = \"exit 1\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args([
+            "new",
+            "feat",
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+        ])
+        .assert()
+        .failure();
+
+    let wt = worktrees_dir.join("repo").join("feat");
+    assert!(wt.is_dir());
+
+    // Without --resume the existing worktree is an error.
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["new", "feat", "--no-hooks"])
+        .assert()
+        .failure();
+
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        "[[hooks]]\ncommand = \"touch .gw_resumed\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["new", "feat", "--resume"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(wt.to_string_lossy().as_ref()))
+        .stderr(predicate::str::contains("resuming"));
+
+    assert!(wt.join(".gw_resumed").exists());
+}
+
+#[test]
+fn new_with_remote_default_base_branches_from_fetched_origin_head() {
+    // spec: GW-NEW-023
+    let td = TempDir::new().unwrap();
+    let upstream = td.path().join("upstream");
+    std::fs::create_dir_all(&upstream).unwrap();
+    run_git(&upstream, &["init", "-b", "main"]);
+    run_git(&upstream, &["config", "user.email", "gw@example.com"]);
+    run_git(&upstream, &["config", "user.name", "gw"]);
+    std::fs::write(upstream.join("README.md"), "hi\n").unwrap();
+    run_git(&upstream, &["add", "."]);
+    run_git(&upstream, &["commit", "-m", "init"]);
+
+    let repo = td.path().join("repo");
+    run_git(
+        td.path(),
+        &["clone", upstream.to_str().unwrap(), repo.to_str().unwrap()],
+    );
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+
+    // Upstream moves on after the clone; local HEAD also diverges.
+    std::fs::write(upstream.join("upstream.txt"), "new\n").unwrap();
+    run_git(&upstream, &["add", "."]);
+    run_git(&upstream, &["commit", "-m", "upstream change"]);
+    std::fs::write(repo.join("local.txt"), "local\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "local change"]);
+
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    std::fs::write(cfg_dir.join("config.toml"), "base = \"remote-default\"\n").unwrap();
+    let worktrees_dir = td.path().join("worktrees");
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args([
+            "new",
+            "feat",
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+            "--no-hooks",
+        ])
+        .assert()
+        .success();
+
+    let rev = |cwd: &Path, r: &str| {
+        let out = StdCommand::new("git")
+            .current_dir(cwd)
+            .args(["rev-parse", r])
+            .output()
+            .unwrap();
+        String::from_utf8(out.stdout).unwrap().trim().to_string()
+    };
+    let upstream_tip = rev(&upstream, "HEAD");
+    assert_eq!(rev(&repo, "feat"), upstream_tip);
+    assert_eq!(rev(&repo, "origin/main"), upstream_tip);
+}
+
+#[test]
+fn new_with_remote_default_base_uses_the_only_remote_even_if_not_origin() {
+    // spec: GW-NEW-023
+    let td = TempDir::new().unwrap();
+    let upstream = td.path().join("upstream");
+    std::fs::create_dir_all(&upstream).unwrap();
+    run_git(&upstream, &["init", "-b", "main"]);
+    run_git(&upstream, &["config", "user.email", "gw@example.com"]);
+    run_git(&upstream, &["config", "user.name", "gw"]);
+    std::fs::write(upstream.join("README.md"), "hi\n").unwrap();
+    run_git(&upstream, &["add", "."]);
+    run_git(&upstream, &["commit", "-m", "init"]);
+
+    let repo = td.path().join("repo");
+    run_git(
+        td.path(),
+        &[
+            "clone",
+            "--origin",
+            "upstream",
+            upstream.to_str().unwrap(),
+            repo.to_str().unwrap(),
+        ],
+    );
+    std::fs::write(upstream.join("upstream.txt"), "new\n").unwrap();
+    run_git(&upstream, &["add", "."]);
+    run_git(&upstream, &["commit", "-m", "upstream change"]);
+
+    let cfg_dir = td.path().join("cfg");
+    let worktrees_dir = td.path().join("worktrees");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args([
+            "new",
+            "feat",
+            "--base-remote-default",
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+            "--no-hooks",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("fetching upstream/main"));
+
+    let rev = |cwd: &Path, r: &str| {
+        let out = StdCommand::new("git")
+            .current_dir(cwd)
+            .args(["rev-parse", r])
+            .output()
+            .unwrap();
+        String::from_utf8(out.stdout).unwrap().trim().to_string()
+    };
+    assert_eq!(rev(&repo, "feat"), rev(&upstream, "HEAD"));
+}
+
+#[test]
+fn new_applies_worktree_dir_prefix_to_directory_only() {
+    // spec: GW-NEW-046
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+
+    // First run persists the repo config; then set the prefix there.
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args([
+            "new",
+            "first",
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+            "--no-hooks",
+        ])
+        .assert()
+        .success();
+    let repo_cfg = std::fs::read_dir(cfg_dir.join("repos"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path()
+        .join("config.toml");
+    let mut cfg = std::fs::read_to_string(&repo_cfg).unwrap();
+    cfg.insert_str(0, "worktree_dir_prefix = \"wt-\"\n");
+    std::fs::write(&repo_cfg, cfg).unwrap();
+
+    let expected = worktrees_dir.join("repo").join("wt-feat");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["new", "feat", "--no-hooks"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            expected.to_string_lossy().as_ref(),
+        ));
+    assert!(expected.is_dir());
+
+    let branch = StdCommand::new("git")
+        .current_dir(&expected)
+        .args(["branch", "--show-current"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8(branch.stdout).unwrap().trim(), "feat");
+
+    // The flag overrides the config prefix.
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["new", "other", "--no-hooks", "--worktree-prefix", "x-"])
+        .assert()
+        .success();
+    assert!(worktrees_dir.join("repo").join("x-other").is_dir());
+}
+
+#[test]
+fn new_refuses_past_max_worktrees_without_force() {
+    // spec: GW-NEW-047
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    std::fs::write(cfg_dir.join("config.toml"), "max_worktrees = 1\n").unwrap();
+
+    let gw_new = |branch: &str, extra: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args([
+                "new",
+                branch,
+                "--worktrees-dir",
+                worktrees_dir.to_str().unwrap(),
+                "--no-hooks",
+            ])
+            .args(extra)
+            .assert()
+    };
+
+    gw_new("one", &[]).success();
+    gw_new("two", &[])
+        .failure()
+        .stderr(predicate::str::contains("max_worktrees = 1"));
+    assert!(!worktrees_dir.join("repo").join("two").exists());
+    gw_new("two", &["--force"]).success();
+}
+
+#[test]
+fn new_rejects_dash_leading_branch_with_guidance() {
+    // spec: GW-NEW-005
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args([
+            "new",
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+            "--",
+            "-foo",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("looks like an option"))
+        .stderr(predicate::str::contains("gw new --base main -- my-branch"));
+
+    assert!(!worktrees_dir.join("repo").join("-foo").exists());
+    assert!(git_out(&repo, &["for-each-ref", "refs/heads/-foo"]).is_empty());
+}
+
+#[test]
+fn new_continue_on_error_hook_does_not_block_later_hooks() {
+    // spec: GW-NEW-054
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        r#"[[hooks]]
+command = "exit 3"
+continue_on_error = true
+
+[[hooks]]
+command = "touch .gw_after_failure"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args([
+            "new",
+            "feat",
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("hook failed: exit 3"));
+
+    assert!(
+        worktrees_dir
+            .join("repo")
+            .join("feat")
+            .join(".gw_after_failure")
+            .exists()
+    );
+}
+
+#[test]
+fn new_pre_create_hooks_run_in_repo_and_can_abort_creation() {
+    // spec: GW-NEW-061
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        r#"[[hooks]]
+command = "touch .gw_post"
+
+[[hooks]]
+command = "echo \"$GW_BRANCH $GW_WORKTREE_PATH\" > .gw_pre; test ! -e \"$GW_WORKTREE_PATH\" && test \"$GW_BRANCH\" != blocked"
+when = "pre_create"
+"#,
+    )
+    .unwrap();
+    let gw_new = |branch: &str| {
+        Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+            .current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args([
+                "new",
+                branch,
+                "--worktrees-dir",
+                worktrees_dir.to_str().unwrap(),
+            ])
+            .assert()
+    };
+
+    gw_new("feat").success();
+    let wt = worktrees_dir.join("repo").join("feat");
+    assert_eq!(
+        std::fs::read_to_string(repo.join(".gw_pre")).unwrap(),
+        format!("feat {}\n", wt.display())
+    );
+    assert!(wt.join(".gw_post").exists());
+    assert!(!wt.join(".gw_pre").exists());
+
+    gw_new("blocked")
+        .failure()
+        .stderr(predicate::str::contains("hook failed"));
+    assert!(!worktrees_dir.join("repo").join("blocked").exists());
+    assert!(git_out(&repo, &["branch", "--list", "blocked"]).is_empty());
+
+    // A leftover directory `--replace-leftover` would replace survives a pre-create hook that
+    // aborts.
+    let leftover = worktrees_dir.join("repo").join("blocked");
+    std::fs::create_dir_all(&leftover).unwrap();
+    std::fs::write(leftover.join("junk.txt"), "old\n").unwrap();
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["new", "blocked", "--replace-leftover"])
+        .args(["--worktrees-dir", worktrees_dir.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("hook failed"));
+    assert!(leftover.join("junk.txt").exists());
+}
+
+#[test]
+fn new_passes_base_to_hooks() {
+    // spec: GW-NEW-062
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+    run_git(&repo, &["branch", "dev"]);
+    run_git(&repo, &["branch", "old"]);
+
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        r#"[[hooks]]
+command = "echo \"${GW_BASE-unset}\" > .gw_base"
+"#,
+    )
+    .unwrap();
+    let base_for = |args: &[&str]| {
+        Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+            .current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .arg("new")
+            .args(args)
+            .args(["--worktrees-dir", worktrees_dir.to_str().unwrap()])
+            .assert()
+            .success();
+        let wt = worktrees_dir.join("repo").join(args[0]);
+        std::fs::read_to_string(wt.join(".gw_base")).unwrap()
+    };
+
+    assert_eq!(base_for(&["feat", "--base", "dev"]), "dev\n");
+    assert_eq!(base_for(&["plain"]), "HEAD\n");
+    assert_eq!(base_for(&["old"]), "\n");
+}
+
+#[test]
+fn new_kills_hooks_that_time_out() {
+    // spec: GW-NEW-064
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "unused");
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        r#"[[hooks]]
+command = "sleep 30"
+timeout_secs = 1
+"#,
+    )
+    .unwrap();
+
+    for extra in [&[][..], &["--json-hooks-output", "-"]] {
+        let started = std::time::Instant::now();
+        Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+            .current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args(["new", "feat", "--resume"])
+            .args(["--worktrees-dir", worktrees_dir.to_str().unwrap()])
+            .args(extra)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "hook failed: sleep 30 (timed out after 1s)",
+            ));
+        assert!(started.elapsed() < std::time::Duration::from_secs(15));
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn new_timeout_kills_processes_the_hook_started() {
+    // spec: GW-NEW-064
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "unused");
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    let pid_file = td.path().join("sleep.pid");
+    // A compound command: the shell forks `sleep` rather than exec'ing it.
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        format!(
+            "[[hooks]]\ncommand = \"sleep 37 & echo $! > '{}'; sleep 38\"\ntimeout_secs = 1\n",
+            pid_file.display()
+        ),
+    )
+    .unwrap();
+
+    let started = std::time::Instant::now();
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["new", "feat"])
+        .args(["--worktrees-dir", worktrees_dir.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("timed out after 1s"));
+    assert!(started.elapsed() < std::time::Duration::from_secs(15));
+
+    let pid = std::fs::read_to_string(&pid_file).unwrap();
+    let stat = Path::new("/proc").join(pid.trim()).join("stat");
+    // Gone, or a zombie nobody has reaped yet.
+    let alive = || {
+        std::fs::read_to_string(&stat).is_ok_and(|s| {
+            s.rsplit(')')
+                .next()
+                .is_some_and(|r| !r.trim_start().starts_with('Z'))
+        })
+    };
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while alive() && std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    assert!(!alive(), "the hook's background sleep outlived the timeout");
+}
+
+#[test]
+fn new_runs_hooks_concurrently_with_ordered_output() {
+    // spec: GW-NEW-065
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "unused");
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    // The first hook only finishes once the second has run, so this passes only in parallel.
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        r#"hook_concurrency = 2
+
+[[hooks]]
+command = "while [ ! -e .gw_second ]; do sleep 0.05; done; echo first; echo first-err >&2"
+timeout_secs = 10
+name = "wait"
+
+[[hooks]]
+command = "sleep 0.2; touch .gw_second; echo second"
+name = "touch"
+"#,
+    )
+    .unwrap();
+
+    let out = Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["new", "feat"])
+        .args(["--worktrees-dir", worktrees_dir.to_str().unwrap()])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "[hook: wait] first\n[hook: wait] first-err\n[hook: touch] second\n",
+        ))
+        .get_output()
+        .clone();
+    // Only the worktree path goes to stdout, for the shell wrapper's `cd`.
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        format!("{}\n", worktrees_dir.join("repo").join("feat").display())
+    );
+}
+
+#[test]
+fn new_sets_branch_description() {
+    // spec: GW-NEW-066
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "unused");
+    let worktrees_dir = td.path().join("worktrees");
+
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .env("GW_CONFIG_DIR", td.path().join("cfg"))
+        .args(["new", "feat", "--no-hooks"])
+        .args(["--worktrees-dir", worktrees_dir.to_str().unwrap()])
+        .args(["--set-description", "Fix the login redirect"])
+        .assert()
+        .success();
+    assert_eq!(
+        git_out(&repo, &["config", "branch.feat.description"]).trim(),
+        "Fix the login redirect"
+    );
+}
+
+#[test]
+fn new_prefixes_hook_output_and_reports_it_on_failure() {
+    // spec: GW-NEW-067
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "unused");
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    let write_cfg = |quiet: bool| {
+        std::fs::write(
+            cfg_dir.join("config.toml"),
+            format!(
+                r#"hook_quiet = {quiet}
+
+[[hooks]]
+command = "echo installing dependencies for the worktree; echo warn >&2"
+
+[[hooks]]
+command = "echo step one; echo broken >&2; exit 3"
+continue_on_error = true
+"#
+            ),
+        )
+        .unwrap();
+    };
+    let gw_new = |branch: &str| {
+        Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+            .current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args(["new", branch])
+            .args(["--worktrees-dir", worktrees_dir.to_str().unwrap()])
+            .assert()
+            .failure()
+    };
+
+    write_cfg(false);
+    let out = gw_new("feat")
+        .stdout("")
+        .stderr(predicate::str::contains(
+            "[hook: echo installing depende…] installing dependencies for the worktree\n",
+        ))
+        .stderr(predicate::str::contains(
+            "[hook: echo installing depende…] warn\n",
+        ))
+        .stderr(predicate::str::contains(
+            "hook failed: echo step one; echo broken >&2; exit 3\n--- output of echo step one; echo broken >&2; exit 3 ---\nstep one\nbroken",
+        ))
+        .get_output()
+        .clone();
+    // A failed hook's output is only in the error.
+    assert!(!String::from_utf8_lossy(&out.stderr).contains("] step one"));
+
+    write_cfg(true);
+    let out = gw_new("feat2")
+        .stdout("")
+        .stderr(predicate::str::contains("step one\nbroken"))
+        .get_output()
+        .clone();
+    assert!(!String::from_utf8_lossy(&out.stderr).contains("installing"));
+    assert!(!String::from_utf8_lossy(&out.stderr).contains("warn"));
+}
+
+#[test]
+fn new_writes_json_hook_results() {
+    // spec: GW-NEW-055
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        r#"[[hooks]]
+command = "echo first-out"
+continue_on_error = true
+
+[[hooks]]
+command = "echo second-err >&2; exit 4"
+"#,
+    )
+    .unwrap();
+
+    let results = td.path().join("hooks.json");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args([
+            "new",
+            "feat",
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+            "--json-hooks-output",
+            results.to_str().unwrap(),
+        ])
+        .assert()
+        .failure();
+
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&results).unwrap()).unwrap();
+    let hooks = json.as_array().unwrap();
+    assert_eq!(hooks.len(), 2);
+    assert_eq!(hooks[0]["command"], "echo first-out");
+    assert_eq!(hooks[0]["exit_code"], 0);
+    assert_eq!(hooks[0]["stdout"], "first-out\n");
+    assert_eq!(hooks[1]["command"], "echo second-err >&2; exit 4");
+    assert_eq!(hooks[1]["exit_code"], 4);
+    assert_eq!(hooks[1]["stderr"], "second-err\n");
+    assert!(hooks[1]["duration_ms"].is_u64());
+
+    // With `-` the JSON goes to stderr so stdout stays just the path for the shell wrapper.
+    let cfg_dir = td.path().join("cfg-dash");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        "[[hooks]]\ncommand = \"echo dash-out\"\n",
+    )
+    .unwrap();
+    let out = Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args([
+            "new",
+            "dash",
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+            "--json-hooks-output",
+            "-",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .clone();
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 1, "stdout: {stdout}");
+    assert!(stdout.trim_end().ends_with("dash"), "stdout: {stdout}");
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    let line = stderr
+        .lines()
+        .find(|l| l.starts_with('['))
+        .expect("JSON line on stderr");
+    let json: serde_json::Value = serde_json::from_str(line).unwrap();
+    assert_eq!(json[0]["stdout"], "dash-out\n");
+}
+
+#[test]
+fn new_reads_worktrees_dir_from_stdin() {
+    // spec: GW-NEW-048
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let home = td.path().join("home");
+    std::fs::create_dir_all(&home).unwrap();
+    let cfg_dir = td.path().join("cfg");
+
+    let expected = home.join("wt").join("repo").join("feat");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .env("HOME", &home)
+        .args(["new", "feat", "--worktrees-dir", "-", "--no-hooks"])
+        .write_stdin("~/wt\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            expected.to_string_lossy().as_ref(),
+        ));
+    assert!(expected.is_dir());
+}
+
+fn init_repo_with_branch(repo: &Path, branch: &str) {
+    std::fs::create_dir_all(repo).unwrap();
+    run_git(repo, &["init"]);
+    run_git(repo, &["config", "user.email", "gw@example.com"]);
+    run_git(repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(repo, &["add", "."]);
+    run_git(repo, &["commit", "-m", "init"]);
+    run_git(repo, &["branch", branch]);
+}
+
+fn gw_new_with_action(td: &Path, repo: &Path, action: &str) -> assert_cmd::assert::Assert {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(repo)
+        .env("GW_CONFIG_DIR", td.join("cfg"))
+        .args([
+            "new",
+            "feat",
+            "--worktrees-dir",
+            td.join("worktrees").to_str().unwrap(),
+            "--no-hooks",
+            "--branch-exists-action",
+            action,
+        ])
+        .assert()
+}
+
+#[test]
+fn new_branch_exists_action_use_checks_out_existing_branch() {
+    // spec: GW-NEW-024
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "feat");
+
+    let wt = td.path().join("worktrees").join("repo").join("feat");
+    gw_new_with_action(td.path(), &repo, "use")
+        .success()
+        .stdout(predicate::str::contains(wt.to_string_lossy().as_ref()));
+    let out = StdCommand::new("git")
+        .current_dir(&wt)
+        .args(["branch", "--show-current"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8(out.stdout).unwrap().trim(), "feat");
+}
+
+#[test]
+fn new_branch_exists_action_error_fails() {
+    // spec: GW-NEW-024
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "feat");
+
+    gw_new_with_action(td.path(), &repo, "error")
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+    assert!(
+        !td.path()
+            .join("worktrees")
+            .join("repo")
+            .join("feat")
+            .exists()
+    );
+}
+
+#[test]
+fn new_branch_exists_action_new_suffix_creates_next_free_branch() {
+    // spec: GW-NEW-024
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "feat");
+    run_git(&repo, &["branch", "feat-2"]);
+
+    let wt = td.path().join("worktrees").join("repo").join("feat-3");
+    gw_new_with_action(td.path(), &repo, "new-suffix")
+        .success()
+        .stdout(predicate::str::contains(wt.to_string_lossy().as_ref()));
+    let out = StdCommand::new("git")
+        .current_dir(&wt)
+        .args(["branch", "--show-current"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8(out.stdout).unwrap().trim(), "feat-3");
+}
+
+#[test]
+fn new_prefetch_fetches_remote_base_before_creating() {
+    // spec: GW-NEW-025
+    let td = TempDir::new().unwrap();
+    let upstream = td.path().join("upstream");
+    std::fs::create_dir_all(&upstream).unwrap();
+    run_git(&upstream, &["init", "-b", "main"]);
+    run_git(&upstream, &["config", "user.email", "gw@example.com"]);
+    run_git(&upstream, &["config", "user.name", "gw"]);
+    std::fs::write(upstream.join("README.md"), "hi\n").unwrap();
+    run_git(&upstream, &["add", "."]);
+    run_git(&upstream, &["commit", "-m", "init"]);
+
+    let repo = td.path().join("repo");
+    run_git(
+        td.path(),
+        &["clone", upstream.to_str().unwrap(), repo.to_str().unwrap()],
+    );
+
+    std::fs::write(upstream.join("later.txt"), "later\n").unwrap();
+    run_git(&upstream, &["add", "."]);
+    run_git(&upstream, &["commit", "-m", "later"]);
+    let tip = StdCommand::new("git")
+        .current_dir(&upstream)
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .unwrap();
+    let tip = String::from_utf8(tip.stdout).unwrap().trim().to_string();
+
+    let has_object = || {
+        StdCommand::new("git")
+            .current_dir(&repo)
+            .args(["cat-file", "-e", &tip])
+            .status()
+            .unwrap()
+            .success()
+    };
+    assert!(!has_object());
+
+    let cfg_dir = td.path().join("cfg");
+    let worktrees_dir = td.path().join("worktrees");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args([
+            "new",
+            "feat",
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+            "--no-hooks",
+            "--base",
+            "origin/main",
+            "--prefetch",
+        ])
+        .assert()
+        .success();
+
+    assert!(has_object());
+    assert!(
+        worktrees_dir
+            .join("repo")
+            .join("feat")
+            .join("later.txt")
+            .exists()
+    );
+}
+
+#[test]
+fn new_from_pr_url_writes_worktree_readme() {
+    // spec: GW-NEW-032
+    let td = TempDir::new().unwrap();
+    let remote = td.path().join("remote.git");
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(td.path(), &["init", "--bare", remote.to_str().unwrap()]);
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+    run_git(
+        &repo,
+        &["remote", "add", "upstream", remote.to_str().unwrap()],
+    );
+    run_git(&repo, &["push", "upstream", "HEAD:refs/pull/7/head"]);
+
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    std::fs::write(cfg_dir.join("config.toml"), "worktree_readme = true\n").unwrap();
+
+    let pr_url = "https://github.com/example/repo/pull/7";
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args([
+            "new",
+            pr_url,
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+            "--no-hooks",
+        ])
+        .assert()
+        .success();
+
+    let wt = worktrees_dir.join("repo").join("pr").join("7");
+    let readme = std::fs::read_to_string(wt.join(".gw-worktree.md")).unwrap();
+    assert!(readme.contains("`pr/7`"), "{readme}");
+    assert!(readme.contains(pr_url), "{readme}");
+    assert!(readme.contains("Created: "), "{readme}");
+
+    // Ignored via info/exclude, so the worktree stays clean.
+    assert_eq!(git_out(&wt, &["status", "--porcelain"]).trim(), "");
+}
+
+#[test]
+fn new_inherits_hooks_from_another_repo() {
+    // spec: GW-NEW-056, GW-NEW-057
+    let td = TempDir::new().unwrap();
+    let alpha = td.path().join("alpha");
+    let beta = td.path().join("beta");
+    init_repo_with_branch(&alpha, "main-copy");
+    init_repo_with_branch(&beta, "main-copy");
+    let cfg_dir = td.path().join("cfg");
+    let worktrees_dir = td.path().join("worktrees");
+
+    let gw_new = |repo: &Path, branch: &str, extra: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args([
+                "new",
+                branch,
+                "--worktrees-dir",
+                worktrees_dir.to_str().unwrap(),
+            ])
+            .args(extra)
+            .assert()
+    };
+
+    // Register alpha, then give it a hook.
+    gw_new(&alpha, "setup", &["--no-hooks"]).success();
+    let repos_dir = cfg_dir.join("repos");
+    let alpha_cfg = std::fs::read_dir(&repos_dir)
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path()
+        .join("config.toml");
+    let s = std::fs::read_to_string(&alpha_cfg).unwrap();
+    std::fs::write(
+        &alpha_cfg,
+        s.replace(
+            "hooks = []",
+            r#"hooks = [{ command = "touch .gw_from_alpha" }]"#,
+        ),
+    )
+    .unwrap();
+
+    gw_new(&beta, "feat", &["--inherit-hooks-from", "alpha"]).success();
+    assert!(
+        worktrees_dir
+            .join("beta")
+            .join("feat")
+            .join(".gw_from_alpha")
+            .exists()
+    );
+
+    // alpha -> beta -> alpha: the cycle is cut and alpha's hook still runs once.
+    let s = std::fs::read_to_string(&alpha_cfg).unwrap();
+    std::fs::write(&alpha_cfg, format!("inherit_hooks_from = \"beta\"\n{s}")).unwrap();
+    gw_new(&beta, "feat2", &["--inherit-hooks-from", "alpha"])
+        .success()
+        .stderr(predicate::str::contains("inherit_hooks_from cycle"));
+    assert!(
+        worktrees_dir
+            .join("beta")
+            .join("feat2")
+            .join(".gw_from_alpha")
+            .exists()
+    );
+}
+
+#[test]
+fn new_expands_env_vars_in_worktrees_dir() {
+    // spec: GW-NEW-049
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "unused");
+    let cfg_dir = td.path().join("cfg");
+    let projects = td.path().join("projects");
+
+    let gw_new = |branch: &str, extra: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .env("GW_TEST_PROJECTS", &projects)
+            .env_remove("GW_TEST_UNSET")
+            .args(["new", branch, "--no-hooks"])
+            .args(extra)
+            .assert()
+    };
+
+    gw_new("feat", &["--worktrees-dir", "$GW_TEST_PROJECTS/wt"]).success();
+    assert!(projects.join("wt").join("repo").join("feat").is_dir());
+
+    // A hand-edited stored dir is expanded too.
+    let repo_cfg = std::fs::read_dir(cfg_dir.join("repos"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path()
+        .join("config.toml");
+    let s = std::fs::read_to_string(&repo_cfg).unwrap();
+    let stored = projects
+        .join("wt")
+        .join("repo")
+        .to_string_lossy()
+        .to_string();
+    std::fs::write(&repo_cfg, s.replace(&stored, "${GW_TEST_PROJECTS}/stored")).unwrap();
+    gw_new("feat2", &[]).success();
+    assert!(projects.join("stored").join("feat2").is_dir());
+
+    gw_new("feat3", &["--worktrees-dir", "$GW_TEST_UNSET/wt"])
+        .failure()
+        .stderr(predicate::str::contains("GW_TEST_UNSET is not set"));
+}
+
+#[test]
+fn new_prefers_os_specific_worktrees_dir() {
+    // spec: GW-NEW-063
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "unused");
+    let cfg_dir = td.path().join("cfg");
+    let gw_new = |branch: &str, extra: &[&str]| {
+        Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+            .current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args(["new", branch, "--no-hooks"])
+            .args(extra)
+            .assert()
+            .success();
+    };
+
+    let generic = td.path().join("generic");
+    gw_new("feat", &["--worktrees-dir", generic.to_str().unwrap()]);
+    let repo_cfg = std::fs::read_dir(cfg_dir.join("repos"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path()
+        .join("config.toml");
+    let (this_os, other_os) = if cfg!(windows) {
+        ("worktrees_dir_windows", "worktrees_dir_unix")
+    } else {
+        ("worktrees_dir_unix", "worktrees_dir_windows")
+    };
+    let specific = td.path().join("specific");
+    let elsewhere = td.path().join("elsewhere");
+    let s = std::fs::read_to_string(&repo_cfg).unwrap();
+    let s = s.replacen(
+        "worktrees_dir = ",
+        &format!(
+            "{this_os} = {:?}\n{other_os} = {:?}\nworktrees_dir = ",
+            specific.to_str().unwrap(),
+            elsewhere.to_str().unwrap()
+        ),
+        1,
+    );
+    std::fs::write(&repo_cfg, s).unwrap();
+
+    gw_new("feat2", &[]);
+    assert!(specific.join("feat2").is_dir());
+    assert!(!generic.join("repo").join("feat2").exists());
+    assert!(!elsewhere.exists());
+
+    // An explicit dir updates the key in effect, leaving the generic one alone.
+    let moved = td.path().join("moved");
+    gw_new("feat3", &["--worktrees-dir", moved.to_str().unwrap()]);
+    let cfg: toml::Value = toml::from_str(&std::fs::read_to_string(&repo_cfg).unwrap()).unwrap();
+    assert_eq!(
+        cfg[this_os].as_str(),
+        Some(moved.join("repo").to_str().unwrap())
+    );
+    assert_eq!(
+        cfg["worktrees_dir"].as_str(),
+        Some(generic.join("repo").to_str().unwrap())
+    );
+}
+
+#[test]
+fn new_links_main_worktree_git_hooks() {
+    // spec: GW-NEW-033
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "unused");
+    // Team hooks live in an untracked dir referenced by a relative core.hooksPath, so a new
+    // worktree's effective hooks dir (<worktree>/.githooks) would be empty.
+    let hooks_dir = repo.join(".githooks");
+    std::fs::create_dir_all(&hooks_dir).unwrap();
+    std::fs::write(hooks_dir.join("pre-commit"), "#!/bin/sh\nexit 0\n").unwrap();
+    std::fs::write(hooks_dir.join("pre-push.sample"), "#!/bin/sh\n").unwrap();
+    run_git(&repo, &["config", "core.hooksPath", ".githooks"]);
+
+    let worktrees_dir = td.path().join("worktrees");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", td.path().join("cfg"))
+        .args([
+            "new",
+            "feat",
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+            "--no-hooks",
+            "--link-worktree-git-hooks",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("linked 1 git hook(s)"));
+
+    let wt_hooks = worktrees_dir.join("repo").join("feat").join(".githooks");
+    assert!(wt_hooks.join("pre-commit").is_file());
+    assert!(!wt_hooks.join("pre-push.sample").exists());
+    #[cfg(unix)]
+    assert_eq!(
+        std::fs::read_link(wt_hooks.join("pre-commit")).unwrap(),
+        std::fs::canonicalize(hooks_dir.join("pre-commit")).unwrap()
+    );
+}
+
+#[test]
+fn new_copy_from_main_ignores_anchor() {
+    // spec: GW-NEW-034, GW-NEW-035
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "unused");
+    std::fs::write(repo.join(".env"), "from-main\n").unwrap();
+    let cfg_dir = td.path().join("cfg");
+    let worktrees_dir = td.path().join("worktrees").join("repo");
+
+    let gw_new = |branch: &str, extra: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args([
+                "new",
+                branch,
+                "--worktrees-dir",
+                td.path().join("worktrees").to_str().unwrap(),
+                "--no-hooks",
+            ])
+            .args(extra)
+            .assert()
+    };
+
+    // `one` becomes the anchor; give it a different .env and enable copy_on_new.
+    gw_new("one", &[]).success();
+    std::fs::write(worktrees_dir.join("one").join(".env"), "from-anchor\n").unwrap();
+    let repo_cfg = std::fs::read_dir(cfg_dir.join("repos"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path()
+        .join("config.toml");
+    let s = std::fs::read_to_string(&repo_cfg).unwrap();
+    std::fs::write(&repo_cfg, format!("copy_on_new = [\".env\"]\n{s}")).unwrap();
+
+    gw_new("two", &[]).success();
+    assert_eq!(
+        std::fs::read_to_string(worktrees_dir.join("two").join(".env")).unwrap(),
+        "from-anchor\n"
+    );
+
+    gw_new("three", &["--copy-from-main"]).success();
+    assert_eq!(
+        std::fs::read_to_string(worktrees_dir.join("three").join(".env")).unwrap(),
+        "from-main\n"
+    );
+}
+
+#[test]
+fn new_refuses_below_min_free_disk_without_force() {
+    // spec: GW-NEW-026
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "unused");
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    // No disk has an exabyte free.
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        "min_free_disk_mb = 1000000000000\n",
+    )
+    .unwrap();
+    let worktrees_dir = td.path().join("worktrees");
+
+    let gw_new = |extra: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args([
+                "new",
+                "feat",
+                "--worktrees-dir",
+                worktrees_dir.to_str().unwrap(),
+                "--no-hooks",
+            ])
+            .args(extra)
+            .assert()
+    };
+
+    gw_new(&["--print-remaining-disk"])
+        .failure()
+        .stderr(predicate::str::is_match(r"gw: \d+ MB free on ").unwrap())
+        .stderr(predicate::str::contains("min_free_disk_mb = 1000000000000"));
+    assert!(!worktrees_dir.join("repo").join("feat").exists());
+
+    gw_new(&["--force"]).success();
+    assert!(worktrees_dir.join("repo").join("feat").is_dir());
+}
+
+#[test]
+fn new_require_clean_refuses_dirty_worktree() {
+    // spec: GW-NEW-029
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "unused");
+    let cfg_dir = td.path().join("cfg");
+    let worktrees_dir = td.path().join("worktrees");
+
+    let gw_new = |branch: &str| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args([
+                "new",
+                branch,
+                "--worktrees-dir",
+                worktrees_dir.to_str().unwrap(),
+                "--no-hooks",
+                "--require-clean",
+            ])
+            .assert()
+    };
+
+    std::fs::write(repo.join("scratch.txt"), "wip\n").unwrap();
+    gw_new("feat")
+        .failure()
+        .stderr(predicate::str::contains("1 uncommitted entries"))
+        .stderr(predicate::str::contains("git stash"));
+    assert!(!worktrees_dir.join("repo").join("feat").exists());
+
+    std::fs::remove_file(repo.join("scratch.txt")).unwrap();
+    gw_new("feat").success();
+    assert!(worktrees_dir.join("repo").join("feat").is_dir());
+}
+
+#[test]
+fn new_shallow_since_fetches_only_recent_history() {
+    // spec: GW-NEW-027
+    let td = TempDir::new().unwrap();
+    let remote = td.path().join("remote");
+    std::fs::create_dir_all(&remote).unwrap();
+    run_git(&remote, &["init", "-b", "main"]);
+    run_git(&remote, &["config", "user.email", "gw@example.com"]);
+    run_git(&remote, &["config", "user.name", "gw"]);
+    let commit = |msg: &str, date: Option<&str>| {
+        std::fs::write(remote.join("f.txt"), msg).unwrap();
+        run_git(&remote, &["add", "."]);
+        let mut cmd = StdCommand::new("git");
+        cmd.current_dir(&remote).args(["commit", "-q", "-m", msg]);
+        if let Some(date) = date {
+            cmd.env("GIT_AUTHOR_DATE", date)
+                .env("GIT_COMMITTER_DATE", date);
+        }
+        assert!(cmd.status().unwrap().success());
+    };
+    for i in 1..=5 {
+        commit(&format!("old{i}"), Some(&format!("2020-01-0{i}T00:00:00Z")));
+    }
+    // `topic` forks from old history and adds three recent commits.
+    run_git(&remote, &["checkout", "-q", "-b", "topic", "main~3"]);
+    for i in 1..=3 {
+        commit(&format!("new{i}"), None);
+    }
+    run_git(&remote, &["checkout", "-q", "main"]);
+
+    // A shallow single-branch clone that still maps every remote branch.
+    let repo = td.path().join("repo");
+    let url = format!("file://{}", remote.display());
+    run_git(
+        td.path(),
+        &[
+            "clone",
+            "-q",
+            "--depth",
+            "1",
+            "--single-branch",
+            &url,
+            "repo",
+        ],
+    );
+    run_git(
+        &repo,
+        &[
+            "config",
+            "remote.origin.fetch",
+            "+refs/heads/*:refs/remotes/origin/*",
+        ],
+    );
+
+    let worktrees_dir = td.path().join("worktrees");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .env("GW_CONFIG_DIR", td.path().join("cfg"))
+        .args([
+            "new",
+            "topic",
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+            "--no-hooks",
+            "--shallow-since",
+            "2024-01-01",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "--shallow-since makes the whole repository shallow",
+        ))
+        .stderr(predicate::str::contains("git fetch --unshallow"));
+
+    let wt = worktrees_dir.join("repo").join("topic");
+    assert_eq!(git_out(&wt, &["rev-list", "--count", "HEAD"]).trim(), "3");
+    // The shallow boundary is shared: the main checkout is shallow too.
+    assert_eq!(
+        git_out(&repo, &["rev-parse", "--is-shallow-repository"]).trim(),
+        "true"
+    );
+}
+
+#[test]
+fn new_show_relation_reports_commits_ahead_of_trunk() {
+    // spec: GW-NEW-028
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    run_git(&repo, &["init", "-b", "main"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+    run_git(&repo, &["checkout", "-q", "-b", "side"]);
+    std::fs::write(repo.join("side.txt"), "side\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "side"]);
+    run_git(&repo, &["checkout", "-q", "main"]);
+
+    let gw_new = |branch: &str, extra: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", td.path().join("cfg"))
+            .args([
+                "new",
+                branch,
+                "--worktrees-dir",
+                td.path().join("worktrees").to_str().unwrap(),
+                "--no-hooks",
+            ])
+            .args(extra)
+            .assert()
+    };
+
+    gw_new("feat", &["--show-relation"])
+        .success()
+        .stderr(predicate::str::contains(
+            "feat is 0 commits ahead of main (0 behind)",
+        ))
+        .stderr(predicate::str::contains("warning").not());
+    gw_new("off-side", &["--base", "side", "--show-relation=main"])
+        .success()
+        .stderr(predicate::str::contains(
+            "off-side is 1 commits ahead of main (0 behind)",
+        ))
+        .stderr(predicate::str::contains(
+            "warning: off-side has commits not on main",
+        ));
+}
+
+#[test]
+fn new_keep_cwd_leaves_anchor_and_recency_untouched() {
+    // spec: GW-NEW-058
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "unused");
+    let cfg_dir = td.path().join("cfg");
+    let worktrees_dir = td.path().join("worktrees");
+
+    let gw_new = |branch: &str, extra: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args([
+                "new",
+                branch,
+                "--worktrees-dir",
+                worktrees_dir.to_str().unwrap(),
+                "--no-hooks",
+            ])
+            .args(extra)
+            .assert()
+    };
+
+    gw_new("one", &[]).success();
+    let repo_cfg = std::fs::read_dir(cfg_dir.join("repos"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path()
+        .join("config.toml");
+    let s = std::fs::read_to_string(&repo_cfg).unwrap();
+    std::fs::write(&repo_cfg, format!("last_opened = 1700000000\n{s}")).unwrap();
+    let before = std::fs::read_to_string(&repo_cfg).unwrap();
+
+    let two = worktrees_dir.join("repo").join("two");
+    gw_new("two", &["--keep-cwd"])
+        .success()
+        .stdout(predicate::str::contains(two.to_string_lossy().to_string()));
+    assert!(two.exists());
+
+    let after = std::fs::read_to_string(&repo_cfg).unwrap();
+    let one = worktrees_dir.join("repo").join("one");
+    assert!(
+        after.contains(&format!("anchor_path = \"{}\"", one.display())),
+        "{after}"
+    );
+    assert!(after.contains("last_opened = 1700000000"), "{after}");
+    assert_eq!(
+        toml::from_str::<toml::Value>(&before).unwrap(),
+        toml::from_str::<toml::Value>(&after).unwrap()
+    );
+}
+
+#[test]
+fn new_warns_when_another_repo_uses_the_same_worktrees_dir() {
+    // spec: GW-NEW-059
+    let td = TempDir::new().unwrap();
+    let cfg_dir = td.path().join("cfg");
+    let base = td.path().join("shared");
+
+    let gw_new_inferred = |name: &str| {
+        let repo = td.path().join(name);
+        init_repo_with_branch(&repo, "unused");
+        // A worktree created outside gw directly in the shared base, so `--infer-dir` picks it.
+        let existing = base.join(format!("{name}-existing"));
+        run_git(&repo, &["worktree", "add", existing.to_str().unwrap()]);
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args(["new", &format!("{name}-feat"), "--infer-dir", "--no-hooks"])
+            .assert()
+            .success()
+    };
+
+    gw_new_inferred("one").stderr(predicate::str::contains("also used by").not());
+    gw_new_inferred("two")
+        .stderr(predicate::str::contains(format!(
+            "worktrees dir {} is also used by one",
+            base.display()
+        )))
+        .stderr(predicate::str::contains("--worktrees-dir"));
+}
+
+#[test]
+fn new_remote_branch_uses_guess_remote_when_directory_matches() {
+    // spec: GW-NEW-014
+    let td = TempDir::new().unwrap();
+    let remote = td.path().join("remote.git");
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "unused");
+    run_git(td.path(), &["init", "--bare", remote.to_str().unwrap()]);
+    run_git(
+        &repo,
+        &["remote", "add", "origin", remote.to_str().unwrap()],
+    );
+    run_git(&repo, &["push", "origin", "HEAD:refs/heads/feat"]);
+    run_git(&repo, &["push", "origin", "HEAD:refs/heads/team/fix"]);
+
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+    let gw_new = |branch: &str| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args([
+                "new",
+                branch,
+                "--worktrees-dir",
+                worktrees_dir.to_str().unwrap(),
+                "--no-hooks",
+            ])
+            .assert()
+            .success()
+    };
+
+    gw_new("feat").stderr(predicate::str::contains("--guess-remote"));
+    let wt = worktrees_dir.join("repo").join("feat");
+    assert_eq!(git_out(&wt, &["branch", "--show-current"]).trim(), "feat");
+    assert_eq!(
+        git_out(&wt, &["rev-parse", "--abbrev-ref", "@{u}"]).trim(),
+        "origin/feat"
+    );
+
+    // The directory is `fix`, not `team/fix`, so git can't guess it: track it by hand.
+    gw_new("team/fix").stderr(predicate::str::contains("--guess-remote").not());
+    let wt = worktrees_dir.join("repo").join("team").join("fix");
+    assert_eq!(
+        git_out(&wt, &["branch", "--show-current"]).trim(),
+        "team/fix"
+    );
+    assert_eq!(
+        git_out(&wt, &["rev-parse", "--abbrev-ref", "@{u}"]).trim(),
+        "origin/team/fix"
+    );
+}
+
+#[test]
+fn new_set_main_branch_persists_base_for_later_branches() {
+    // spec: GW-NEW-015
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    init_repo_with_branch(&repo, "develop");
+    // `develop` moves ahead of HEAD.
+    run_git(&repo, &["checkout", "develop"]);
+    std::fs::write(repo.join("dev.txt"), "dev\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "dev"]);
+    run_git(&repo, &["checkout", "-"]);
+    let develop = git_out(&repo, &["rev-parse", "develop"]);
+
+    let cfg_dir = td.path().join("cfg");
+    let worktrees_dir = td.path().join("worktrees");
+    let gw_new = |args: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args(["new", "--worktrees-dir", worktrees_dir.to_str().unwrap()])
+            .args(args)
+            .assert()
+    };
+
+    gw_new(&["feat", "--set-main-branch", "nope"])
+        .failure()
+        .stderr(predicate::str::contains("not a branch or commit"));
+
+    gw_new(&["feat", "--set-main-branch", "develop"]).success();
+    let feat = worktrees_dir.join("repo").join("feat");
+    assert_eq!(git_out(&feat, &["rev-parse", "HEAD"]), develop);
+
+    gw_new(&["later"]).success();
+    let later = worktrees_dir.join("repo").join("later");
+    assert_eq!(git_out(&later, &["rev-parse", "HEAD"]), develop);
+}
+
+#[test]
+fn new_branch_from_current_uses_current_worktree_branch() {
+    // spec: GW-NEW-069
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let feat_a = td.path().join("feat-a");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "feat-a", feat_a.to_str().unwrap()],
+    );
+    std::fs::write(feat_a.join("a.txt"), "a\n").unwrap();
+    run_git(&feat_a, &["add", "."]);
+    run_git(&feat_a, &["commit", "-m", "a"]);
+
+    let worktrees_dir = td.path().join("worktrees");
+    let gw_new = |cwd: &Path, args: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(cwd)
+            .env("GW_CONFIG_DIR", td.path().join("cfg"))
+            .arg("new")
+            .args(args)
+            .args(["--worktrees-dir", worktrees_dir.to_str().unwrap()])
+            .assert()
+    };
+
+    let out = gw_new(&feat_a, &["feat-b", "--branch-from-current"]).success();
+    let wt = String::from_utf8_lossy(&out.get_output().stdout)
+        .trim()
+        .to_string();
+    let tip_a = git_out(&repo, &["rev-parse", "feat-a"]);
+    assert_eq!(
+        git_out(&repo, &["merge-base", "feat-a", "feat-b"]),
+        tip_a,
+        "feat-b should start at feat-a's tip"
+    );
+    assert!(Path::new(&wt).join("a.txt").exists());
+
+    gw_new(
+        &feat_a,
+        &["feat-c", "--branch-from-current", "--base", "HEAD"],
+    )
+    .failure();
+
+    run_git(&feat_a, &["checkout", "--detach"]);
+    gw_new(&feat_a, &["feat-d", "--branch-from-current"])
+        .failure()
+        .stderr(predicate::str::contains("detached HEAD"));
+    assert!(git_out(&repo, &["branch", "--list", "feat-d"]).is_empty());
+}
+
+#[test]
+fn concurrent_new_runs_take_turns_on_the_repo_lock() {
+    // spec: GW-NEW-072
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let worktrees_dir = td.path().join("worktrees");
+    let cfg_dir = td.path().join("cfg");
+    let gw_new = |branch: &str| {
+        let mut cmd = StdCommand::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args(["new", branch, "--keep-cwd"])
+            .args(["--worktrees-dir", worktrees_dir.to_str().unwrap()])
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped());
+        cmd
+    };
+    assert!(gw_new("first").status().unwrap().success());
+
+    // Hold the repo's lock: both runs wait for it, then create their worktrees one at a time.
+    let hash_dir = std::fs::read_dir(cfg_dir.join("repos"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let lock = std::fs::File::create(hash_dir.join("lock")).unwrap();
+    fs2::FileExt::lock_exclusive(&lock).unwrap();
+
+    let a = gw_new("feat-a").spawn().unwrap();
+    let b = gw_new("feat-b").spawn().unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    let base = worktrees_dir.join("repo");
+    assert!(!base.join("feat-a").exists() && !base.join("feat-b").exists());
+    drop(lock);
+
+    for (out, branch) in [
+        (a.wait_with_output().unwrap(), "feat-a"),
+        (b.wait_with_output().unwrap(), "feat-b"),
+    ] {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        assert!(out.status.success(), "{branch}: {stderr}");
+        assert!(stderr.contains("waiting for another gw run"), "{stderr}");
+        assert!(base.join(branch).join("README.md").exists());
+        assert_eq!(
+            git_out(&base.join(branch), &["branch", "--show-current"]).trim(),
+            branch
+        );
+    }
+    let listed = git_out(&repo, &["worktree", "list", "--porcelain"]);
+    assert_eq!(listed.matches("worktree ").count(), 4, "{listed}");
+}
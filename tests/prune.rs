@@ -0,0 +1,97 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command as StdCommand;
+use tempfile::TempDir;
+
+fn run_git(cwd: &Path, args: &[&str]) {
+    let status = StdCommand::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .status()
+        .expect("failed to run git");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn prune_forgets_deleted_worktrees_and_repairs_anchor() {
+    // spec: GW-PRUNE-001, GW-PRUNE-002
+    let td = TempDir::new().unwrap();
+    let root = std::fs::canonicalize(td.path()).unwrap();
+    let repo = root.join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+    let cfg_dir = root.join("cfg");
+
+    let gw = || {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo).env("GW_CONFIG_DIR", &cfg_dir);
+        cmd
+    };
+    for branch in ["keep", "gone"] {
+        gw().args([
+            "new",
+            branch,
+            "--worktrees-dir",
+            root.join("worktrees").to_str().unwrap(),
+            "--no-hooks",
+        ])
+        .assert()
+        .success();
+    }
+    // `gone` is the anchor now; delete it behind git's back.
+    let gone = root.join("worktrees").join("repo").join("gone");
+    std::fs::remove_dir_all(&gone).unwrap();
+
+    gw().arg("prune")
+        .assert()
+        .success()
+        .stdout(format!("{}\n", gone.display()));
+
+    let list = StdCommand::new("git")
+        .current_dir(&repo)
+        .args(["worktree", "list"])
+        .output()
+        .unwrap();
+    let list = String::from_utf8(list.stdout).unwrap();
+    assert!(!list.contains("gone"), "{list}");
+    assert!(list.contains("keep"), "{list}");
+
+    let repo_cfg = std::fs::read_dir(cfg_dir.join("repos"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path()
+        .join("config.toml");
+    let cfg = std::fs::read_to_string(repo_cfg).unwrap();
+    assert!(
+        cfg.contains(&format!("anchor_path = \"{}\"", repo.display())),
+        "{cfg}"
+    );
+
+    gw().arg("prune")
+        .assert()
+        .success()
+        .stdout("")
+        .stderr(predicate::str::contains("nothing to prune"));
+}
+
+#[test]
+fn prune_outside_a_repo_fails_clearly() {
+    // spec: GW-PRUNE-003
+    let td = TempDir::new().unwrap();
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(td.path())
+        .env("GW_CONFIG_DIR", td.path().join("cfg"))
+        .env("GIT_CEILING_DIRECTORIES", td.path())
+        .arg("prune")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("inside a git repository"));
+}
@@ -0,0 +1,101 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command as StdCommand;
+use tempfile::TempDir;
+
+fn run_git(cwd: &Path, args: &[&str]) {
+    let status = StdCommand::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .status()
+        .expect("failed to run git");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+fn branches(repo: &Path) -> Vec<String> {
+    let out = StdCommand::new("git")
+        .current_dir(repo)
+        .args(["branch", "--format=%(refname:short)"])
+        .output()
+        .unwrap();
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn prune_branches_deletes_only_merged_branches_without_a_worktree() {
+    // spec: GW-PRUNEBR-001, GW-PRUNEBR-002
+    let td = TempDir::new().unwrap();
+    let root = std::fs::canonicalize(td.path()).unwrap();
+    let repo = root.join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    run_git(&repo, &["init", "-b", "main"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+    // `merged` is fully merged and has no worktree; `ahead` has its own commit.
+    run_git(&repo, &["branch", "merged"]);
+    run_git(&repo, &["checkout", "-q", "-b", "ahead"]);
+    std::fs::write(repo.join("ahead.txt"), "x\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "ahead"]);
+    run_git(&repo, &["checkout", "-q", "main"]);
+    let cfg_dir = root.join("cfg");
+
+    let gw = || {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo).env("GW_CONFIG_DIR", &cfg_dir);
+        cmd
+    };
+    // Merged too, but checked out in a worktree.
+    gw().args([
+        "new",
+        "busy",
+        "--worktrees-dir",
+        root.join("worktrees").to_str().unwrap(),
+        "--no-hooks",
+    ])
+    .assert()
+    .success();
+
+    // Without a TTY or --yes it only offers them.
+    gw().arg("prune-branches")
+        .assert()
+        .failure()
+        .stdout("merged\n")
+        .stderr(predicate::str::contains("--yes"));
+    assert_eq!(branches(&repo), ["ahead", "busy", "main", "merged"]);
+
+    gw().args(["prune-branches", "--yes"])
+        .assert()
+        .success()
+        .stdout("merged\n")
+        .stderr(predicate::str::contains("deleted branch merged"));
+    assert_eq!(branches(&repo), ["ahead", "busy", "main"]);
+
+    gw().args(["prune-branches", "--yes"])
+        .assert()
+        .success()
+        .stdout("")
+        .stderr(predicate::str::contains("no merged branches"));
+}
+
+#[test]
+fn prune_branches_requires_a_git_repo() {
+    // spec: GW-PRUNEBR-003
+    let td = TempDir::new().unwrap();
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(td.path())
+        .env("GW_CONFIG_DIR", td.path().join("cfg"))
+        .arg("prune-branches")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "must be run inside a git repository",
+        ));
+}
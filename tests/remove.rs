@@ -169,17 +169,20 @@ fn remove_dirty_worktree_fails_without_force_in_nontty() {
         &["worktree", "add", "-b", "feat", wt.to_str().unwrap()],
     );
     std::fs::write(wt.join("README.md"), "changed\n").unwrap();
+    std::fs::write(wt.join("notes.txt"), "scratch\n").unwrap();
 
     let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
     cmd.current_dir(&repo)
         .args(["rm", wt.to_str().unwrap(), "--yes"])
         .assert()
         .failure()
-        .stderr(predicate::str::contains(
-            "contains modified or untracked files",
-        ));
+        .stderr(predicate::str::contains("has uncommitted changes"))
+        .stderr(predicate::str::contains("re-run with --force"))
+        .stderr(predicate::str::contains("  README.md"))
+        .stderr(predicate::str::contains("  notes.txt"));
 
     assert!(wt.exists());
+    assert!(wt.join("notes.txt").exists());
 }
 
 #[test]
@@ -210,3 +213,364 @@ fn remove_requires_yes_without_tty() {
         .failure()
         .stderr(predicate::str::contains("TTY").or(predicate::str::contains("--yes")));
 }
+
+#[test]
+fn remove_dry_run_prints_plan_and_keeps_worktree() {
+    // spec: GW-RM-009, GW-RM-010
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let wt = td.path().join("wt");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "feat", wt.to_str().unwrap()],
+    );
+    std::fs::write(wt.join("wip.txt"), "wip\n").unwrap();
+
+    let canonical = std::fs::canonicalize(&wt).unwrap();
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.current_dir(&repo)
+        .args(["remove", wt.to_str().unwrap(), "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains(format!(
+            "would run: git worktree remove {}",
+            canonical.to_string_lossy()
+        )))
+        .stderr(predicate::str::contains("branch feat would be kept"))
+        .stderr(predicate::str::contains("dirty"));
+
+    assert!(wt.exists());
+    let list = git_stdout(&repo, &["worktree", "list"]);
+    assert!(list.contains(wt.to_string_lossy().as_ref()));
+}
+
+fn repo_config_file(cfg_dir: &Path) -> std::path::PathBuf {
+    let repos = cfg_dir.join("repos");
+    let entry = std::fs::read_dir(&repos)
+        .unwrap()
+        .next()
+        .expect("no repo config written")
+        .unwrap();
+    entry.path().join("config.toml")
+}
+
+#[test]
+fn remove_prunes_worktree_metadata_from_config() {
+    // spec: GW-RM-011
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let cfg_dir = td.path().join("cfg");
+    let worktrees_dir = td.path().join("worktrees");
+    let gw_new = |branch: &str| {
+        let out = Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+            .current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args([
+                "new",
+                branch,
+                "--worktrees-dir",
+                worktrees_dir.to_str().unwrap(),
+                "--no-hooks",
+            ])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        String::from_utf8(out).unwrap().trim().to_string()
+    };
+    let wt_a = gw_new("feat-a");
+    let wt_b = gw_new("feat-b");
+
+    let cfg_file = repo_config_file(&cfg_dir);
+    let mut cfg = std::fs::read_to_string(&cfg_file).unwrap();
+    cfg.push_str(&format!(
+        "\n[worktrees.\"{wt_a}\"]\nlabel = \"label-a\"\n\n[worktrees.\"{wt_b}\"]\nlabel = \"label-b\"\n"
+    ));
+    std::fs::write(&cfg_file, cfg).unwrap();
+
+    let gw_rm = |wt: &str, extra: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args(["rm", wt, "--yes"])
+            .args(extra)
+            .assert()
+            .success();
+    };
+
+    // feat-b is the anchor (last created); removing it prunes its label and moves the anchor.
+    gw_rm(&wt_b, &[]);
+    let cfg = std::fs::read_to_string(&cfg_file).unwrap();
+    assert!(!cfg.contains("label-b"), "{cfg}");
+    assert!(!cfg.contains(&wt_b), "{cfg}");
+    assert!(cfg.contains("label-a"), "{cfg}");
+
+    gw_rm(&wt_a, &["--keep-config"]);
+    let cfg = std::fs::read_to_string(&cfg_file).unwrap();
+    assert!(cfg.contains("label-a"), "{cfg}");
+}
+
+#[test]
+fn remove_accepts_branch_name() {
+    // spec: GW-RM-012
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let wt = td.path().join("wt");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "feat/login", wt.to_str().unwrap()],
+    );
+    run_git(&repo, &["branch", "no-worktree"]);
+
+    let gw_rm = |target: &str| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", td.path().join("cfg"))
+            .args(["rm", target, "--yes"])
+            .assert()
+    };
+
+    gw_rm("no-worktree")
+        .failure()
+        .stderr(predicate::str::contains("neither a path nor a branch"));
+    gw_rm("feat/login").success();
+    assert!(!wt.exists());
+    assert!(
+        !git_stdout(&repo, &["worktree", "list"]).contains("feat/login"),
+        "worktree still registered"
+    );
+}
+
+#[test]
+fn remove_runs_pre_remove_hooks_and_aborts_when_one_fails() {
+    // spec: GW-RM-013
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let feat = td.path().join("feat");
+    let keep = td.path().join("keep");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "feat", feat.to_str().unwrap()],
+    );
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "keep", keep.to_str().unwrap()],
+    );
+
+    let log = td.path().join("removed.log");
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(&cfg_dir).unwrap();
+    std::fs::write(
+        cfg_dir.join("config.toml"),
+        format!(
+            r#"[[hooks]]
+command = "test -f README.md && echo \"$GW_BRANCH $GW_WORKTREE_PATH\" >> '{}' && test \"$GW_BRANCH\" != keep"
+when = "pre_remove"
+"#,
+            log.display()
+        ),
+    )
+    .unwrap();
+
+    let (feat_canon, keep_canon) = (feat.canonicalize().unwrap(), keep.canonicalize().unwrap());
+    let gw_rm = |wt: &Path| {
+        Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+            .current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args(["rm", wt.to_str().unwrap(), "--yes"])
+            .assert()
+    };
+
+    gw_rm(&feat).success();
+    assert!(!feat.exists());
+
+    gw_rm(&keep)
+        .failure()
+        .stderr(predicate::str::contains("hook failed"));
+    assert!(keep.join("README.md").exists());
+
+    assert_eq!(
+        std::fs::read_to_string(&log).unwrap(),
+        format!(
+            "feat {}\nkeep {}\n",
+            feat_canon.display(),
+            keep_canon.display()
+        )
+    );
+}
+
+#[test]
+fn remove_deletes_branch_only_when_asked() {
+    // spec: GW-RM-014
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let gw_rm = |wt: &Path, extra: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", td.path().join("cfg"))
+            .args(["rm", wt.to_str().unwrap(), "--yes"])
+            .args(extra)
+            .assert()
+    };
+    let has_branch = |b: &str| !git_stdout(&repo, &["branch", "--list", b]).is_empty();
+
+    let kept = td.path().join("kept");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "kept", kept.to_str().unwrap()],
+    );
+    gw_rm(&kept, &["--keep-branch"]).success();
+    assert!(!kept.exists());
+    assert!(has_branch("kept"));
+
+    let gone = td.path().join("gone");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "gone", gone.to_str().unwrap()],
+    );
+    gw_rm(&gone, &["--delete-branch"])
+        .success()
+        .stderr(predicate::str::contains("deleted branch gone"));
+    assert!(!gone.exists());
+    assert!(!has_branch("gone"));
+
+    // A branch checked out in another worktree too is left alone, and so is the worktree.
+    let shared = td.path().join("shared");
+    let twin = td.path().join("twin");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "shared", shared.to_str().unwrap()],
+    );
+    run_git(
+        &repo,
+        &["worktree", "add", "-f", twin.to_str().unwrap(), "shared"],
+    );
+    gw_rm(&shared, &["--delete-branch"])
+        .failure()
+        .stderr(predicate::str::contains("refusing to delete branch shared"));
+    assert!(shared.exists());
+    assert!(has_branch("shared"));
+
+    gw_rm(&twin, &["--delete-branch", "--keep-branch"]).failure();
+}
+
+#[test]
+fn remove_archives_uncommitted_changes_first() {
+    // spec: GW-RM-015
+    let td = TempDir::new().unwrap();
+    let repo = td.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let wt = td.path().join("wt");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "feat/x", wt.to_str().unwrap()],
+    );
+    std::fs::write(wt.join("README.md"), "hi\nwork in progress\n").unwrap();
+    std::fs::write(wt.join("notes.txt"), "scratch\n").unwrap();
+
+    let archive = td.path().join("archive");
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .env("GW_CONFIG_DIR", td.path().join("cfg"))
+        .args(["rm", wt.to_str().unwrap(), "--yes", "--archive"])
+        .arg(&archive)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("archived uncommitted changes"));
+    assert!(!wt.exists());
+
+    let mut files: Vec<_> = std::fs::read_dir(&archive)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .collect();
+    files.sort();
+    assert_eq!(files.len(), 2, "{files:?}");
+    let (patch, tar) = (&files[0], &files[1]);
+    let name = patch.file_name().unwrap().to_str().unwrap();
+    assert!(
+        name.starts_with("feat-x-") && name.ends_with(".patch"),
+        "{name}"
+    );
+    assert_eq!(tar.with_extension("patch"), *patch);
+
+    let patch = std::fs::read_to_string(patch).unwrap();
+    assert!(patch.contains("+work in progress"), "{patch}");
+    let listing = StdCommand::new("tar").arg("-tf").arg(tar).output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&listing.stdout).trim(), "notes.txt");
+
+    // A clean worktree leaves nothing behind.
+    let clean = td.path().join("clean");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "clean", clean.to_str().unwrap()],
+    );
+    let empty = td.path().join("empty");
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .env("GW_CONFIG_DIR", td.path().join("cfg"))
+        .args(["rm", clean.to_str().unwrap(), "--yes", "--archive"])
+        .arg(&empty)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("nothing to archive"));
+    assert!(!clean.exists());
+    assert!(!empty.exists());
+}
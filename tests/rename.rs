@@ -0,0 +1,117 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command as StdCommand;
+use tempfile::TempDir;
+
+fn run_git(cwd: &Path, args: &[&str]) {
+    let status = StdCommand::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .status()
+        .expect("failed to run git");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+fn git_stdout(cwd: &Path, args: &[&str]) -> String {
+    let out = StdCommand::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .output()
+        .expect("failed to run git");
+    assert!(out.status.success(), "git {:?} failed", args);
+    String::from_utf8(out.stdout).unwrap()
+}
+
+fn repo_config_file(cfg_dir: &Path) -> std::path::PathBuf {
+    let repos = cfg_dir.join("repos");
+    let entry = std::fs::read_dir(&repos)
+        .unwrap()
+        .next()
+        .expect("no repo config written")
+        .unwrap();
+    entry.path().join("config.toml")
+}
+
+#[test]
+fn rename_moves_branch_and_worktree() {
+    // spec: GW-RENAME-001, GW-RENAME-002
+    let td = TempDir::new().unwrap();
+    let root = std::fs::canonicalize(td.path()).unwrap();
+    let repo = root.join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+
+    let cfg_dir = root.join("cfg");
+    let gw = |cwd: &Path, args: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(cwd)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .env_remove("GW_WORKTREES_DIR")
+            .args(args)
+            .assert()
+    };
+    let worktrees = root.join("worktrees");
+    gw(
+        &repo,
+        &[
+            "new",
+            "feat/a",
+            "--worktrees-dir",
+            worktrees.to_str().unwrap(),
+        ],
+    )
+    .success();
+    let old = worktrees.join("repo").join("feat").join("a");
+    assert!(old.join("README.md").exists());
+    run_git(&repo, &["branch", "taken"]);
+
+    gw(&repo, &["rename", "feat/a", "taken"])
+        .failure()
+        .stderr(predicate::str::contains("branch taken already exists"));
+    gw(&repo, &["rename", "nope", "other"])
+        .failure()
+        .stderr(predicate::str::contains("no branch named nope"));
+    assert!(old.exists());
+
+    // Run from inside the worktree: the new path is printed for the shell wrapper to follow.
+    let new = worktrees.join("repo").join("feat").join("b");
+    gw(&old, &["rename", "feat/a", "feat/b"])
+        .success()
+        .stdout(format!("{}\n", new.display()))
+        .stderr(predicate::str::contains("renamed branch feat/a to feat/b"));
+    assert!(!old.exists());
+    assert!(new.join("README.md").exists());
+    assert_eq!(
+        git_stdout(&new, &["branch", "--show-current"]).trim(),
+        "feat/b"
+    );
+    assert!(git_stdout(&repo, &["branch", "--list", "feat/a"]).is_empty());
+
+    // The anchor pointed at the moved worktree (gw new made it the anchor) and follows it.
+    let cfg = std::fs::read_to_string(repo_config_file(&cfg_dir)).unwrap();
+    assert!(
+        cfg.contains(&format!("anchor_path = \"{}\"", new.display())),
+        "{cfg}"
+    );
+
+    // Run from elsewhere, nothing is printed; a branch without a worktree is just renamed, and
+    // the picker's remembered branch follows it.
+    let cfg = cfg.replace("last_branch = \"feat/b\"\n", "");
+    std::fs::write(
+        repo_config_file(&cfg_dir),
+        format!("last_branch = \"taken\"\n{cfg}"),
+    )
+    .unwrap();
+    gw(&repo, &["rename", "taken", "free"])
+        .success()
+        .stdout(predicate::str::is_empty());
+    assert!(!git_stdout(&repo, &["branch", "--list", "free"]).is_empty());
+    let cfg = std::fs::read_to_string(repo_config_file(&cfg_dir)).unwrap();
+    assert!(cfg.contains("last_branch = \"free\""), "{cfg}");
+}
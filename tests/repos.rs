@@ -0,0 +1,96 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command as StdCommand;
+use std::time::{Duration, SystemTime};
+use tempfile::TempDir;
+
+fn run_git(cwd: &Path, args: &[&str]) {
+    let status = StdCommand::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .status()
+        .expect("failed to run git");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+fn write_registration(cfg_dir: &Path, hash: &str, body: &str, age_secs: u64) {
+    let dir = cfg_dir.join("repos").join(hash);
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("config.toml");
+    std::fs::write(&path, body).unwrap();
+    std::fs::File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(SystemTime::now() - Duration::from_secs(age_secs))
+        .unwrap();
+}
+
+#[test]
+fn repos_prune_duplicates_collapses_registrations() {
+    // spec: GW-REPOS-001, GW-REPOS-002
+    let td = TempDir::new().unwrap();
+    let root = std::fs::canonicalize(td.path()).unwrap();
+    let repo = root.join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    run_git(&repo, &["init"]);
+    let cfg_dir = root.join("cfg");
+
+    // The same repo registered twice, once via a non-canonical git dir path.
+    write_registration(
+        &cfg_dir,
+        "stale",
+        &format!(
+            "repo_name = \"repo\"\ngit_common_dir = \"{}/./.git\"\nanchor_path = \"/old/anchor\"\nhooks = [{{ command = \"echo old\" }}, {{ command = \"echo both\" }}]\n",
+            repo.display()
+        ),
+        3600,
+    );
+    write_registration(
+        &cfg_dir,
+        "newer",
+        &format!(
+            "repo_name = \"repo\"\ngit_common_dir = \"{}/.git\"\nanchor_path = \"{}\"\nhooks = [{{ command = \"echo both\" }}, {{ command = \"echo new\" }}]\n",
+            repo.display(),
+            repo.display()
+        ),
+        0,
+    );
+
+    let gw = || {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo).env("GW_CONFIG_DIR", &cfg_dir);
+        cmd
+    };
+
+    gw().args(["repos"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("/old/anchor"));
+
+    gw().args(["repos", "--prune-duplicates"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("merged 2 registrations of repo"));
+
+    let dirs: Vec<_> = std::fs::read_dir(cfg_dir.join("repos")).unwrap().collect();
+    assert_eq!(dirs.len(), 1);
+    gw().args(["repos"]).assert().success().stdout(format!(
+        "repo\t{}\t{}/.git\n",
+        repo.display(),
+        repo.display()
+    ));
+    // The survivor is the config gw finds for the repo, with hooks from both.
+    gw().args(["hooks"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "repo: echo both\nrepo: echo new\nrepo: echo old\n",
+        ));
+
+    gw().args(["repos", "--prune-duplicates"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("no duplicate repo registrations"));
+}
@@ -0,0 +1,56 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn session_save_and_open_round_trips_paths() {
+    // spec: GW-SESSION-001, GW-SESSION-002, GW-SESSION-003
+    let td = TempDir::new().unwrap();
+    let cfg_dir = td.path().join("cfg");
+    let a = td.path().join("api-feat");
+    let b = td.path().join("web-feat");
+    std::fs::create_dir_all(&a).unwrap();
+    std::fs::create_dir_all(&b).unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.env("GW_CONFIG_DIR", &cfg_dir)
+        .args([
+            "session",
+            "save",
+            "feat",
+            b.to_str().unwrap(),
+            a.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+    assert!(cfg_dir.join("sessions").join("feat.toml").exists());
+
+    let out = Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["session", "open", "feat"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let expected = format!(
+        "{}\n{}\n",
+        std::fs::canonicalize(&b).unwrap().display(),
+        std::fs::canonicalize(&a).unwrap().display()
+    );
+    assert_eq!(String::from_utf8(out).unwrap(), expected);
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["session", "list"])
+        .assert()
+        .success()
+        .stdout("feat\n");
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["session", "open", "missing"])
+        .assert()
+        .failure();
+}
@@ -0,0 +1,93 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command as StdCommand;
+use tempfile::TempDir;
+
+fn run_git(cwd: &Path, args: &[&str]) {
+    let status = StdCommand::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .status()
+        .expect("failed to run git");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+fn init_repo(repo: &Path) {
+    std::fs::create_dir_all(repo).unwrap();
+    run_git(repo, &["init"]);
+    run_git(repo, &["config", "user.email", "gw@example.com"]);
+    run_git(repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(repo, &["add", "."]);
+    run_git(repo, &["commit", "-m", "init"]);
+}
+
+fn gw_new(repo: &Path, cfg_dir: &Path, worktrees_dir: &Path, branch: &str) {
+    let status = StdCommand::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(repo)
+        .env("GW_CONFIG_DIR", cfg_dir)
+        .args([
+            "new",
+            branch,
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+            "--no-hooks",
+        ])
+        .status()
+        .expect("failed to run gw new");
+    assert!(status.success());
+}
+
+#[test]
+fn stats_reports_worktree_and_dirty_counts_per_repo() {
+    // spec: GW-STATS-001, GW-STATS-002
+    let td = TempDir::new().unwrap();
+    let alpha = td.path().join("alpha");
+    let beta = td.path().join("beta");
+    init_repo(&alpha);
+    init_repo(&beta);
+
+    let cfg_dir = td.path().join("cfg");
+    let worktrees_dir = td.path().join("worktrees");
+
+    // alpha: main + 1 worktree, clean.
+    gw_new(&alpha, &cfg_dir, &worktrees_dir, "a1");
+    // beta: main + 2 worktrees, one dirty.
+    gw_new(&beta, &cfg_dir, &worktrees_dir, "b1");
+    gw_new(&beta, &cfg_dir, &worktrees_dir, "b2");
+    std::fs::write(
+        worktrees_dir.join("beta").join("b2").join("wip.txt"),
+        "wip\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["stats"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("alpha\tworktrees=2\tdirty=0"))
+        .stdout(predicate::str::contains("beta\tworktrees=3\tdirty=1"))
+        .stdout(predicate::str::contains(
+            "total\trepos=2\tworktrees=5\tdirty=1",
+        ));
+
+    let out = StdCommand::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["stats", "--json"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(v["total"]["worktrees"], 5);
+    assert_eq!(v["total"]["dirty"], 1);
+    let beta_stats = v["repos"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r["name"] == "beta")
+        .unwrap();
+    assert_eq!(beta_stats["worktrees"], 3);
+    assert_eq!(beta_stats["dirty"], 1);
+}
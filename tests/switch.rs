@@ -0,0 +1,73 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command as StdCommand;
+use tempfile::TempDir;
+
+fn run_git(cwd: &Path, args: &[&str]) {
+    let status = StdCommand::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .status()
+        .expect("failed to run git");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn switch_jumps_to_existing_worktree_or_creates_it() {
+    // spec: GW-SWITCH-001, GW-SWITCH-002
+    let td = TempDir::new().unwrap();
+    let root = std::fs::canonicalize(td.path()).unwrap();
+    let repo = root.join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+    let existing = root.join("elsewhere");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "feat", existing.to_str().unwrap()],
+    );
+
+    let cfg_dir = root.join("cfg");
+    let worktrees_dir = root.join("worktrees");
+    let gw_switch = |branch: &str| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+        cmd.current_dir(&repo)
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args(["switch", branch])
+            .assert()
+    };
+
+    gw_switch("feat")
+        .success()
+        .stdout(format!("{}\n", existing.display()));
+    assert!(!worktrees_dir.exists());
+
+    // No worktrees dir is configured yet; seed one so creation doesn't prompt.
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args([
+            "new",
+            "seed",
+            "--worktrees-dir",
+            worktrees_dir.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    let created = worktrees_dir.join("repo").join("fix");
+    gw_switch("fix")
+        .success()
+        .stdout(format!("{}\n", created.display()))
+        .stderr(predicate::str::contains("creating"));
+    assert!(created.exists());
+
+    gw_switch("fix")
+        .success()
+        .stdout(format!("{}\n", created.display()))
+        .stderr(predicate::str::contains("checked out in"));
+}
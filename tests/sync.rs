@@ -0,0 +1,187 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command as StdCommand;
+use tempfile::TempDir;
+
+fn run_git(cwd: &Path, args: &[&str]) {
+    let status = StdCommand::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .status()
+        .expect("failed to run git");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+fn git_out(cwd: &Path, args: &[&str]) -> String {
+    let out = StdCommand::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .output()
+        .expect("failed to run git");
+    assert!(out.status.success(), "git {:?} failed", args);
+    String::from_utf8(out.stdout).unwrap().trim().to_string()
+}
+
+#[test]
+fn sync_fast_forwards_worktrees_and_skips_dirty_ones() {
+    // spec: GW-SYNC-001, GW-SYNC-002
+    let td = TempDir::new().unwrap();
+    let origin = td.path().join("origin");
+    std::fs::create_dir_all(&origin).unwrap();
+    run_git(&origin, &["init"]);
+    run_git(&origin, &["config", "user.email", "gw@example.com"]);
+    run_git(&origin, &["config", "user.name", "gw"]);
+    std::fs::write(origin.join("README.md"), "hi\n").unwrap();
+    run_git(&origin, &["add", "."]);
+    run_git(&origin, &["commit", "-m", "init"]);
+    run_git(&origin, &["branch", "feat"]);
+    run_git(&origin, &["branch", "stale"]);
+
+    run_git(td.path(), &["clone", "-q", "origin", "clone"]);
+    let clone = td.path().join("clone");
+    let feat = td.path().join("feat");
+    let stale = td.path().join("stale");
+    run_git(
+        &clone,
+        &[
+            "worktree",
+            "add",
+            "--track",
+            "-b",
+            "feat",
+            feat.to_str().unwrap(),
+            "origin/feat",
+        ],
+    );
+    run_git(
+        &clone,
+        &[
+            "worktree",
+            "add",
+            "--track",
+            "-b",
+            "stale",
+            stale.to_str().unwrap(),
+            "origin/stale",
+        ],
+    );
+
+    // Upstream of `feat` and `stale` moves on; `stale` has local edits.
+    run_git(&origin, &["checkout", "-q", "feat"]);
+    std::fs::write(origin.join("feat.txt"), "feat\n").unwrap();
+    run_git(&origin, &["add", "."]);
+    run_git(&origin, &["commit", "-m", "feat work"]);
+    run_git(&origin, &["checkout", "-q", "stale"]);
+    std::fs::write(origin.join("stale.txt"), "stale\n").unwrap();
+    run_git(&origin, &["add", "."]);
+    run_git(&origin, &["commit", "-m", "stale work"]);
+    std::fs::write(stale.join("README.md"), "local edit\n").unwrap();
+
+    let before = git_out(&feat, &["rev-parse", "--short", "HEAD"]);
+    let after = git_out(&origin, &["rev-parse", "--short", "feat"]);
+    let stale_head = git_out(&stale, &["rev-parse", "HEAD"]);
+
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&clone)
+        .env("GW_CONFIG_DIR", td.path().join("cfg"))
+        .args(["sync", "--jobs", "2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "{}\tfeat\tadvanced {before}..{after}\n",
+            feat.display()
+        )))
+        .stdout(predicate::str::contains(format!(
+            "{}\tstale\tskipped-dirty\n",
+            stale.display()
+        )))
+        .stdout(predicate::str::contains("\tup-to-date\n"))
+        .stderr(predicate::str::contains("uncommitted changes"));
+
+    assert!(feat.join("feat.txt").exists());
+    assert_eq!(git_out(&stale, &["rev-parse", "HEAD"]), stale_head);
+}
+
+#[test]
+fn sync_reports_branches_that_cannot_fast_forward() {
+    // spec: GW-SYNC-001
+    let td = TempDir::new().unwrap();
+    let origin = td.path().join("origin");
+    std::fs::create_dir_all(&origin).unwrap();
+    run_git(&origin, &["init"]);
+    run_git(&origin, &["config", "user.email", "gw@example.com"]);
+    run_git(&origin, &["config", "user.name", "gw"]);
+    std::fs::write(origin.join("README.md"), "hi\n").unwrap();
+    run_git(&origin, &["add", "."]);
+    run_git(&origin, &["commit", "-m", "init"]);
+
+    run_git(td.path(), &["clone", "-q", "origin", "clone"]);
+    let clone = td.path().join("clone");
+    run_git(&clone, &["config", "user.email", "gw@example.com"]);
+    run_git(&clone, &["config", "user.name", "gw"]);
+
+    std::fs::write(origin.join("a.txt"), "a\n").unwrap();
+    run_git(&origin, &["add", "."]);
+    run_git(&origin, &["commit", "-m", "upstream"]);
+    std::fs::write(clone.join("b.txt"), "b\n").unwrap();
+    run_git(&clone, &["add", "."]);
+    run_git(&clone, &["commit", "-m", "local"]);
+
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&clone)
+        .env("GW_CONFIG_DIR", td.path().join("cfg"))
+        .args(["sync"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("\tfailed\n"))
+        .stderr(predicate::str::contains("couldn't be fast-forwarded"));
+}
+
+#[test]
+fn sync_all_repos_covers_registered_repos_outside_any_repo() {
+    // spec: GW-SYNC-003
+    let td = TempDir::new().unwrap();
+    let origin = td.path().join("origin");
+    std::fs::create_dir_all(&origin).unwrap();
+    run_git(&origin, &["init"]);
+    run_git(&origin, &["config", "user.email", "gw@example.com"]);
+    run_git(&origin, &["config", "user.name", "gw"]);
+    std::fs::write(origin.join("README.md"), "hi\n").unwrap();
+    run_git(&origin, &["add", "."]);
+    run_git(&origin, &["commit", "-m", "init"]);
+
+    run_git(td.path(), &["clone", "-q", "origin", "clone"]);
+    let clone = td.path().join("clone");
+    let cfg_dir = td.path().join("cfg");
+    // Registers the clone with gw.
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&clone)
+        .env("GW_CONFIG_DIR", &cfg_dir)
+        .args([
+            "new",
+            "scratch",
+            "--worktrees-dir",
+            td.path().join("worktrees").to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    std::fs::write(origin.join("a.txt"), "a\n").unwrap();
+    run_git(&origin, &["add", "."]);
+    run_git(&origin, &["commit", "-m", "upstream"]);
+
+    for args in [
+        vec!["sync", "--all-repos", "--jobs", "1"],
+        vec!["sync", "--repo", "clone"],
+    ] {
+        Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+            .current_dir(td.path())
+            .env("GW_CONFIG_DIR", &cfg_dir)
+            .args(&args)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(clone.display().to_string()));
+    }
+    assert!(clone.join("a.txt").exists());
+}
@@ -0,0 +1,42 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+#[test]
+fn uninit_prints_removal_snippet() {
+    // spec: GW-INIT-005
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.args(["uninit", "zsh"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("unset -f gw"));
+
+    // Without a shell argument it follows `$SHELL`, like `gw init`.
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .env("SHELL", "/bin/bash")
+        .arg("uninit")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(bash)"))
+        .stdout(predicate::str::contains("~/.bashrc"));
+}
+
+#[test]
+fn uninit_purge_removes_config_root() {
+    // spec: GW-INIT-006
+    let td = TempDir::new().unwrap();
+    let cfg_dir = td.path().join("cfg");
+    std::fs::create_dir_all(cfg_dir.join("repos").join("abc")).unwrap();
+    std::fs::write(cfg_dir.join("config.toml"), "").unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("gw"));
+    cmd.env("GW_CONFIG_DIR", &cfg_dir)
+        .args(["uninit", "--purge", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("unset -f gw"))
+        .stderr(predicate::str::contains("removed"));
+
+    assert!(!cfg_dir.exists());
+    assert!(td.path().exists());
+}
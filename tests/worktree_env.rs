@@ -0,0 +1,82 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command as StdCommand;
+use tempfile::TempDir;
+
+fn run_git(cwd: &Path, args: &[&str]) {
+    let status = StdCommand::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .status()
+        .expect("failed to run git");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn worktree_env_exports_branch_and_paths() {
+    // spec: GW-ENV-001
+    let td = TempDir::new().unwrap();
+    let root = std::fs::canonicalize(td.path()).unwrap();
+    let repo = root.join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    run_git(&repo, &["init"]);
+    run_git(&repo, &["config", "user.email", "gw@example.com"]);
+    run_git(&repo, &["config", "user.name", "gw"]);
+    std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "init"]);
+    let wt = root.join("my wt");
+    run_git(
+        &repo,
+        &["worktree", "add", "-b", "feat/x", wt.to_str().unwrap()],
+    );
+
+    let out = Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&wt)
+        .env("GW_CONFIG_DIR", root.join("cfg"))
+        .arg("worktree-env")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let out = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            format!("export GW_WORKTREE_PATH='{}'", wt.display()),
+            "export GW_BRANCH=feat/x".to_string(),
+            format!("export GW_REPO_ROOT='{}'", wt.display()),
+        ]
+    );
+
+    // Like `gw new`'s hooks, GW_REPO_ROOT is the worktree gw runs in.
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&repo)
+        .env("GW_CONFIG_DIR", root.join("cfg"))
+        .args(["worktree-env", wt.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "export GW_WORKTREE_PATH='{}'\n",
+            wt.display()
+        )))
+        .stdout(predicate::str::contains(format!(
+            "export GW_REPO_ROOT={}\n",
+            repo.display()
+        )));
+
+    // An explicit path works from anywhere.
+    Command::new(assert_cmd::cargo::cargo_bin!("gw"))
+        .current_dir(&root)
+        .env("GW_CONFIG_DIR", root.join("cfg"))
+        .args(["worktree-env", repo.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "export GW_WORKTREE_PATH={}\n",
+            repo.display()
+        )));
+}